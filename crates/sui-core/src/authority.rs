@@ -63,6 +63,7 @@ use sui_config::node::{
     AuthorityStorePruningConfig, DBCheckpointConfig, ExpensiveSafetyCheckConfig,
 };
 use sui_config::transaction_deny_config::TransactionDenyConfig;
+use sui_execution::{ChangeEpochParams, GasEstimate};
 use sui_framework::{BuiltInFramework, SystemPackage};
 use sui_json_rpc_types::{
     DevInspectResults, DryRunTransactionBlockResponse, EventFilter, SuiEvent, SuiMoveValue,
@@ -1496,32 +1497,31 @@ impl AuthorityState {
         };
 
         let protocol_config = epoch_store.protocol_config();
-        let (kind, signer, _) = transaction.execution_parts();
 
         let silent = true;
         let executor = sui_execution::executor(protocol_config, silent)
             .expect("Creating an executor should not fail here");
 
-        let expensive_checks = false;
-        let (inner_temp_store, effects, _execution_error) = executor
-            .execute_transaction_to_effects(
-                &self.database,
-                protocol_config,
-                self.metrics.limits_metrics.clone(),
-                expensive_checks,
-                self.certificate_deny_config.certificate_deny_set(),
-                &epoch_store.epoch_start_config().epoch_data().epoch_id(),
-                epoch_store
-                    .epoch_start_config()
-                    .epoch_data()
-                    .epoch_start_timestamp(),
-                checked_input_objects,
-                gas_object_refs,
-                gas_status,
-                kind,
-                signer,
-                transaction_digest,
-            );
+        let GasEstimate {
+            effects,
+            inner_temporary_store: inner_temp_store,
+        } = executor.estimate_gas_for_transaction(
+            &self.database,
+            protocol_config,
+            self.metrics.limits_metrics.clone(),
+            self.certificate_deny_config.certificate_deny_set(),
+            &epoch_store.epoch_start_config().epoch_data().epoch_id(),
+            epoch_store
+                .epoch_start_config()
+                .epoch_data()
+                .epoch_start_timestamp(),
+            checked_input_objects,
+            gas_object_refs,
+            gas_status,
+            transaction.kind().clone(),
+            transaction.sender(),
+            transaction_digest,
+        );
         let tx_digest = *effects.transaction_digest();
 
         let module_cache =
@@ -4173,34 +4173,17 @@ impl AuthorityState {
             ));
         };
 
-        let tx = if epoch_store
-            .protocol_config()
-            .end_of_epoch_transaction_supported()
-        {
-            txns.push(EndOfEpochTransactionKind::new_change_epoch(
-                next_epoch,
-                next_epoch_protocol_version,
-                gas_cost_summary.storage_cost,
-                gas_cost_summary.computation_cost,
-                gas_cost_summary.storage_rebate,
-                gas_cost_summary.non_refundable_storage_fee,
-                epoch_start_timestamp_ms,
-                next_epoch_system_package_bytes,
-            ));
-
-            VerifiedTransaction::new_end_of_epoch_transaction(txns)
-        } else {
-            VerifiedTransaction::new_change_epoch(
+        let tx = epoch_store.executor().advance_epoch_transaction(
+            epoch_store.protocol_config(),
+            txns,
+            ChangeEpochParams {
                 next_epoch,
-                next_epoch_protocol_version,
-                gas_cost_summary.storage_cost,
-                gas_cost_summary.computation_cost,
-                gas_cost_summary.storage_rebate,
-                gas_cost_summary.non_refundable_storage_fee,
+                next_protocol_version: next_epoch_protocol_version,
+                gas_cost_summary: gas_cost_summary.clone(),
                 epoch_start_timestamp_ms,
-                next_epoch_system_package_bytes,
-            )
-        };
+                system_packages: next_epoch_system_package_bytes,
+            },
+        );
 
         let executable_tx = VerifiedExecutableTransaction::new_from_checkpoint(
             tx.clone(),
@@ -595,15 +595,19 @@ impl<T, C> ConsensusHandler<T, C> {
         random_bytes: Vec<u8>,
     ) -> VerifiedExecutableTransaction {
         assert!(self.epoch_store.randomness_state_enabled());
-        let transaction = VerifiedTransaction::new_randomness_state_update(
-            self.epoch(),
-            randomness_round,
-            random_bytes,
-            self.epoch_store
-                .epoch_start_config()
-                .randomness_obj_initial_shared_version()
-                .expect("randomness state obj must exist"),
-        );
+        let transaction = self
+            .epoch_store
+            .executor()
+            .randomness_state_update_transaction(
+                self.epoch(),
+                randomness_round,
+                random_bytes,
+                self.epoch_store
+                    .epoch_start_config()
+                    .randomness_obj_initial_shared_version()
+                    .expect("randomness state obj must exist"),
+            )
+            .expect("randomness state updates must be supported when randomness is enabled");
         debug!(
             "created randomness state update transaction: {:?}",
             transaction.digest()
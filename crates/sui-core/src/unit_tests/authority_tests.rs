@@ -294,6 +294,69 @@ async fn test_dry_run_no_gas_big_transfer() {
     assert_eq!(*dry_run_res.effects.status(), SuiExecutionStatus::Success);
 }
 
+#[tokio::test]
+async fn test_dry_run_gas_estimate_matches_execution() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (validator, fullnode) = init_state_validator_with_fullnode().await;
+    let (validator, object_basics) = publish_object_basics(validator).await;
+    let (fullnode, _object_basics) = publish_object_basics(fullnode).await;
+    let gas_object = Object::with_id_owner_for_testing(gas_object_id, sender);
+    let gas_object_ref = gas_object.compute_object_reference();
+    validator.insert_genesis_object(gas_object.clone()).await;
+    fullnode.insert_genesis_object(gas_object).await;
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let value = builder.pure(16u64).unwrap();
+    let recipient = builder.pure(sender).unwrap();
+    builder.command(Command::move_call(
+        object_basics.0,
+        Identifier::new("object_basics").unwrap(),
+        Identifier::new("create").unwrap(),
+        vec![],
+        vec![value, recipient],
+    ));
+
+    let rgp = fullnode.reference_gas_price_for_testing().unwrap();
+    let data = TransactionData::new_programmable(
+        sender,
+        vec![gas_object_ref],
+        builder.finish(),
+        rgp * TEST_ONLY_GAS_UNIT_FOR_OBJECT_BASICS,
+        rgp,
+    );
+    let transaction = to_sender_signed_transaction(data.clone(), &sender_key);
+
+    let (estimate, _, _, _) = fullnode
+        .dry_exec_transaction(data, *transaction.digest())
+        .await
+        .unwrap();
+    assert_eq!(*estimate.effects.status(), SuiExecutionStatus::Success);
+
+    let executed = send_and_confirm_transaction_(&validator, Some(&fullnode), transaction, false)
+        .await
+        .unwrap()
+        .1
+        .into_data();
+    assert_eq!(executed.status(), &ExecutionStatus::Success);
+    assert_eq!(
+        estimate.effects.gas_cost_summary(),
+        executed.gas_cost_summary(),
+    );
+
+    // Every transaction is charged at least the base transaction cost.
+    let protocol_config = fullnode.epoch_store_for_testing().protocol_config().clone();
+    let parameters = sui_execution::executor(&protocol_config, true)
+        .unwrap()
+        .reference_gas_parameters(&protocol_config);
+    let base_cost = if parameters.base_tx_cost_as_multiplier {
+        parameters.base_tx_cost_fixed * rgp
+    } else {
+        parameters.base_tx_cost_fixed
+    };
+    assert!(executed.gas_cost_summary().computation_cost >= base_cost);
+}
+
 #[tokio::test]
 async fn test_dev_inspect_object_by_bytes() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -327,9 +390,12 @@ async fn test_dev_inspect_object_by_bytes() {
     assert_eq!(results.len(), 1);
     let exec_results = results.pop().unwrap();
     let SuiExecutionResult {
+        gas_used,
         mutable_reference_outputs,
         return_values,
     } = exec_results;
+    // the command's gas is reported alongside its results
+    assert!(gas_used > 0);
     assert!(mutable_reference_outputs.is_empty());
     assert!(return_values.is_empty());
     let dev_inspect_gas_summary = effects.gas_cost_summary().clone();
@@ -398,6 +464,7 @@ async fn test_dev_inspect_object_by_bytes() {
     let SuiExecutionResult {
         mutable_reference_outputs,
         return_values,
+        ..
     } = exec_results;
     assert_eq!(mutable_reference_outputs.len(), 1);
     assert!(return_values.is_empty());
@@ -502,6 +569,7 @@ async fn test_dev_inspect_unowned_object() {
     let SuiExecutionResult {
         mutable_reference_outputs,
         return_values,
+        ..
     } = exec_results;
     assert_eq!(mutable_reference_outputs.len(), 1);
     assert!(return_values.is_empty());
@@ -614,6 +682,7 @@ async fn test_dev_inspect_dynamic_field() {
     let SuiExecutionResult {
         mutable_reference_outputs,
         return_values,
+        ..
     } = exec_results;
     assert_eq!(mutable_reference_outputs.len(), 1);
     assert!(return_values.is_empty());
@@ -677,6 +746,7 @@ async fn test_dev_inspect_return_values() {
     let SuiExecutionResult {
         mutable_reference_outputs,
         mut return_values,
+        ..
     } = exec_results;
     assert_eq!(mutable_reference_outputs.len(), 1);
     assert_eq!(return_values.len(), 1);
@@ -704,6 +774,7 @@ async fn test_dev_inspect_return_values() {
     let SuiExecutionResult {
         mutable_reference_outputs,
         mut return_values,
+        ..
     } = exec_results;
     assert!(mutable_reference_outputs.is_empty());
     assert_eq!(return_values.len(), 1);
@@ -731,6 +802,7 @@ async fn test_dev_inspect_return_values() {
     let SuiExecutionResult {
         mutable_reference_outputs,
         mut return_values,
+        ..
     } = exec_results;
     assert!(mutable_reference_outputs.is_empty());
     assert_eq!(return_values.len(), 1);
@@ -785,6 +857,7 @@ async fn test_dev_inspect_return_values() {
     let SuiExecutionResult {
         mutable_reference_outputs,
         mut return_values,
+        ..
     } = exec_results;
     assert!(mutable_reference_outputs.is_empty());
     assert_eq!(return_values.len(), 1);
@@ -826,6 +899,7 @@ async fn test_dev_inspect_gas_coin_argument() {
     let SuiExecutionResult {
         mutable_reference_outputs,
         return_values,
+        ..
     } = &results[0];
     // check argument is the gas coin updated
     assert_eq!(mutable_reference_outputs.len(), 1);
@@ -841,6 +915,7 @@ async fn test_dev_inspect_gas_coin_argument() {
     let SuiExecutionResult {
         mutable_reference_outputs,
         return_values,
+        ..
     } = &results[1];
     assert!(mutable_reference_outputs.is_empty());
     assert!(return_values.is_empty());
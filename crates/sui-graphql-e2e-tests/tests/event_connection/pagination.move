@@ -51,7 +51,7 @@ module Test::M1 {
 
 //# run-graphql --variables A
 {
-  eventConnection(first: 2 after: "2:0", filter: {sender: $A}) {
+  eventConnection(first: 2 after: "AAICAAAAAAAAAAAAAAAAAAAAAA==", filter: {sender: $A}) {
     edges {
       cursor
       node {
@@ -73,7 +73,7 @@ module Test::M1 {
 
 //# run-graphql --variables A
 {
-  eventConnection(last: 2 before: "3:1", filter: {sender: $A}) {
+  eventConnection(last: 2 before: "AAIDAAAAAAAAAAEAAAAAAAAAAA==", filter: {sender: $A}) {
     edges {
       cursor
       node {
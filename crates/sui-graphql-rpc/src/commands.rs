@@ -40,6 +40,9 @@ pub enum Command {
         /// DB URL for data fetching
         #[clap(short, long)]
         db_url: Option<String>,
+        /// DB URL of a read replica to spread reads across (can be repeated)
+        #[clap(long = "db-replica-url")]
+        db_replica_urls: Vec<String>,
         /// Port to bind the server to
         #[clap(short, long)]
         port: Option<u16>,
@@ -4,8 +4,13 @@
 use crate::{error::Error as SuiGraphQLError, types::big_int::BigInt};
 use async_graphql::*;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeSet, path::PathBuf, time::Duration};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+    time::Duration,
+};
 use sui_json_rpc::name_service::NameServiceConfig;
+use sui_protocol_config::Chain;
 
 use crate::functional_group::FunctionalGroup;
 
@@ -16,9 +21,21 @@ const MAX_QUERY_PAYLOAD_SIZE: u32 = 2_000;
 const MAX_DB_QUERY_COST: u64 = 20_000; // Max DB query cost (normally f64) truncated
 const DEFAULT_PAGE_SIZE: u64 = 20; // Default number of elements allowed on a page of a connection
 const MAX_PAGE_SIZE: u64 = 50; // Maximum number of elements allowed on a page of a connection
+const MAX_OWNERSHIP_DEPTH: u32 = 3; // Maximum levels of object ownership expanded by nested queries
+const MAX_MULTI_GET_SIZE: u64 = 50; // Maximum number of keys in one batch lookup
 
 const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 40_000;
 
+// Default per-client rate limits.
+const DEFAULT_REQUESTS_PER_SECOND: u32 = 50;
+const DEFAULT_REQUEST_BURST: u32 = 100;
+const DEFAULT_QUERY_NODES_PER_SECOND: u32 = 1_000;
+const DEFAULT_QUERY_NODE_BURST: u32 = 2_000;
+
+// How far the latest indexed checkpoint can fall behind the wall clock before the service reports
+// itself as unhealthy.
+const DEFAULT_MAX_CHECKPOINT_LAG_MS: u64 = 5 * 60 * 1_000;
+
 const DEFAULT_IDE_TITLE: &str = "Sui GraphQL IDE";
 
 pub(crate) const RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD: Duration = Duration::from_millis(10_000);
@@ -39,23 +56,60 @@ pub struct ConnectionConfig {
     pub(crate) port: u16,
     pub(crate) host: String,
     pub(crate) db_url: String,
+    /// Read replicas of the database at `db_url`, that read queries are spread across.
+    #[serde(default)]
+    pub(crate) db_replica_urls: Vec<String>,
     pub(crate) db_pool_size: u32,
     pub(crate) prom_url: String,
     pub(crate) prom_port: u16,
 }
 
 /// Configuration on features supported by the RPC, passed in a TOML-based file.
-#[derive(Serialize, Clone, Deserialize, Debug, Eq, PartialEq, Default)]
+#[derive(Serialize, Clone, Deserialize, Debug, Eq, PartialEq)]
 #[serde(rename_all = "kebab-case")]
 pub struct ServiceConfig {
     #[serde(default)]
     pub(crate) limits: Limits,
 
     #[serde(default)]
+    pub(crate) rate_limits: RateLimits,
+
+    #[serde(default)]
+    pub(crate) health: Health,
+
+    /// Features to disable. Dry runs are disabled unless this is set explicitly.
+    #[serde(default = "default_disabled_features")]
     pub(crate) disabled_features: BTreeSet<FunctionalGroup>,
 
+    /// Individual fields to disable, in addition to those belonging to disabled features,
+    /// identified as `Type.field` (e.g. `Query.eventConnection`).
+    #[serde(default)]
+    pub(crate) disabled_fields: BTreeSet<String>,
+
+    /// Maximum page sizes that override `limits.max-page-size` for a category of connection
+    /// (e.g. `events`), or for an individual connection field, identified as `Type.field` (e.g.
+    /// `Address.coinConnection`). Overrides for a field take precedence over its category's.
+    #[serde(default)]
+    pub(crate) max_page_sizes: BTreeMap<String, u64>,
+
+    /// Maximum levels of object ownership expanded by nested queries that override
+    /// `limits.max-ownership-depth` for object connections as a whole (`objects`), or for an
+    /// individual object connection field, identified as `Type.field` (e.g.
+    /// `Query.objectConnection`). Overrides for a field take precedence over the category's.
+    #[serde(default)]
+    pub(crate) max_ownership_depths: BTreeMap<String, u32>,
+
     #[serde(default)]
     pub(crate) experiments: Experiments,
+
+    /// API keys that grant access to administrative queries, such as database diagnostics.
+    #[serde(default)]
+    pub(crate) admin_api_keys: BTreeSet<String>,
+
+    /// API keys that clients are rate limited by, in place of their IP address (as are
+    /// `admin-api-keys`). Requests with any other key are rate limited by IP address.
+    #[serde(default)]
+    pub(crate) client_api_keys: BTreeSet<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Copy)]
@@ -73,6 +127,14 @@ pub struct Limits {
     pub(crate) default_page_size: u64,
     #[serde(default)]
     pub(crate) max_page_size: u64,
+    /// Serve pages that are larger than the maximum page size at the maximum size, rather than
+    /// rejecting the request.
+    #[serde(default)]
+    pub(crate) clamp_page_size: bool,
+    #[serde(default)]
+    pub(crate) max_ownership_depth: u32,
+    #[serde(default)]
+    pub(crate) max_multi_get_size: u64,
     #[serde(default)]
     pub(crate) request_timeout_ms: u64,
 }
@@ -100,6 +162,68 @@ impl Limits {
     }
 }
 
+/// Categories of connection whose maximum page size can be overridden as a whole, in
+/// `max-page-sizes`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum PageCategory {
+    Checkpoints,
+    Coins,
+    Events,
+    Objects,
+    Transactions,
+}
+
+impl PageCategory {
+    /// How the category is identified in the service's config.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            PageCategory::Checkpoints => "checkpoints",
+            PageCategory::Coins => "coins",
+            PageCategory::Events => "events",
+            PageCategory::Objects => "objects",
+            PageCategory::Transactions => "transactions",
+        }
+    }
+
+    pub(crate) fn all() -> &'static [PageCategory] {
+        use PageCategory as C;
+        &[
+            C::Checkpoints,
+            C::Coins,
+            C::Events,
+            C::Objects,
+            C::Transactions,
+        ]
+    }
+}
+
+/// Budgets for each client of the service, identified by a configured API key or IP address. Each
+/// budget is a sustained rate, plus a burst that can be spent above that rate.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub struct RateLimits {
+    #[serde(default)]
+    pub(crate) requests_per_second: u32,
+    #[serde(default)]
+    pub(crate) request_burst: u32,
+    /// Query cost is measured in query nodes, as counted by the query limits checker.
+    #[serde(default)]
+    pub(crate) query_nodes_per_second: u32,
+    #[serde(default)]
+    pub(crate) query_node_burst: u32,
+}
+
+/// Thresholds on the signals reported by `Query.serviceStatus` and the `/health` route, beyond
+/// which the service considers itself unhealthy.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub struct Health {
+    /// How far (in milliseconds) the timestamp of the latest indexed checkpoint can fall behind
+    /// the wall clock.
+    #[serde(default)]
+    pub(crate) max_checkpoint_lag_ms: u64,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
 #[serde(rename_all = "kebab-case")]
 pub struct Ide {
@@ -146,6 +270,7 @@ impl ConnectionConfig {
             port: port.unwrap_or(default.port),
             host: host.unwrap_or(default.host),
             db_url: db_url.unwrap_or(default.db_url),
+            db_replica_urls: default.db_replica_urls,
             db_pool_size: db_pool_size.unwrap_or(default.db_pool_size),
             prom_url: prom_url.unwrap_or(default.prom_url),
             prom_port: prom_port.unwrap_or(default.prom_port),
@@ -159,6 +284,11 @@ impl ConnectionConfig {
         }
     }
 
+    pub fn with_db_replica_urls(mut self, db_replica_urls: Vec<String>) -> Self {
+        self.db_replica_urls = db_replica_urls;
+        self
+    }
+
     pub fn db_url(&self) -> String {
         self.db_url.clone()
     }
@@ -194,6 +324,12 @@ impl ServiceConfig {
             .collect()
     }
 
+    /// Fields that are disabled on this GraphQL service, individually rather than as part of a
+    /// disabled feature, identified as `Type.field`.
+    async fn disabled_fields(&self) -> Vec<String> {
+        self.disabled_fields.iter().cloned().collect()
+    }
+
     /// The maximum depth a GraphQL query can be to be accepted by this service.
     pub async fn max_query_depth(&self) -> u32 {
         self.limits.max_query_depth
@@ -215,11 +351,30 @@ impl ServiceConfig {
         self.limits.default_page_size
     }
 
-    /// Maximum number of elements allowed on a single page of a connection.
+    /// Maximum number of elements allowed on a single page of a connection, unless overridden for
+    /// that kind of connection.
     async fn max_page_size(&self) -> u64 {
         self.limits.max_page_size
     }
 
+    /// Whether requests for pages larger than the maximum page size are served a page of the
+    /// maximum size (`true`), or rejected (`false`).
+    async fn clamp_page_size(&self) -> bool {
+        self.limits.clamp_page_size
+    }
+
+    /// Maximum number of levels of object ownership that are expanded when a query for owned
+    /// objects includes objects nested inside other owned objects.
+    async fn max_ownership_depth(&self) -> u32 {
+        self.limits.max_ownership_depth
+    }
+
+    /// Maximum number of objects or transaction blocks that can be looked up at once by
+    /// `multiGetObjects` or `multiGetTransactionBlocks`.
+    async fn max_multi_get_size(&self) -> u64 {
+        self.limits.max_multi_get_size
+    }
+
     /// Maximum time in milliseconds that will be spent to serve one request.
     async fn request_timeout_ms(&self) -> u64 {
         self.limits.request_timeout_ms
@@ -237,6 +392,7 @@ impl Default for ConnectionConfig {
             port: DEFAULT_SERVER_CONNECTION_PORT,
             host: DEFAULT_SERVER_CONNECTION_HOST.to_string(),
             db_url: DEFAULT_SERVER_DB_URL.to_string(),
+            db_replica_urls: vec![],
             db_pool_size: DEFAULT_SERVER_DB_POOL_SIZE,
             prom_url: DEFAULT_SERVER_PROM_HOST.to_string(),
             prom_port: DEFAULT_SERVER_PROM_PORT,
@@ -244,6 +400,23 @@ impl Default for ConnectionConfig {
     }
 }
 
+impl Default for ServiceConfig {
+    fn default() -> Self {
+        Self {
+            limits: Limits::default(),
+            rate_limits: RateLimits::default(),
+            health: Health::default(),
+            disabled_features: default_disabled_features(),
+            disabled_fields: BTreeSet::new(),
+            max_page_sizes: BTreeMap::new(),
+            max_ownership_depths: BTreeMap::new(),
+            experiments: Experiments::default(),
+            admin_api_keys: BTreeSet::new(),
+            client_api_keys: BTreeSet::new(),
+        }
+    }
+}
+
 impl Default for Limits {
     fn default() -> Self {
         Self {
@@ -253,11 +426,33 @@ impl Default for Limits {
             max_db_query_cost: MAX_DB_QUERY_COST,
             default_page_size: DEFAULT_PAGE_SIZE,
             max_page_size: MAX_PAGE_SIZE,
+            clamp_page_size: false,
+            max_ownership_depth: MAX_OWNERSHIP_DEPTH,
+            max_multi_get_size: MAX_MULTI_GET_SIZE,
             request_timeout_ms: DEFAULT_REQUEST_TIMEOUT_MS,
         }
     }
 }
 
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            requests_per_second: DEFAULT_REQUESTS_PER_SECOND,
+            request_burst: DEFAULT_REQUEST_BURST,
+            query_nodes_per_second: DEFAULT_QUERY_NODES_PER_SECOND,
+            query_node_burst: DEFAULT_QUERY_NODE_BURST,
+        }
+    }
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self {
+            max_checkpoint_lag_ms: DEFAULT_MAX_CHECKPOINT_LAG_MS,
+        }
+    }
+}
+
 #[derive(Serialize, Clone, Deserialize, Debug, Eq, PartialEq)]
 pub struct InternalFeatureConfig {
     #[serde(default)]
@@ -269,6 +464,8 @@ pub struct InternalFeatureConfig {
     #[serde(default)]
     pub(crate) query_timeout: bool,
     #[serde(default)]
+    pub(crate) rate_limiter: bool,
+    #[serde(default)]
     pub(crate) metrics: bool,
     #[serde(default)]
     pub(crate) tracing: bool,
@@ -278,6 +475,10 @@ pub struct InternalFeatureConfig {
     pub(crate) open_telemetry: bool,
 }
 
+fn default_disabled_features() -> BTreeSet<FunctionalGroup> {
+    BTreeSet::from([FunctionalGroup::DryRun])
+}
+
 impl Default for InternalFeatureConfig {
     fn default() -> Self {
         Self {
@@ -285,6 +486,7 @@ impl Default for InternalFeatureConfig {
             feature_gate: true,
             logger: true,
             query_timeout: true,
+            rate_limiter: false,
             metrics: true,
             tracing: false,
             apollo_tracing: false,
@@ -293,6 +495,15 @@ impl Default for InternalFeatureConfig {
     }
 }
 
+impl InternalFeatureConfig {
+    /// Whether spans for GraphQL resolvers should be created, to be exported over OTLP along with
+    /// the spans for the database queries they issue.
+    pub fn with_open_telemetry(mut self, open_telemetry: bool) -> Self {
+        self.open_telemetry = open_telemetry;
+        self
+    }
+}
+
 #[derive(Serialize, Clone, Deserialize, Debug, Eq, PartialEq, Default)]
 pub struct TxExecFullNodeConfig {
     #[serde(default)]
@@ -315,6 +526,10 @@ pub struct ServerConfig {
     pub internal_features: InternalFeatureConfig,
     #[serde(default)]
     pub name_service: NameServiceConfig,
+    /// Name service configurations for specific networks, which take precedence over
+    /// `name_service` when the RPC is serving that network.
+    #[serde(default)]
+    pub name_service_networks: BTreeMap<Chain, NameServiceConfig>,
     #[serde(default)]
     pub tx_exec_full_node: TxExecFullNodeConfig,
     #[serde(default)]
@@ -360,6 +575,7 @@ mod tests {
         let actual = ServiceConfig::read("").unwrap();
         let expect = ServiceConfig::default();
         assert_eq!(actual, expect);
+        assert!(actual.disabled_features.contains(&FunctionalGroup::DryRun));
     }
 
     #[test]
@@ -372,6 +588,9 @@ mod tests {
                 max-db-query-cost = 50
                 default-page-size = 20
                 max-page-size = 50
+                clamp-page-size = true
+                max-ownership-depth = 4
+                max-multi-get-size = 30
                 request-timeout-ms = 27000
             "#,
         )
@@ -385,6 +604,9 @@ mod tests {
                 max_db_query_cost: 50,
                 default_page_size: 20,
                 max_page_size: 50,
+                clamp_page_size: true,
+                max_ownership_depth: 4,
+                max_multi_get_size: 30,
                 request_timeout_ms: 27_000,
             },
             ..Default::default()
@@ -407,8 +629,15 @@ mod tests {
         use FunctionalGroup as G;
         let expect = ServiceConfig {
             limits: Limits::default(),
+            rate_limits: RateLimits::default(),
+            health: Health::default(),
             disabled_features: BTreeSet::from([G::Coins, G::NameService]),
+            disabled_fields: BTreeSet::new(),
+            max_page_sizes: BTreeMap::new(),
+            max_ownership_depths: BTreeMap::new(),
             experiments: Experiments::default(),
+            admin_api_keys: BTreeSet::new(),
+            client_api_keys: BTreeSet::new(),
         };
 
         assert_eq!(actual, expect)
@@ -435,6 +664,9 @@ mod tests {
     fn test_read_everything_in_service_config() {
         let actual = ServiceConfig::read(
             r#" disabled-features = ["analytics"]
+                disabled-fields = ["Query.eventConnection"]
+                admin-api-keys = ["operator-key"]
+                client-api-keys = ["partner-key"]
 
                 [limits]
                 max-query-depth = 42
@@ -443,8 +675,28 @@ mod tests {
                 max-db-query-cost = 20
                 default-page-size = 10
                 max-page-size = 20
+                clamp-page-size = true
+                max-ownership-depth = 2
+                max-multi-get-size = 10
                 request-timeout-ms = 30000
 
+                [max-page-sizes]
+                events = 10
+                "Address.coinConnection" = 5
+
+                [max-ownership-depths]
+                objects = 1
+                "Query.objectConnection" = 3
+
+                [rate-limits]
+                requests-per-second = 5
+                request-burst = 10
+                query-nodes-per-second = 100
+                query-node-burst = 200
+
+                [health]
+                max-checkpoint-lag-ms = 60000
+
                 [experiments]
                 test-flag = true
             "#,
@@ -459,10 +711,33 @@ mod tests {
                 max_db_query_cost: 20,
                 default_page_size: 10,
                 max_page_size: 20,
+                clamp_page_size: true,
+                max_ownership_depth: 2,
+                max_multi_get_size: 10,
                 request_timeout_ms: 30_000,
             },
+            rate_limits: RateLimits {
+                requests_per_second: 5,
+                request_burst: 10,
+                query_nodes_per_second: 100,
+                query_node_burst: 200,
+            },
+            health: Health {
+                max_checkpoint_lag_ms: 60_000,
+            },
             disabled_features: BTreeSet::from([FunctionalGroup::Analytics]),
+            disabled_fields: BTreeSet::from(["Query.eventConnection".to_string()]),
+            max_page_sizes: BTreeMap::from([
+                ("events".to_string(), 10),
+                ("Address.coinConnection".to_string(), 5),
+            ]),
+            max_ownership_depths: BTreeMap::from([
+                ("objects".to_string(), 1),
+                ("Query.objectConnection".to_string(), 3),
+            ]),
             experiments: Experiments { test_flag: true },
+            admin_api_keys: BTreeSet::from(["operator-key".to_string()]),
+            client_api_keys: BTreeSet::from(["partner-key".to_string()]),
         };
 
         assert_eq!(actual, expect);
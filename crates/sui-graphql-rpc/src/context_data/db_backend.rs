@@ -1,9 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::ops::RangeInclusive;
+
 use diesel::backend::Backend;
 use sui_indexer::{
-    schema_v2::{checkpoints, epochs, events, objects, transactions},
+    schema_v2::{checkpoints, epochs, events, objects, objects_history, transactions},
     types_v2::OwnerType,
 };
 
@@ -28,12 +30,109 @@ pub(crate) type BalanceQuery<'a, DB> = BoxedSelectStatement<
     objects::dsl::coin_type,
 >;
 
+pub(crate) type HistoricalBalanceQuery<'a, DB> = BoxedSelectStatement<
+    'a,
+    (
+        diesel::sql_types::Nullable<diesel::sql_types::BigInt>,
+        diesel::sql_types::Nullable<diesel::sql_types::BigInt>,
+        diesel::sql_types::Nullable<diesel::sql_types::Text>,
+    ),
+    FromClause<objects_history::table>,
+    DB,
+    objects_history::dsl::coin_type,
+>;
+
+pub(crate) type EpochAggregatesQuery<'a, DB> = BoxedSelectStatement<
+    'a,
+    (
+        diesel::sql_types::Nullable<diesel::sql_types::BigInt>,
+        diesel::sql_types::Nullable<diesel::sql_types::BigInt>,
+        diesel::sql_types::Nullable<diesel::sql_types::BigInt>,
+        diesel::sql_types::Nullable<diesel::sql_types::BigInt>,
+    ),
+    FromClause<checkpoints::table>,
+    DB,
+>;
+
+/// Pages through the activity of an address: the transactions it sent, the staking operations it
+/// performed, and the transactions that sent objects to it, as rows of `(tx_sequence_number,
+/// kind)`, where `kind` is an `ActivityKind`. Each transaction appears at most once, ordered by
+/// `tx_sequence_number` and then `kind`, which together serve as its cursor. Pages are read in
+/// descending order when `descending_order` is set (when paginating from the back).
+pub(crate) struct AddressActivityQuery {
+    pub address: Vec<u8>,
+    pub before: Option<(i64, i16)>,
+    pub after: Option<(i64, i16)>,
+    pub descending_order: bool,
+    pub limit: i64,
+}
+
+/// Length of the days that balance history is bucketed into.
+pub(crate) const MS_PER_DAY: i64 = 24 * 60 * 60 * 1_000;
+
+/// Pages through the daily history of an address's balance of `coin_type`, as rows of `(day,
+/// checkpoint, balance)`: For each day (UTC, as the number of days since the Unix epoch) that
+/// checkpoints were produced on, the total balance of the address's coins of that type as of the
+/// last checkpoint of the day. Only checkpoints with timestamps in `[start_ms, end_ms)` are
+/// considered (see `balance_history_window`), and rows are ordered by `day`, which serves as
+/// their cursor.
+pub(crate) struct BalanceHistoryQuery {
+    pub address: Vec<u8>,
+    pub coin_type: String,
+    pub start_ms: i64,
+    pub end_ms: i64,
+}
+
+/// The days (inclusive) that a page of up to `limit` days of balance history covers, given the
+/// days of the earliest and latest checkpoints, and the pagination cursors. Pages are read
+/// forward from the day after `after` (or the earliest day), or backward from the day before
+/// `before` (or the latest day) if `backward` is set. Also returns whether there are more days
+/// beyond the page, in the direction it was read in. Returns `None` if there are no days to read.
+pub(crate) fn balance_history_window(
+    earliest: i64,
+    latest: i64,
+    after: Option<i64>,
+    before: Option<i64>,
+    limit: i64,
+    backward: bool,
+) -> Option<(RangeInclusive<i64>, bool)> {
+    let lo = after.map_or(earliest, |day| day + 1).max(earliest);
+    let hi = before.map_or(latest, |day| day - 1).min(latest);
+    if lo > hi || limit <= 0 {
+        return None;
+    }
+
+    Some(if backward {
+        let start = lo.max(hi - limit + 1);
+        (start..=hi, start > lo)
+    } else {
+        let end = hi.min(lo + limit - 1);
+        (lo..=end, end < hi)
+    })
+}
+
+/// The ways an address can be involved in a transaction in its activity feed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(i16)]
+pub(crate) enum ActivityKind {
+    /// Sent by the address, and not a staking operation.
+    Sent = 0,
+    /// Sent by the address, adding or withdrawing stake.
+    Staking = 1,
+    /// Not sent by the address, but left it owning some object.
+    Received = 2,
+}
+
 pub(crate) trait GenericQueryBuilder<DB: Backend> {
     fn get_tx_by_digest(digest: Vec<u8>) -> transactions::BoxedQuery<'static, DB>;
     fn get_obj(address: Vec<u8>, version: Option<i64>) -> objects::BoxedQuery<'static, DB>;
     fn get_obj_by_type(object_type: String) -> objects::BoxedQuery<'static, DB>;
     fn get_epoch(epoch_id: i64) -> epochs::BoxedQuery<'static, DB>;
     fn get_latest_epoch() -> epochs::BoxedQuery<'static, DB>;
+    /// Aggregates over the checkpoints indexed for `epoch`: the sequence numbers of its first and
+    /// last checkpoints, and the network's total transaction count before its first checkpoint
+    /// and after its last.
+    fn get_epoch_aggregates(epoch: i64) -> EpochAggregatesQuery<'static, DB>;
     fn get_checkpoint_by_digest(digest: Vec<u8>) -> checkpoints::BoxedQuery<'static, DB>;
     fn get_checkpoint_by_sequence_number(
         sequence_number: i64,
@@ -50,6 +149,24 @@ pub(crate) trait GenericQueryBuilder<DB: Backend> {
         after_tx_seq_num: Option<i64>,
         before_tx_seq_num: Option<i64>,
     ) -> Result<transactions::BoxedQuery<'static, DB>, Error>;
+    /// Fetch the transactions with the given sequence numbers, in no particular order.
+    fn multi_get_txs_by_sequence_numbers(
+        tx_sequence_numbers: Vec<i64>,
+    ) -> transactions::BoxedQuery<'static, DB>;
+    /// Fetch the transactions with the given digests, in no particular order.
+    fn multi_get_txs_by_digests(digests: Vec<Vec<u8>>) -> transactions::BoxedQuery<'static, DB>;
+    fn multi_get_address_activity(
+        address: Vec<u8>,
+        before: Option<(i64, i16)>,
+        after: Option<(i64, i16)>,
+        descending_order: bool,
+        limit: i64,
+    ) -> AddressActivityQuery;
+    fn multi_get_balance_history(
+        address: Vec<u8>,
+        coin_type: String,
+        days: RangeInclusive<i64>,
+    ) -> BalanceHistoryQuery;
     fn multi_get_coins(
         before: Option<Vec<u8>>,
         after: Option<Vec<u8>>,
@@ -57,15 +174,57 @@ pub(crate) trait GenericQueryBuilder<DB: Backend> {
         address: Option<Vec<u8>>,
         coin_type: String,
     ) -> objects::BoxedQuery<'static, DB>;
+    /// Like `multi_get_coins`, but paginated in descending order of balance, then ascending order
+    /// of object ID, with cursors that are `(balance, object_id)` pairs.
+    fn multi_get_coins_by_balance(
+        before: Option<(i64, Vec<u8>)>,
+        after: Option<(i64, Vec<u8>)>,
+        limit: i64,
+        address: Option<Vec<u8>>,
+        coin_type: String,
+    ) -> objects::BoxedQuery<'static, DB>;
     fn multi_get_objs(
         before: Option<Vec<u8>>,
         after: Option<Vec<u8>>,
         limit: i64,
         filter: Option<ObjectFilter>,
         owner_type: Option<OwnerType>,
+        nested_depth: Option<u32>,
     ) -> Result<objects::BoxedQuery<'static, DB>, Error>;
+    /// Like `multi_get_objs`, but selecting objects as they were at `checkpoint`, from their
+    /// history, rather than as they are now.
+    fn multi_get_objs_at_checkpoint(
+        before: Option<Vec<u8>>,
+        after: Option<Vec<u8>>,
+        limit: i64,
+        filter: Option<ObjectFilter>,
+        owner_type: Option<OwnerType>,
+        checkpoint: i64,
+    ) -> Result<objects_history::BoxedQuery<'static, DB>, Error>;
+    /// Fetch the live objects with the given IDs, in no particular order.
+    fn multi_get_objs_by_ids(object_ids: Vec<Vec<u8>>) -> objects::BoxedQuery<'static, DB>;
+    /// Fetch the exact versions of objects identified by `(object_id, version, digest)`.
+    fn multi_get_history_objs(
+        refs: Vec<(Vec<u8>, i64, Vec<u8>)>,
+    ) -> objects_history::BoxedQuery<'static, DB>;
+    /// Fetch the versions of the object `object_id` at which it was live (not wrapped or
+    /// deleted), between the `after` and `before` versions (exclusive), in ascending order of
+    /// version, or descending if `descending_order` is set.
+    fn multi_get_object_versions(
+        object_id: Vec<u8>,
+        before: Option<i64>,
+        after: Option<i64>,
+        descending_order: bool,
+        limit: i64,
+    ) -> objects_history::BoxedQuery<'static, DB>;
     fn multi_get_balances(address: Vec<u8>) -> BalanceQuery<'static, DB>;
     fn get_balance(address: Vec<u8>, coin_type: String) -> BalanceQuery<'static, DB>;
+    /// Balances of the coins owned by `address` as of (the end of) `checkpoint`, computed from
+    /// the latest version of each coin at or before that checkpoint.
+    fn multi_get_historical_balances(
+        address: Vec<u8>,
+        checkpoint: i64,
+    ) -> HistoricalBalanceQuery<'static, DB>;
     fn multi_get_checkpoints(
         before: Option<i64>,
         after: Option<i64>,
@@ -80,6 +239,41 @@ pub(crate) trait GenericQueryBuilder<DB: Backend> {
     ) -> Result<events::BoxedQuery<'static, DB>, Error>;
 }
 
+impl TryFrom<i16> for ActivityKind {
+    type Error = Error;
+
+    fn try_from(kind: i16) -> Result<Self, Error> {
+        Ok(match kind {
+            0 => ActivityKind::Sent,
+            1 => ActivityKind::Staking,
+            2 => ActivityKind::Received,
+            _ => return Err(Error::Internal(format!("Unknown activity kind: {kind}"))),
+        })
+    }
+}
+
+impl QueryId for AddressActivityQuery {
+    type QueryId = ();
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl diesel::query_builder::Query for AddressActivityQuery {
+    type SqlType = (diesel::sql_types::BigInt, diesel::sql_types::SmallInt);
+}
+
+impl QueryId for BalanceHistoryQuery {
+    type QueryId = ();
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl diesel::query_builder::Query for BalanceHistoryQuery {
+    type SqlType = (
+        diesel::sql_types::BigInt,
+        diesel::sql_types::BigInt,
+        diesel::sql_types::Nullable<diesel::sql_types::BigInt>,
+    );
+}
+
 /// The struct returned for query.explain()
 #[derive(Debug, Clone, Copy)]
 pub struct Explained<T> {
@@ -106,3 +300,39 @@ impl<T: QueryId> QueryId for Explained<T> {
 impl<T: diesel::query_builder::Query> diesel::query_builder::Query for Explained<T> {
     type SqlType = Text;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balance_history_window() {
+        let window = |after, before, limit, backward| {
+            balance_history_window(100, 109, after, before, limit, backward)
+        };
+
+        // Forward from the earliest day, or the day after the cursor.
+        assert_eq!(window(None, None, 4, false), Some((100..=103, true)));
+        assert_eq!(window(Some(103), None, 4, false), Some((104..=107, true)));
+        assert_eq!(window(Some(105), None, 4, false), Some((106..=109, false)));
+        assert_eq!(window(Some(107), None, 4, false), Some((108..=109, false)));
+        assert_eq!(window(None, Some(103), 4, false), Some((100..=102, false)));
+
+        // Backward from the latest day, or the day before the cursor.
+        assert_eq!(window(None, None, 4, true), Some((106..=109, true)));
+        assert_eq!(window(None, Some(106), 4, true), Some((102..=105, true)));
+        assert_eq!(window(None, Some(104), 4, true), Some((100..=103, false)));
+        assert_eq!(window(Some(103), None, 4, true), Some((106..=109, true)));
+        assert_eq!(window(Some(106), None, 4, true), Some((107..=109, false)));
+
+        // Cursors from before the earliest day, or after the latest day.
+        assert_eq!(window(Some(50), None, 4, false), Some((100..=103, true)));
+        assert_eq!(window(None, Some(200), 4, true), Some((106..=109, true)));
+
+        // Nothing to read.
+        assert_eq!(window(Some(109), None, 4, false), None);
+        assert_eq!(window(None, Some(100), 4, true), None);
+        assert_eq!(window(Some(104), Some(105), 4, false), None);
+        assert_eq!(window(None, None, 0, false), None);
+    }
+}
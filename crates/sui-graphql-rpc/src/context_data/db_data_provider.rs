@@ -1,23 +1,34 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::db_backend::GenericQueryBuilder;
+use super::{
+    db_backend::{balance_history_window, GenericQueryBuilder, MS_PER_DAY},
+    db_query_stats::DbQueryStats,
+    db_replicas::ReadReplicas,
+    dry_run::{dry_run, invalid_tx, DbExecutionStore, DryRunObjects},
+    filter_validation::{validate_event_filter, validate_obj_filter, validate_tx_block_filter},
+};
 use crate::{
-    config::{Limits, DEFAULT_SERVER_DB_POOL_SIZE},
+    config::{Limits, PageCategory, DEFAULT_SERVER_DB_POOL_SIZE},
     error::Error,
+    extensions::page_size,
+    metrics::DbMetrics,
     types::{
         address::{Address, AddressTransactionBlockRelationship},
-        balance::Balance,
+        address_activity::AddressActivity,
+        balance::{Balance, DailyBalance},
         big_int::BigInt,
         checkpoint::Checkpoint,
-        coin::Coin,
+        coin::{Coin, CoinOrder},
         coin_metadata::CoinMetadata,
         committee_member::CommitteeMember,
+        cursor::{Cursor, CursorKey},
         date_time::DateTime,
         digest::Digest,
         dynamic_field::{DynamicField, DynamicFieldName},
         end_of_epoch_data::EndOfEpochData,
         epoch::Epoch,
+        epoch_aggregates::EpochAggregates,
         event::{Event, EventFilter},
         gas::GasCostSummary,
         move_function::MoveFunction,
@@ -25,7 +36,7 @@ use crate::{
         move_object::MoveObject,
         move_package::MovePackage,
         move_type::MoveType,
-        object::{Object, ObjectFilter},
+        object::{Object, ObjectFilter, ObjectRef},
         protocol_config::{ProtocolConfigAttr, ProtocolConfigFeatureFlag, ProtocolConfigs},
         safe_mode::SafeMode,
         stake::StakedSui,
@@ -41,31 +52,37 @@ use crate::{
 };
 use async_graphql::connection::{Connection, Edge};
 use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
-use std::{collections::BTreeMap, str::FromStr};
+use std::{collections::BTreeMap, str::FromStr, sync::Arc};
 use sui_indexer::{
     apis::GovernanceReadApiV2,
     indexer_reader::IndexerReader,
     models_v2::{
-        checkpoints::StoredCheckpoint, epoch::StoredEpochInfo, events::StoredEvent,
-        objects::StoredObject, transactions::StoredTransaction,
+        checkpoints::StoredCheckpoint,
+        epoch::StoredEpochInfo,
+        events::StoredEvent,
+        objects::{StoredHistoryObject, StoredObject},
+        transactions::StoredTransaction,
     },
-    schema_v2::transactions,
-    types_v2::OwnerType,
+    schema_v2::{checkpoints, objects, transactions},
+    types_v2::{ObjectStatus, OwnerType},
     PgConnectionPoolConfig,
 };
 use sui_json_rpc::{
+    balance_changes::get_balance_changes_from_effect,
     coin_api::{parse_to_struct_tag, parse_to_type_tag},
-    name_service::{Domain, NameRecord, NameServiceConfig},
 };
-use sui_json_rpc_types::{ProtocolConfigResponse, Stake as RpcStakedSui};
-use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
+use sui_json_rpc_types::{
+    BalanceChange as NativeBalanceChange, ProtocolConfigResponse, Stake as RpcStakedSui,
+};
+use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
 use sui_types::base_types::ConciseableName;
 use sui_types::{
     base_types::{MoveObjectType, ObjectID, SuiAddress as NativeSuiAddress},
     coin::{CoinMetadata as NativeCoinMetadata, TreasuryCap},
     digests::ChainIdentifier,
     digests::TransactionDigest,
-    dynamic_field::{DynamicFieldType, Field},
+    dynamic_field::DynamicFieldType,
+    effects::TransactionEffects as NativeTransactionEffects,
     gas_coin::{GAS, TOTAL_SUPPLY_SUI},
     governance::StakedSui as NativeStakedSui,
     messages_checkpoint::{
@@ -75,11 +92,12 @@ use sui_types::{
     sui_system_state::sui_system_state_summary::{
         SuiSystemStateSummary as NativeSuiSystemStateSummary, SuiValidatorSummary,
     },
+    transaction::{TransactionData as NativeTransactionData, TransactionDataAPI},
     TypeTag,
 };
 
 #[cfg(feature = "pg_backend")]
-use super::pg_backend::{PgQueryExecutor, QueryBuilder};
+use super::pg_backend::{record_rows, PgQueryExecutor, QueryBuilder};
 
 #[derive(thiserror::Error, Debug, Eq, PartialEq)]
 pub enum DbValidationError {
@@ -87,10 +105,16 @@ pub enum DbValidationError {
     InvalidCheckpointCombination,
     #[error("Before checkpoint must be greater than after checkpoint")]
     InvalidCheckpointOrder,
+    #[error("No checkpoints precede checkpoint 0, so before checkpoint must be greater than 0")]
+    BeforeGenesisCheckpoint,
+    #[error("Before time must be later than after time")]
+    InvalidTimeOrder,
     #[error("Filtering objects by package::module::type is not currently supported")]
     UnsupportedPMT,
     #[error("Filtering objects by object keys is not currently supported")]
     UnsupportedObjectKeys,
+    #[error("Filtering transactions by paid address is not currently supported")]
+    UnsupportedPaidAddress,
     #[error("Requires package and module")]
     RequiresPackageAndModule,
     #[error("Requires package")]
@@ -101,32 +125,103 @@ pub enum DbValidationError {
     LastBefore,
     #[error("Pagination is currently disabled on balances")]
     PaginationDisabledOnBalances,
+    #[error("Checkpoint {0} is not available - must be between {1} and {2}")]
+    CheckpointOutOfRange(u64, u64, u64),
     #[error("Invalid owner type. Must be Address or Object")]
     InvalidOwnerType,
+    #[error("Shared and immutable objects do not have owners, so cannot be filtered by owner")]
+    OwnerFilterOnUnownedKind,
+    #[error("Object kind filter conflicts with the kind of objects being queried")]
+    ConflictingObjectKind,
+    #[error(
+        "Filtering for shared or immutable objects requires also filtering by type or object ID"
+    )]
+    UnboundedUnownedKind,
+    #[error("Including nested objects requires filtering by owner")]
+    NestedWithoutOwner,
     #[error("Query cost exceeded - cost: {0}, limit: {1}")]
     QueryCostExceeded(u64, u64),
     #[error("Page size exceeded - requested: {0}, limit: {1}")]
     PageSizeExceeded(u64, u64),
+    #[error("Too many keys in batch lookup - requested: {0}, limit: {1}")]
+    MultiGetSizeExceeded(u64, u64),
+    #[error("Objects must be looked up by either 'ids' or 'refs', but not both")]
+    MultiGetObjectKeys,
     #[error("Invalid type provided as filter: {0}")]
     InvalidType(String),
+    #[error("Too many types provided as filter - requested: {0}, limit: {1}")]
+    TooManyTypes(usize, usize),
+    #[error("At least one type must be provided as filter")]
+    NoTypes,
+    #[error("A type match was provided without any types to match")]
+    TypeMatchWithoutType,
 }
 
-#[derive(thiserror::Error, Debug)]
-pub enum TypeFilterError {
-    #[error("Invalid format in '{0}' - if '::' is present, there must be a non-empty string on both sides. Expected format like '{1}'")]
-    MissingComponents(String, &'static str),
-    #[error("Invalid format in '{0}' - value must have {1} or fewer components. Expected format like '{2}'")]
-    TooManyComponents(String, u64, &'static str),
-}
-
+#[derive(Clone)]
 pub(crate) struct PgManager {
     pub inner: IndexerReader,
     pub limits: Limits,
+    pub max_page_sizes: BTreeMap<String, u64>,
+    pub max_ownership_depths: BTreeMap<String, u32>,
+    pub replicas: ReadReplicas,
+    pub stats: Arc<DbQueryStats>,
 }
 
 impl PgManager {
     pub(crate) fn new(inner: IndexerReader, limits: Limits) -> Self {
-        Self { inner, limits }
+        Self {
+            inner,
+            limits,
+            max_page_sizes: BTreeMap::new(),
+            max_ownership_depths: BTreeMap::new(),
+            replicas: ReadReplicas::new(vec![]),
+            stats: Arc::new(DbQueryStats::default()),
+        }
+    }
+
+    /// Spread read queries across `replicas`, rather than sending them all to `inner`.
+    pub(crate) fn with_replicas(mut self, replicas: ReadReplicas) -> Self {
+        self.replicas = replicas;
+        self
+    }
+
+    /// Override the maximum page size for categories of connection, or individual connection
+    /// fields (see `ServiceConfig::max_page_sizes`).
+    pub(crate) fn with_max_page_sizes(mut self, max_page_sizes: BTreeMap<String, u64>) -> Self {
+        self.max_page_sizes = max_page_sizes;
+        self
+    }
+
+    /// Override the maximum ownership depth for object connections, or individual object
+    /// connection fields (see `ServiceConfig::max_ownership_depths`).
+    pub(crate) fn with_max_ownership_depths(
+        mut self,
+        max_ownership_depths: BTreeMap<String, u32>,
+    ) -> Self {
+        self.max_ownership_depths = max_ownership_depths;
+        self
+    }
+
+    /// Report connection pool usage and query timings to `metrics`.
+    pub(crate) fn with_metrics(mut self, metrics: DbMetrics) -> Self {
+        self.stats = Arc::new(DbQueryStats::new(metrics));
+        self
+    }
+
+    /// The readers for the primary database and each of its replicas, identified by name.
+    pub(crate) fn pools(&self) -> Vec<(String, &IndexerReader)> {
+        let replicas = self.replicas.readers().enumerate();
+        std::iter::once((Self::pool_name(None), &self.inner))
+            .chain(replicas.map(|(index, reader)| (Self::pool_name(Some(index)), reader)))
+            .collect()
+    }
+
+    /// Name of the connection pool for the replica at `index`, or the primary if there is none.
+    pub(crate) fn pool_name(replica: Option<usize>) -> String {
+        match replica {
+            Some(index) => format!("replica-{index}"),
+            None => "primary".to_string(),
+        }
     }
 
     /// Create a new underlying reader, which is used by this type as well as other data providers.
@@ -189,6 +284,17 @@ impl PgManager {
         .await
     }
 
+    async fn get_epoch_aggregates(
+        &self,
+        epoch_id: i64,
+    ) -> Result<(Option<i64>, Option<i64>, Option<i64>, Option<i64>), Error> {
+        self.run_query_async_with_cost(
+            move || Ok(QueryBuilder::get_epoch_aggregates(epoch_id)),
+            |query| move |conn| query.get_result(conn),
+        )
+        .await
+    }
+
     async fn get_checkpoint(
         &self,
         digest: Option<Vec<u8>>,
@@ -213,6 +319,20 @@ impl PgManager {
         .await
     }
 
+    /// The latest checkpoint that the live objects table reflects, or `None` if it holds no
+    /// objects yet.
+    async fn get_latest_object_checkpoint(&self) -> Result<Option<u64>, Error> {
+        let checkpoint: Option<i64> = self
+            .run_query_async(|conn| {
+                objects::dsl::objects
+                    .select(diesel::dsl::max(objects::dsl::checkpoint_sequence_number))
+                    .get_result(conn)
+            })
+            .await?;
+
+        Ok(checkpoint.map(|c| c as u64))
+    }
+
     async fn get_earliest_complete_checkpoint(&self) -> Result<Option<StoredCheckpoint>, Error> {
         let query = move || Ok(QueryBuilder::get_earliest_complete_checkpoint());
         self.run_query_async_with_cost(query, |query| {
@@ -237,39 +357,69 @@ impl PgManager {
 
     /// Fetches the coins owned by the address and filters them by the given coin type.
     /// If no address is given, it fetches all available coin objects matching the coin type.
+    #[allow(clippy::too_many_arguments)]
     async fn multi_get_coins(
         &self,
         address: Option<Vec<u8>>,
         coin_type: String,
+        order: CoinOrder,
         first: Option<u64>,
         after: Option<String>,
         last: Option<u64>,
         before: Option<String>,
     ) -> Result<Option<(Vec<StoredObject>, bool)>, Error> {
-        let limit = self.validate_page_limit(first, last)?;
-        let before = before
-            .map(|cursor| self.parse_obj_cursor(&cursor))
-            .transpose()?;
-        let after = after
-            .map(|cursor| self.parse_obj_cursor(&cursor))
-            .transpose()?;
+        let limit = self.validate_page_limit(first, last, PageCategory::Coins)?;
         let coin_type = parse_to_type_tag(Some(coin_type))
             .map_err(|e| Error::InvalidCoinType(e.to_string()))?
             .to_canonical_string(/* with_prefix */ true);
-        let result: Option<Vec<StoredObject>> = self
-            .run_query_async_with_cost(
-                move || {
-                    Ok(QueryBuilder::multi_get_coins(
-                        before.clone(),
-                        after.clone(),
-                        limit,
-                        address.clone(),
-                        coin_type.clone(),
-                    ))
-                },
-                |query| move |conn| query.load(conn).optional(),
-            )
-            .await?;
+
+        let result: Option<Vec<StoredObject>> = match order {
+            CoinOrder::ObjectId => {
+                let before = before
+                    .map(|cursor| self.parse_obj_cursor(&cursor))
+                    .transpose()?;
+                let after = after
+                    .map(|cursor| self.parse_obj_cursor(&cursor))
+                    .transpose()?;
+
+                self.run_query_async_with_cost(
+                    move || {
+                        Ok(QueryBuilder::multi_get_coins(
+                            before.clone(),
+                            after.clone(),
+                            limit,
+                            address.clone(),
+                            coin_type.clone(),
+                        ))
+                    },
+                    |query| move |conn| query.load(conn).optional().map(record_rows),
+                )
+                .await?
+            }
+
+            CoinOrder::BalanceDesc => {
+                let before = before
+                    .map(|cursor| self.parse_coin_balance_cursor(&cursor))
+                    .transpose()?;
+                let after = after
+                    .map(|cursor| self.parse_coin_balance_cursor(&cursor))
+                    .transpose()?;
+
+                self.run_query_async_with_cost(
+                    move || {
+                        Ok(QueryBuilder::multi_get_coins_by_balance(
+                            before.clone(),
+                            after.clone(),
+                            limit,
+                            address.clone(),
+                            coin_type.clone(),
+                        ))
+                    },
+                    |query| move |conn| query.load(conn).optional().map(record_rows),
+                )
+                .await?
+            }
+        };
 
         result
             .map(|mut stored_objs| {
@@ -311,6 +461,7 @@ impl PgManager {
         after: Option<String>,
         last: Option<u64>,
         before: Option<String>,
+        at_checkpoint: Option<u64>,
     ) -> Result<Option<Vec<(Option<i64>, Option<i64>, Option<String>)>>, Error> {
         // Todo (wlmyng): paginating on balances does not really make sense
         // We'll always need to calculate all balances first
@@ -318,13 +469,53 @@ impl PgManager {
             return Err(DbValidationError::PaginationDisabledOnBalances.into());
         }
 
+        let Some(checkpoint) = at_checkpoint else {
+            return self
+                .run_query_async_with_cost(
+                    move || Ok(QueryBuilder::multi_get_balances(address.clone())),
+                    |query| move |conn| query.load(conn).optional().map(record_rows),
+                )
+                .await;
+        };
+
+        self.validate_checkpoint_available(checkpoint).await?;
         self.run_query_async_with_cost(
-            move || Ok(QueryBuilder::multi_get_balances(address.clone())),
-            |query| move |conn| query.load(conn).optional(),
+            move || {
+                Ok(QueryBuilder::multi_get_historical_balances(
+                    address.clone(),
+                    checkpoint as i64,
+                ))
+            },
+            |query| move |conn| query.load(conn).optional().map(record_rows),
         )
         .await
     }
 
+    /// Checks that `checkpoint` falls within the range of checkpoints whose data is fully
+    /// available, so that historical queries against it return complete results.
+    async fn validate_checkpoint_available(&self, checkpoint: u64) -> Result<(), Error> {
+        let earliest = self
+            .get_earliest_complete_checkpoint()
+            .await?
+            .ok_or_else(|| Error::Internal("Earliest checkpoint not found".to_string()))?;
+        let latest = self
+            .get_checkpoint(None, None)
+            .await?
+            .ok_or_else(|| Error::Internal("Latest checkpoint not found".to_string()))?;
+
+        let (earliest, latest) = (
+            earliest.sequence_number as u64,
+            latest.sequence_number as u64,
+        );
+        if checkpoint < earliest || checkpoint > latest {
+            return Err(
+                DbValidationError::CheckpointOutOfRange(checkpoint, earliest, latest).into(),
+            );
+        }
+
+        Ok(())
+    }
+
     async fn multi_get_txs(
         &self,
         first: Option<u64>,
@@ -333,7 +524,7 @@ impl PgManager {
         before: Option<String>,
         filter: Option<TransactionBlockFilter>,
     ) -> Result<Option<(Vec<StoredTransaction>, bool)>, Error> {
-        let limit = self.validate_page_limit(first, last)?;
+        let limit = self.validate_page_limit(first, last, PageCategory::Transactions)?;
         let descending_order = last.is_some();
         let cursor = after
             .or(before)
@@ -344,15 +535,18 @@ impl PgManager {
         let mut before_tx_seq_num: Option<i64> = None;
         if let Some(filter) = &filter {
             if let Some(checkpoint) = filter.after_checkpoint {
-                let subquery = transactions::dsl::transactions
-                    .filter(transactions::dsl::checkpoint_sequence_number.eq(checkpoint as i64))
-                    .order(transactions::dsl::tx_sequence_number.asc())
-                    .select(transactions::dsl::tx_sequence_number)
-                    .limit(1)
-                    .into_boxed();
-
                 after_tx_seq_num = self
-                    .run_query_async(|conn| subquery.get_result::<i64>(conn).optional())
+                    .run_query_async(move |conn| {
+                        transactions::dsl::transactions
+                            .filter(
+                                transactions::dsl::checkpoint_sequence_number.eq(checkpoint as i64),
+                            )
+                            .order(transactions::dsl::tx_sequence_number.asc())
+                            .select(transactions::dsl::tx_sequence_number)
+                            .limit(1)
+                            .get_result::<i64>(conn)
+                            .optional()
+                    })
                     .await?;
 
                 // Return early if we cannot find txs after the specified checkpoint
@@ -362,14 +556,17 @@ impl PgManager {
             }
 
             if let Some(checkpoint) = filter.before_checkpoint {
-                let subquery = transactions::dsl::transactions
-                    .filter(transactions::dsl::checkpoint_sequence_number.eq(checkpoint as i64))
-                    .order(transactions::dsl::tx_sequence_number.desc())
-                    .select(transactions::dsl::tx_sequence_number)
-                    .into_boxed();
-
                 before_tx_seq_num = self
-                    .run_query_async(|conn| subquery.get_result::<i64>(conn).optional())
+                    .run_query_async(move |conn| {
+                        transactions::dsl::transactions
+                            .filter(
+                                transactions::dsl::checkpoint_sequence_number.eq(checkpoint as i64),
+                            )
+                            .order(transactions::dsl::tx_sequence_number.desc())
+                            .select(transactions::dsl::tx_sequence_number)
+                            .get_result::<i64>(conn)
+                            .optional()
+                    })
                     .await?;
 
                 // Return early if we cannot find tx before the specified checkpoint
@@ -377,6 +574,40 @@ impl PgManager {
                     return Ok(None);
                 }
             }
+
+            if let Some(after_time) = &filter.after_time {
+                // Transactions strictly after `after_time` start from the first transaction in
+                // the first checkpoint with a later timestamp.
+                let Some(checkpoint) = self
+                    .first_checkpoint_at_or_after_timestamp(after_time.timestamp_ms() + 1)
+                    .await?
+                else {
+                    return Ok(None);
+                };
+
+                let Some(tx_seq_num) = self.first_tx_seq_num_from_checkpoint(checkpoint).await?
+                else {
+                    return Ok(None);
+                };
+
+                let bound = tx_seq_num - 1;
+                after_tx_seq_num = Some(after_tx_seq_num.map_or(bound, |a| a.max(bound)));
+            }
+
+            if let Some(before_time) = &filter.before_time {
+                // Transactions strictly before `before_time` end just before the first transaction
+                // in the first checkpoint whose timestamp is not earlier than `before_time`. If no
+                // such checkpoint has been indexed yet, every transaction qualifies.
+                let checkpoint = self
+                    .first_checkpoint_at_or_after_timestamp(before_time.timestamp_ms())
+                    .await?;
+
+                if let Some(checkpoint) = checkpoint {
+                    if let Some(bound) = self.first_tx_seq_num_from_checkpoint(checkpoint).await? {
+                        before_tx_seq_num = Some(before_tx_seq_num.map_or(bound, |b| b.min(bound)));
+                    }
+                }
+            }
         }
 
         let query = move || {
@@ -391,7 +622,9 @@ impl PgManager {
         };
 
         let result: Option<Vec<StoredTransaction>> = self
-            .run_query_async_with_cost(query, |query| move |conn| query.load(conn).optional())
+            .run_query_async_with_cost(query, |query| {
+                move |conn| query.load(conn).optional().map(record_rows)
+            })
             .await?;
 
         result
@@ -406,6 +639,43 @@ impl PgManager {
             .transpose()
     }
 
+    /// Resolves a timestamp to the sequence number of the first checkpoint whose timestamp is at
+    /// or after `timestamp_ms`, or `None` if there is no such checkpoint. Checkpoint timestamps are
+    /// non-decreasing in sequence number, so this is the checkpoint with the lowest sequence
+    /// number among those at or after the timestamp, found through the index on timestamps.
+    async fn first_checkpoint_at_or_after_timestamp(
+        &self,
+        timestamp_ms: i64,
+    ) -> Result<Option<i64>, Error> {
+        self.run_query_async(move |conn| {
+            checkpoints::dsl::checkpoints
+                .filter(checkpoints::dsl::timestamp_ms.ge(timestamp_ms))
+                .order(checkpoints::dsl::sequence_number.asc())
+                .select(checkpoints::dsl::sequence_number)
+                .limit(1)
+                .get_result::<i64>(conn)
+                .optional()
+        })
+        .await
+    }
+
+    /// The sequence number of the first transaction in or after the given checkpoint.
+    async fn first_tx_seq_num_from_checkpoint(
+        &self,
+        checkpoint: i64,
+    ) -> Result<Option<i64>, Error> {
+        self.run_query_async(move |conn| {
+            transactions::dsl::transactions
+                .filter(transactions::dsl::checkpoint_sequence_number.ge(checkpoint))
+                .order(transactions::dsl::tx_sequence_number.asc())
+                .select(transactions::dsl::tx_sequence_number)
+                .limit(1)
+                .get_result::<i64>(conn)
+                .optional()
+        })
+        .await
+    }
+
     pub(crate) fn parse_checkpoint_cursor(&self, cursor: &str) -> Result<i64, Error> {
         let sequence_number = cursor.parse::<i64>().map_err(|e| {
             Error::InvalidCursor(format!("Failed to parse checkpoint cursor: {}", e))
@@ -422,7 +692,7 @@ impl PgManager {
         epoch: Option<u64>,
     ) -> Result<Option<(Vec<StoredCheckpoint>, bool)>, Error> {
         validate_cursor_pagination(&first, &after, &last, &before)?;
-        let limit = self.validate_page_limit(first, last)?;
+        let limit = self.validate_page_limit(first, last, PageCategory::Checkpoints)?;
         let before = before
             .map(|cursor| self.parse_checkpoint_cursor(&cursor))
             .transpose()?;
@@ -440,7 +710,7 @@ impl PgManager {
                         epoch.map(|e| e as i64),
                     ))
                 },
-                |query| move |conn| query.load(conn).optional(),
+                |query| move |conn| query.load(conn).optional().map(record_rows),
             )
             .await?;
 
@@ -461,6 +731,17 @@ impl PgManager {
     }
 
     pub(crate) fn parse_event_cursor(&self, cursor: &str) -> Result<(i64, i64), Error> {
+        match Cursor::decode(cursor) {
+            Ok(Cursor {
+                key: CursorKey::Event { tx, event },
+                ..
+            }) => return Ok((tx, event)),
+            Ok(_) => return Err(Error::InvalidCursor("Expected an event cursor".to_string())),
+            // Fall back to the legacy format, `{tx_sequence_number}:{event_sequence_number}`.
+            Err(_) if cursor.contains(':') => {}
+            Err(e) => return Err(e),
+        }
+
         let mut parts = cursor.split(':');
         let tx_sequence_number = parts
             .next()
@@ -491,7 +772,7 @@ impl PgManager {
         before: Option<String>,
         filter: Option<EventFilter>,
     ) -> Result<Option<(Vec<StoredEvent>, bool)>, Error> {
-        let limit = self.validate_page_limit(first, last)?;
+        let limit = self.validate_page_limit(first, last, PageCategory::Events)?;
         let before = before
             .map(|cursor| self.parse_event_cursor(&cursor))
             .transpose()?;
@@ -502,7 +783,9 @@ impl PgManager {
         let query = move || QueryBuilder::multi_get_events(before, after, limit, filter.clone());
 
         let result: Option<Vec<StoredEvent>> = self
-            .run_query_async_with_cost(query, |query| move |conn| query.load(conn).optional())
+            .run_query_async_with_cost(query, |query| {
+                move |conn| query.load(conn).optional().map(record_rows)
+            })
             .await?;
 
         result
@@ -521,6 +804,165 @@ impl PgManager {
             .transpose()
     }
 
+    pub(crate) fn parse_activity_cursor(&self, cursor: &str) -> Result<(i64, i16), Error> {
+        match Cursor::decode(cursor)?.key {
+            CursorKey::Activity { tx, kind } => Ok((tx, kind)),
+            _ => Err(Error::InvalidCursor(
+                "Expected an address activity cursor".to_string(),
+            )),
+        }
+    }
+
+    async fn multi_get_address_activity(
+        &self,
+        address: SuiAddress,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Option<(Vec<(i64, i16)>, bool)>, Error> {
+        let limit = self.validate_page_limit(first, last, PageCategory::Transactions)?;
+        let descending_order = last.is_some();
+        let before = before
+            .map(|cursor| self.parse_activity_cursor(&cursor))
+            .transpose()?;
+        let after = after
+            .map(|cursor| self.parse_activity_cursor(&cursor))
+            .transpose()?;
+
+        let address = address.into_vec();
+        let result: Option<Vec<(i64, i16)>> = self
+            .run_query_async_with_cost(
+                move || {
+                    Ok(QueryBuilder::multi_get_address_activity(
+                        address.clone(),
+                        before,
+                        after,
+                        descending_order,
+                        limit,
+                    ))
+                },
+                |query| move |conn| query.load(conn).optional().map(record_rows),
+            )
+            .await?;
+
+        Ok(result.map(|mut activity| {
+            let has_more = activity.len() as i64 > limit;
+            if has_more {
+                activity.pop();
+            }
+
+            if descending_order {
+                activity.reverse();
+            }
+
+            (activity, has_more)
+        }))
+    }
+
+    pub(crate) fn parse_day_cursor(&self, cursor: &str) -> Result<i64, Error> {
+        match Cursor::decode(cursor)?.key {
+            CursorKey::Day(day) => Ok(day),
+            _ => Err(Error::InvalidCursor(
+                "Expected a balance history cursor".to_string(),
+            )),
+        }
+    }
+
+    async fn multi_get_balance_history(
+        &self,
+        address: SuiAddress,
+        coin_type: String,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Option<(Vec<(i64, i64, Option<i64>)>, bool)>, Error> {
+        let limit = self.validate_page_limit(first, last, PageCategory::Coins)?;
+        let before = before
+            .map(|cursor| self.parse_day_cursor(&cursor))
+            .transpose()?;
+        let after = after
+            .map(|cursor| self.parse_day_cursor(&cursor))
+            .transpose()?;
+
+        // The page is bounded to a window of days, so that the query only scans the checkpoints
+        // (and coin versions) that it needs to.
+        let (Some(earliest), Some(latest)) = (
+            self.get_earliest_complete_checkpoint().await?,
+            self.get_checkpoint(None, None).await?,
+        ) else {
+            return Ok(Some((vec![], false)));
+        };
+
+        let Some((days, has_next_page)) = balance_history_window(
+            earliest.timestamp_ms / MS_PER_DAY,
+            latest.timestamp_ms / MS_PER_DAY,
+            after,
+            before,
+            limit,
+            last.is_some(),
+        ) else {
+            return Ok(Some((vec![], false)));
+        };
+
+        let address = address.into_vec();
+        let result: Option<Vec<(i64, i64, Option<i64>)>> = self
+            .run_query_async_with_cost(
+                move || {
+                    Ok(QueryBuilder::multi_get_balance_history(
+                        address.clone(),
+                        coin_type.clone(),
+                        days.clone(),
+                    ))
+                },
+                |query| move |conn| query.load(conn).optional().map(record_rows),
+            )
+            .await?;
+
+        Ok(result.map(|days| (days, has_next_page)))
+    }
+
+    async fn multi_get_txs_by_sequence_numbers(
+        &self,
+        tx_sequence_numbers: Vec<i64>,
+    ) -> Result<Vec<StoredTransaction>, Error> {
+        if tx_sequence_numbers.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let result: Option<Vec<StoredTransaction>> = self
+            .run_query_async_with_cost(
+                move || {
+                    Ok(QueryBuilder::multi_get_txs_by_sequence_numbers(
+                        tx_sequence_numbers.clone(),
+                    ))
+                },
+                |query| move |conn| query.load(conn).optional().map(record_rows),
+            )
+            .await?;
+
+        Ok(result.unwrap_or_default())
+    }
+
+    async fn multi_get_txs_by_digests(
+        &self,
+        digests: Vec<Vec<u8>>,
+    ) -> Result<Vec<StoredTransaction>, Error> {
+        if digests.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let result: Option<Vec<StoredTransaction>> = self
+            .run_query_async_with_cost(
+                move || Ok(QueryBuilder::multi_get_txs_by_digests(digests.clone())),
+                |query| move |conn| query.load(conn).optional().map(record_rows),
+            )
+            .await?;
+
+        Ok(result.unwrap_or_default())
+    }
+
     async fn multi_get_objs(
         &self,
         first: Option<u64>,
@@ -529,28 +971,95 @@ impl PgManager {
         before: Option<String>,
         filter: Option<ObjectFilter>,
         owner_type: Option<OwnerType>,
-    ) -> Result<Option<(Vec<StoredObject>, bool)>, Error> {
-        let limit = self.validate_page_limit(first, last)?;
+    ) -> Result<Option<(Vec<StoredObject>, bool, Option<u64>)>, Error> {
+        let limit = self.validate_page_limit(first, last, PageCategory::Objects)?;
         let before = before
-            .map(|cursor| self.parse_obj_cursor(&cursor))
+            .map(|cursor| self.parse_obj_cursor_at(&cursor))
             .transpose()?;
         let after = after
-            .map(|cursor| self.parse_obj_cursor(&cursor))
+            .map(|cursor| self.parse_obj_cursor_at(&cursor))
             .transpose()?;
 
-        let query = move || {
-            QueryBuilder::multi_get_objs(
-                before.clone(),
-                after.clone(),
-                limit,
-                filter.clone(),
-                owner_type,
-            )
+        let cursor_checkpoint = match (&before, &after) {
+            (Some((_, before)), Some((_, after))) if before != after => {
+                return Err(Error::InvalidCursor(
+                    "Cursors were read at different checkpoints".to_string(),
+                ));
+            }
+            (Some((_, checkpoint)), _) | (_, Some((_, checkpoint))) => *checkpoint,
+            (None, None) => None,
         };
 
-        let result: Option<Vec<StoredObject>> = self
-            .run_query_async_with_cost(query, |query| move |conn| query.load(conn).optional())
-            .await?;
+        let before = before.map(|(object_id, _)| object_id);
+        let after = after.map(|(object_id, _)| object_id);
+
+        // Nested objects are expanded as far as the service allows.
+        let nested_depth = filter
+            .as_ref()
+            .and_then(|f| f.include_nested)
+            .unwrap_or(false)
+            .then(|| self.max_ownership_depth());
+
+        // Objects are read as of the checkpoint that the first page was read at, so that later
+        // pages are consistent with it. The first page is read from the live objects table, which
+        // can be ahead of the checkpoints table, so pages are pinned to the latest checkpoint
+        // that the objects table reflects. Nested ownership is only expanded over live objects,
+        // so those connections are not pinned to a checkpoint, and do not need to know the
+        // latest checkpoint. If no objects have been indexed yet, there are no objects to read.
+        let (checkpoint, latest) = if nested_depth.is_some() {
+            (None, None)
+        } else {
+            let latest = self.get_latest_object_checkpoint().await?;
+            (cursor_checkpoint.or(latest), latest)
+        };
+
+        let result: Option<Vec<StoredObject>> = match (checkpoint, latest) {
+            (Some(checkpoint), Some(latest)) if checkpoint < latest => {
+                let query = move || {
+                    QueryBuilder::multi_get_objs_at_checkpoint(
+                        before.clone(),
+                        after.clone(),
+                        limit,
+                        filter.clone(),
+                        owner_type,
+                        checkpoint as i64,
+                    )
+                };
+
+                let history: Option<Vec<StoredHistoryObject>> = self
+                    .run_query_async_with_cost(query, |query| {
+                        move |conn| query.load(conn).optional().map(record_rows)
+                    })
+                    .await?;
+
+                history
+                    .map(|objs| {
+                        objs.into_iter()
+                            .map(StoredObject::try_from)
+                            .collect::<Result<Vec<_>, _>>()
+                            .map_err(|e| Error::Internal(e.to_string()))
+                    })
+                    .transpose()?
+            }
+
+            _ => {
+                let query = move || {
+                    QueryBuilder::multi_get_objs(
+                        before.clone(),
+                        after.clone(),
+                        limit,
+                        filter.clone(),
+                        owner_type,
+                        nested_depth,
+                    )
+                };
+
+                self.run_query_async_with_cost(query, |query| {
+                    move |conn| query.load(conn).optional().map(record_rows)
+                })
+                .await?
+            }
+        };
 
         result
             .map(|mut stored_objs| {
@@ -563,7 +1072,92 @@ impl PgManager {
                     stored_objs.reverse();
                 }
 
-                Ok((stored_objs, has_next_page))
+                Ok((stored_objs, has_next_page, checkpoint))
+            })
+            .transpose()
+    }
+
+    async fn multi_get_objs_by_ids(
+        &self,
+        object_ids: Vec<Vec<u8>>,
+    ) -> Result<Vec<StoredObject>, Error> {
+        if object_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let result: Option<Vec<StoredObject>> = self
+            .run_query_async_with_cost(
+                move || Ok(QueryBuilder::multi_get_objs_by_ids(object_ids.clone())),
+                |query| move |conn| query.load(conn).optional().map(record_rows),
+            )
+            .await?;
+
+        Ok(result.unwrap_or_default())
+    }
+
+    async fn multi_get_history_objs(
+        &self,
+        refs: Vec<(Vec<u8>, i64, Vec<u8>)>,
+    ) -> Result<Vec<StoredHistoryObject>, Error> {
+        if refs.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let result: Option<Vec<StoredHistoryObject>> = self
+            .run_query_async_with_cost(
+                move || Ok(QueryBuilder::multi_get_history_objs(refs.clone())),
+                |query| move |conn| query.load(conn).optional().map(record_rows),
+            )
+            .await?;
+
+        Ok(result.unwrap_or_default())
+    }
+
+    async fn multi_get_object_versions(
+        &self,
+        object_id: Vec<u8>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Option<(Vec<StoredHistoryObject>, bool)>, Error> {
+        let limit = self.validate_page_limit(first, last, PageCategory::Objects)?;
+        let before = before
+            .map(|cursor| self.parse_version_cursor(&cursor))
+            .transpose()?;
+        let after = after
+            .map(|cursor| self.parse_version_cursor(&cursor))
+            .transpose()?;
+
+        // Pages read from the back are read in descending order of version, and then reversed.
+        let descending_order = last.is_some();
+        let result: Option<Vec<StoredHistoryObject>> = self
+            .run_query_async_with_cost(
+                move || {
+                    Ok(QueryBuilder::multi_get_object_versions(
+                        object_id.clone(),
+                        before,
+                        after,
+                        descending_order,
+                        limit,
+                    ))
+                },
+                |query| move |conn| query.load(conn).optional().map(record_rows),
+            )
+            .await?;
+
+        result
+            .map(|mut stored_objs| {
+                let has_more = stored_objs.len() as i64 > limit;
+                if has_more {
+                    stored_objs.pop();
+                }
+
+                if descending_order {
+                    stored_objs.reverse();
+                }
+
+                Ok((stored_objs, has_more))
             })
             .transpose()
     }
@@ -571,6 +1165,13 @@ impl PgManager {
 
 /// Implement methods to be used by graphql resolvers
 impl PgManager {
+    pub(crate) fn parse_version_cursor(&self, cursor: &str) -> Result<i64, Error> {
+        let version = cursor
+            .parse::<i64>()
+            .map_err(|_| Error::InvalidCursor("object version".to_string()))?;
+        Ok(version)
+    }
+
     pub(crate) fn parse_tx_cursor(&self, cursor: &str) -> Result<i64, Error> {
         let tx_sequence_number = cursor
             .parse::<i64>()
@@ -578,90 +1179,122 @@ impl PgManager {
         Ok(tx_sequence_number)
     }
 
+    /// Parse a cursor for objects paginated by ID. Also accepts the legacy format for these
+    /// cursors, which was the object's ID.
     pub(crate) fn parse_obj_cursor(&self, cursor: &str) -> Result<Vec<u8>, Error> {
-        Ok(SuiAddress::from_str(cursor)
-            .map_err(|e| Error::InvalidCursor(e.to_string()))?
-            .into_vec())
-    }
-
-    pub(crate) fn build_event_cursor(&self, event: &StoredEvent) -> String {
-        format!(
-            "{}:{}",
-            event.tx_sequence_number, event.event_sequence_number
-        )
+        Ok(self.parse_obj_cursor_at(cursor)?.0)
     }
 
-    pub(crate) fn validate_package_dependencies(
+    /// Parse a cursor for objects paginated by ID, along with the checkpoint it was read at, if
+    /// any. Cursors in the legacy format are not associated with a checkpoint.
+    pub(crate) fn parse_obj_cursor_at(
         &self,
-        package: Option<&SuiAddress>,
-        module: Option<&String>,
-        function: Option<&String>,
-    ) -> Result<(), Error> {
-        if function.is_some() {
-            if package.is_none() || module.is_none() {
-                return Err(DbValidationError::RequiresPackageAndModule.into());
-            }
-        } else if module.is_some() && package.is_none() {
-            return Err(DbValidationError::RequiresPackage.into());
+        cursor: &str,
+    ) -> Result<(Vec<u8>, Option<u64>), Error> {
+        match Cursor::decode(cursor) {
+            Ok(Cursor {
+                key: CursorKey::Object(object_id),
+                checkpoint,
+            }) => Ok((object_id.to_vec(), checkpoint)),
+            Ok(_) => Err(Error::InvalidCursor(
+                "Expected an object cursor".to_string(),
+            )),
+            Err(e) => Ok((
+                SuiAddress::from_str(cursor).map_err(|_| e)?.into_vec(),
+                None,
+            )),
         }
-        Ok(())
     }
 
-    pub(crate) fn validate_tx_block_filter(
-        &self,
-        filter: &TransactionBlockFilter,
-    ) -> Result<(), Error> {
-        if filter.at_checkpoint.is_some()
-            && (filter.before_checkpoint.is_some() || filter.after_checkpoint.is_some())
-        {
-            return Err(DbValidationError::InvalidCheckpointCombination.into());
-        }
-        if let (Some(before), Some(after)) = (filter.before_checkpoint, filter.after_checkpoint) {
-            if before <= after {
-                return Err(DbValidationError::InvalidCheckpointOrder.into());
-            }
-        }
-        self.validate_package_dependencies(
-            filter.package.as_ref(),
-            filter.module.as_ref(),
-            filter.function.as_ref(),
-        )?;
-        Ok(())
+    /// Parse a cursor for coins paginated by balance. Also accepts the legacy format for these
+    /// cursors, `{balance}:{object_id}`.
+    pub(crate) fn parse_coin_balance_cursor(&self, cursor: &str) -> Result<(i64, Vec<u8>), Error> {
+        let e = match Cursor::decode(cursor) {
+            Ok(Cursor {
+                key: CursorKey::CoinBalance { balance, object_id },
+                ..
+            }) => return Ok((balance, object_id.to_vec())),
+            Ok(_) => return Err(Error::InvalidCursor("Expected a coin cursor".to_string())),
+            Err(e) => e,
+        };
+
+        let (balance, object_id) = cursor.split_once(':').ok_or(e)?;
+        let balance = balance.parse::<i64>().map_err(|_| {
+            Error::InvalidCursor("Failed to parse balance from coin cursor".to_string())
+        })?;
+
+        let object_id = SuiAddress::from_str(object_id)
+            .map_err(|e| Error::InvalidCursor(e.to_string()))?
+            .into_vec();
+
+        Ok((balance, object_id))
     }
 
-    pub(crate) fn validate_obj_filter(&self, filter: &ObjectFilter) -> Result<(), Error> {
-        if filter.object_keys.is_some() {
-            return Err(DbValidationError::UnsupportedObjectKeys.into());
+    pub(crate) fn build_event_cursor(&self, event: &StoredEvent) -> String {
+        CursorKey::Event {
+            tx: event.tx_sequence_number,
+            event: event.event_sequence_number,
         }
-
-        Ok(())
+        .encode()
     }
 
     pub(crate) fn validate_page_limit(
         &self,
         first: Option<u64>,
         last: Option<u64>,
+        category: PageCategory,
     ) -> Result<i64, Error> {
-        if let Some(f) = first {
-            if f > self.limits.max_page_size {
-                return Err(
-                    DbValidationError::PageSizeExceeded(f, self.limits.max_page_size).into(),
-                );
+        let max_page_size = self.max_page_size(category);
+        if !self.limits.clamp_page_size {
+            if let Some(f) = first {
+                if f > max_page_size {
+                    return Err(DbValidationError::PageSizeExceeded(f, max_page_size).into());
+                }
             }
-        }
 
-        if let Some(l) = last {
-            if l > self.limits.max_page_size {
-                return Err(
-                    DbValidationError::PageSizeExceeded(l, self.limits.max_page_size).into(),
-                );
+            if let Some(l) = last {
+                if l > max_page_size {
+                    return Err(DbValidationError::PageSizeExceeded(l, max_page_size).into());
+                }
             }
         }
 
         // TODO (wlmyng): even though we do not allow passing in both first and last,
         // per the cursor connection specs, if both are provided, from the response,
         // we need to take the first F from the left and then take the last L from the right.
-        Ok(first.or(last).unwrap_or(self.limits.default_page_size) as i64)
+        let limit = first.or(last).unwrap_or(self.limits.default_page_size);
+        Ok(limit.min(max_page_size) as i64)
+    }
+
+    /// The largest page that can be requested from a connection in `category`, taking into
+    /// account overrides for the field being resolved, and then for the category as a whole.
+    fn max_page_size(&self, category: PageCategory) -> u64 {
+        page_size::current_field()
+            .and_then(|field| self.max_page_sizes.get(&field))
+            .or_else(|| self.max_page_sizes.get(category.name()))
+            .copied()
+            .unwrap_or(self.limits.max_page_size)
+    }
+
+    /// The number of levels of nested ownership to expand for an object connection, taking into
+    /// account overrides for the field being resolved, and then for object connections as a
+    /// whole.
+    fn max_ownership_depth(&self) -> u32 {
+        page_size::current_field()
+            .and_then(|field| self.max_ownership_depths.get(&field))
+            .or_else(|| self.max_ownership_depths.get(PageCategory::Objects.name()))
+            .copied()
+            .unwrap_or(self.limits.max_ownership_depth)
+    }
+
+    /// Check that a batch lookup for `size` keys is within the service's limits.
+    pub(crate) fn validate_multi_get_size(&self, size: usize) -> Result<(), Error> {
+        let max = self.limits.max_multi_get_size;
+        if size as u64 > max {
+            return Err(DbValidationError::MultiGetSizeExceeded(size as u64, max).into());
+        }
+
+        Ok(())
     }
 
     pub(crate) async fn fetch_tx(&self, digest: &str) -> Result<Option<TransactionBlock>, Error> {
@@ -673,6 +1306,36 @@ impl PgManager {
             .transpose()
     }
 
+    /// Fetch the transactions with the given digests, in one query. Each entry in the result
+    /// corresponds to the digest at the same position in `digests`, and is `None` if no such
+    /// transaction exists.
+    pub(crate) async fn fetch_txs_by_digests(
+        &self,
+        digests: Vec<String>,
+    ) -> Result<Vec<Option<TransactionBlock>>, Error> {
+        self.validate_multi_get_size(digests.len())?;
+
+        let digests = digests
+            .iter()
+            .map(|digest| Ok(Digest::from_str(digest)?.into_vec()))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut txs = BTreeMap::new();
+        for stored in self.multi_get_txs_by_digests(digests.clone()).await? {
+            txs.insert(stored.transaction_digest.clone(), stored);
+        }
+
+        digests
+            .iter()
+            .map(|digest| {
+                txs.get(digest)
+                    .cloned()
+                    .map(TransactionBlock::try_from)
+                    .transpose()
+            })
+            .collect()
+    }
+
     pub(crate) async fn fetch_latest_epoch(&self) -> Result<Epoch, Error> {
         let result = self
             .get_epoch(None)
@@ -699,6 +1362,53 @@ impl PgManager {
         }
     }
 
+    /// Totals for the epoch described by `stored`. Once an epoch has ended, these are recorded on
+    /// the epoch itself, otherwise they are aggregated from the epoch's checkpoints so far.
+    pub(crate) async fn fetch_epoch_aggregates(
+        &self,
+        stored: &StoredEpochInfo,
+    ) -> Result<EpochAggregates, Error> {
+        if let (Some(last_checkpoint), Some(transaction_count)) =
+            (stored.last_checkpoint_id, stored.epoch_total_transactions)
+        {
+            return Ok(EpochAggregates {
+                transaction_count: Some(BigInt::from(transaction_count)),
+                total_gas_fees: stored.total_gas_fees.map(BigInt::from),
+                total_stake_rewards: stored.total_stake_rewards_distributed.map(BigInt::from),
+                first_checkpoint: Some(stored.first_checkpoint_id as u64),
+                last_checkpoint: Some(last_checkpoint as u64),
+            });
+        }
+
+        let (first_checkpoint, last_checkpoint, transactions_before, transactions_after) =
+            self.get_epoch_aggregates(stored.epoch).await?;
+
+        let transaction_count = match (transactions_before, transactions_after) {
+            (Some(before), Some(after)) => Some(BigInt::from(after - before)),
+            _ => None,
+        };
+
+        // Checkpoints carry a rolling summary of the gas charged in their epoch, so the epoch's
+        // latest checkpoint reflects the gas charged in the epoch so far.
+        let total_gas_fees = match last_checkpoint {
+            Some(last) => self
+                .get_checkpoint(None, Some(last))
+                .await?
+                .map(|checkpoint| BigInt::from(checkpoint.total_gas_cost)),
+            None => None,
+        };
+
+        Ok(EpochAggregates {
+            transaction_count,
+            total_gas_fees,
+            total_stake_rewards: None,
+            first_checkpoint: first_checkpoint
+                .or(Some(stored.first_checkpoint_id))
+                .map(|cp| cp as u64),
+            last_checkpoint: last_checkpoint.map(|cp| cp as u64),
+        })
+    }
+
     pub(crate) async fn fetch_latest_checkpoint(&self) -> Result<Checkpoint, Error> {
         let stored_checkpoint = self.get_checkpoint(None, None).await?;
         match stored_checkpoint {
@@ -707,6 +1417,41 @@ impl PgManager {
         }
     }
 
+    /// Transactions per second over the last `window` checkpoints (or fewer, if the current epoch
+    /// has not yet produced that many), from the difference in the network's running transaction
+    /// total across them.
+    pub(crate) async fn fetch_tps(&self, window: u64) -> Result<Option<f64>, Error> {
+        let Some(latest) = self.get_checkpoint(None, None).await? else {
+            return Ok(None);
+        };
+
+        let first_in_epoch = self
+            .get_epoch(Some(latest.epoch))
+            .await?
+            .map_or(0, |epoch| epoch.first_checkpoint_id);
+
+        let window = i64::try_from(window).unwrap_or(i64::MAX);
+        let start = latest
+            .sequence_number
+            .saturating_sub(window)
+            .max(first_in_epoch);
+        if start >= latest.sequence_number {
+            return Ok(None);
+        }
+
+        let Some(start) = self.get_checkpoint(None, Some(start)).await? else {
+            return Ok(None);
+        };
+
+        let elapsed_ms = latest.timestamp_ms - start.timestamp_ms;
+        if elapsed_ms <= 0 {
+            return Ok(None);
+        }
+
+        let transactions = latest.network_total_transactions - start.network_total_transactions;
+        Ok(Some(transactions as f64 * 1000.0 / elapsed_ms as f64))
+    }
+
     pub(crate) async fn fetch_checkpoint(
         &self,
         digest: Option<&str>,
@@ -737,6 +1482,10 @@ impl PgManager {
         Ok(result.to_string())
     }
 
+    pub(crate) async fn fetch_chain(&self) -> Result<Chain, Error> {
+        Ok(self.get_chain_identifier().await?.chain())
+    }
+
     pub(crate) async fn fetch_txs_for_address(
         &self,
         first: Option<u64>,
@@ -777,7 +1526,7 @@ impl PgManager {
     ) -> Result<Option<Connection<String, TransactionBlock>>, Error> {
         validate_cursor_pagination(&first, &after, &last, &before)?;
         if let Some(filter) = &filter {
-            self.validate_tx_block_filter(filter)?;
+            validate_tx_block_filter(filter, &self.limits)?;
         }
 
         let transactions = self
@@ -813,6 +1562,8 @@ impl PgManager {
             after_checkpoint: None,
             at_checkpoint: None,
             before_checkpoint: None,
+            after_time: None,
+            before_time: None,
             sign_address: None,
             sent_address: None,
             recv_address: None,
@@ -848,6 +1599,93 @@ impl PgManager {
         stored_obj.map(Object::try_from).transpose()
     }
 
+    /// Fetch the exact versions of objects identified by `refs`, in one query. The result lines up
+    /// with `refs`: an entry is `None` if that version of the object is unknown, its digest does
+    /// not match, or the object was wrapped or deleted at that version.
+    pub(crate) async fn fetch_objs_by_refs(
+        &self,
+        refs: Vec<ObjectRef>,
+    ) -> Result<Vec<Option<Object>>, Error> {
+        self.validate_multi_get_size(refs.len())?;
+
+        let keys = refs
+            .iter()
+            .map(|r| {
+                Ok((
+                    r.address.into_vec(),
+                    r.version as i64,
+                    Digest::from_str(&r.digest)?.into_vec(),
+                ))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut objects = BTreeMap::new();
+        for stored in self.multi_get_history_objs(keys.clone()).await? {
+            if stored.object_status != ObjectStatus::Active as i16 {
+                continue;
+            }
+
+            let key = (stored.object_id.clone(), stored.object_version);
+            objects.insert(key, Object::try_from(stored)?);
+        }
+
+        Ok(keys
+            .into_iter()
+            .map(|(id, version, _)| objects.get(&(id, version)).cloned())
+            .collect())
+    }
+
+    /// Fetch the live objects with the given IDs, in one query. Each entry in the result
+    /// corresponds to the ID at the same position in `ids`, and is `None` if no such object
+    /// exists, or it is currently wrapped or deleted.
+    pub(crate) async fn fetch_objs_by_ids(
+        &self,
+        ids: Vec<SuiAddress>,
+    ) -> Result<Vec<Option<Object>>, Error> {
+        self.validate_multi_get_size(ids.len())?;
+
+        let ids: Vec<_> = ids.into_iter().map(|id| id.into_vec()).collect();
+        let mut objects = BTreeMap::new();
+        for stored in self.multi_get_objs_by_ids(ids.clone()).await? {
+            objects.insert(stored.object_id.clone(), Object::try_from(stored)?);
+        }
+
+        Ok(ids.iter().map(|id| objects.get(id).cloned()).collect())
+    }
+
+    /// Fetch the versions of the object at `address` (excluding versions at which it was wrapped
+    /// or deleted), in ascending order of version. Versions are only available as far back as the
+    /// indexer retains object history.
+    pub(crate) async fn fetch_obj_versions(
+        &self,
+        address: SuiAddress,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Option<Connection<String, Object>>, Error> {
+        validate_cursor_pagination(&first, &after, &last, &before)?;
+        let versions = self
+            .multi_get_object_versions(address.into_vec(), first, after, last, before)
+            .await?;
+
+        let Some((stored_objs, has_more)) = versions else {
+            return Ok(None);
+        };
+
+        // Pages read from the back have more versions before them, rather than after them.
+        let backward = last.is_some();
+        let mut connection = Connection::new(backward && has_more, !backward && has_more);
+        for stored_obj in stored_objs {
+            let cursor = stored_obj.object_version.to_string();
+            connection
+                .edges
+                .push(Edge::new(cursor, Object::try_from(stored_obj)?));
+        }
+
+        Ok(Some(connection))
+    }
+
     pub(crate) async fn fetch_move_obj(
         &self,
         address: SuiAddress,
@@ -936,22 +1774,21 @@ impl PgManager {
     ) -> Result<Option<Connection<String, Object>>, Error> {
         validate_cursor_pagination(&first, &after, &last, &before)?;
         if let Some(filter) = &filter {
-            self.validate_obj_filter(filter)?;
+            validate_obj_filter(filter)?;
         }
         let objects = self
             .multi_get_objs(first, after, last, before, filter, None)
             .await?;
 
-        if let Some((stored_objs, has_next_page)) = objects {
+        if let Some((stored_objs, has_next_page, checkpoint)) = objects {
             let mut connection = Connection::new(false, has_next_page);
-            connection
-                .edges
-                .extend(stored_objs.into_iter().filter_map(|stored_obj| {
-                    Object::try_from(stored_obj)
-                        .map_err(|e| eprintln!("Error converting object: {:?}", e))
-                        .ok()
-                        .map(|obj| Edge::new(obj.address.to_string(), obj))
-                }));
+            for stored_obj in stored_objs {
+                let cursor = CursorKey::object(&stored_obj.object_id)?.encode_at(checkpoint);
+                match Object::try_from(stored_obj) {
+                    Ok(obj) => connection.edges.push(Edge::new(cursor, obj)),
+                    Err(e) => eprintln!("Error converting object: {:?}", e),
+                }
+            }
             Ok(Some(connection))
         } else {
             Ok(None)
@@ -1028,10 +1865,11 @@ impl PgManager {
         after: Option<String>,
         last: Option<u64>,
         before: Option<String>,
+        at_checkpoint: Option<u64>,
     ) -> Result<Option<Connection<String, Balance>>, Error> {
         let address = address.into_vec();
         let Some(balances) = self
-            .multi_get_balances(address, first, after, last, before)
+            .multi_get_balances(address, first, after, last, before, at_checkpoint)
             .await?
         else {
             return Ok(None);
@@ -1064,10 +1902,12 @@ impl PgManager {
     /// Fetches all coins owned by the given address that match the given coin type.
     /// If no address is given, then it will fetch all coin objects of the given type.
     /// If no coin type is provided, it will use the default gas coin (SUI).
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn fetch_coins(
         &self,
         address: Option<SuiAddress>,
         coin_type: Option<String>,
+        order: Option<CoinOrder>,
         first: Option<u64>,
         after: Option<String>,
         last: Option<u64>,
@@ -1078,8 +1918,9 @@ impl PgManager {
             GAS::type_().to_canonical_string(/* with_prefix */ true)
         });
 
+        let order = order.unwrap_or_default();
         let coins = self
-            .multi_get_coins(address, coin_type, first, after, last, before)
+            .multi_get_coins(address, coin_type, order, first, after, last, before)
             .await?;
 
         let Some((stored_objs, has_next_page)) = coins else {
@@ -1088,6 +1929,20 @@ impl PgManager {
 
         let mut connection = Connection::new(false, has_next_page);
         for stored_obj in stored_objs {
+            let cursor = match (order, stored_obj.coin_balance) {
+                (CoinOrder::ObjectId, _) => CursorKey::object(&stored_obj.object_id)?,
+                (CoinOrder::BalanceDesc, Some(balance)) => {
+                    CursorKey::coin_balance(balance, &stored_obj.object_id)?
+                }
+                (CoinOrder::BalanceDesc, None) => {
+                    return Err(Error::Internal(format!(
+                        "Coin {} has no balance in the index",
+                        SuiAddress::from_bytes(&stored_obj.object_id)
+                            .map_err(|e| Error::Internal(e.to_string()))?,
+                    )));
+                }
+            };
+
             let object = Object::try_from(stored_obj)?;
 
             let move_object = MoveObject::try_from(&object).map_err(|_| {
@@ -1104,66 +1959,14 @@ impl PgManager {
                 ))
             })?;
 
-            let cursor = move_object
-                .native
-                .id()
-                .to_canonical_string(/* with_prefix */ true);
-
-            connection.edges.push(Edge::new(cursor, coin_object));
+            connection
+                .edges
+                .push(Edge::new(cursor.encode(), coin_object));
         }
 
         Ok(Some(connection))
     }
 
-    pub(crate) async fn resolve_name_service_address(
-        &self,
-        name_service_config: &NameServiceConfig,
-        name: String,
-    ) -> Result<Option<Address>, Error> {
-        let domain = name.parse::<Domain>()?;
-
-        let record_id = name_service_config.record_field_id(&domain);
-
-        let field_record_object = match self.inner.get_object_in_blocking_task(record_id).await? {
-            Some(o) => o,
-            None => return Ok(None),
-        };
-
-        let record = field_record_object
-            .to_rust::<Field<Domain, NameRecord>>()
-            .ok_or_else(|| Error::Internal(format!("Malformed Object {record_id}")))?
-            .value;
-
-        Ok(record.target_address.map(|address| Address {
-            address: SuiAddress::from_array(address.to_inner()),
-        }))
-    }
-
-    pub(crate) async fn default_name_service_name(
-        &self,
-        name_service_config: &NameServiceConfig,
-        address: SuiAddress,
-    ) -> Result<Option<String>, Error> {
-        let reverse_record_id =
-            name_service_config.reverse_record_field_id(NativeSuiAddress::from(address));
-
-        let field_reverse_record_object = match self
-            .inner
-            .get_object_in_blocking_task(reverse_record_id)
-            .await?
-        {
-            Some(o) => o,
-            None => return Ok(None),
-        };
-
-        let domain = field_reverse_record_object
-            .to_rust::<Field<SuiAddress, Domain>>()
-            .ok_or_else(|| Error::Internal(format!("Malformed Object {reverse_record_id}")))?
-            .value;
-
-        Ok(Some(domain.to_string()))
-    }
-
     pub(crate) async fn fetch_latest_sui_system_state(
         &self,
     ) -> Result<SuiSystemStateSummary, Error> {
@@ -1218,6 +2021,50 @@ impl PgManager {
         })
     }
 
+    /// Execute `tx_data` against the latest state of the chain, without committing its effects,
+    /// returning the effects it would have, and the changes it would make to balances. If the
+    /// transaction does not provide any gas coins, a mock coin pays for its gas.
+    pub(crate) async fn dry_run_tx(
+        &self,
+        tx_data: NativeTransactionData,
+    ) -> Result<(NativeTransactionEffects, Vec<NativeBalanceChange>), Error> {
+        let epoch = self
+            .get_epoch(None)
+            .await?
+            .ok_or_else(|| Error::Internal("Latest epoch not found".to_string()))?;
+
+        let chain = self.get_chain_identifier().await?.chain();
+        let version = ProtocolVersion::from(epoch.protocol_version as u64);
+        let protocol_config = ProtocolConfig::get_for_version_if_supported(version, chain)
+            .ok_or_else(|| {
+                Error::Internal(format!(
+                    "Protocol version {} is not supported",
+                    version.as_u64()
+                ))
+            })?;
+
+        let input_objects = tx_data.input_objects().map_err(invalid_tx)?;
+        let mock_gas = tx_data.gas().is_empty().then(ObjectID::random);
+
+        let (effects, inner_temporary_store) = self
+            .inner
+            .spawn_blocking(move |this| {
+                let store = DbExecutionStore(this);
+                dry_run(&store, &protocol_config, &epoch, tx_data, mock_gas)
+            })
+            .await?;
+
+        let objects = DryRunObjects {
+            pg: self,
+            inner_temporary_store: &inner_temporary_store,
+        };
+
+        let balance_changes =
+            get_balance_changes_from_effect(&objects, &effects, input_objects, mock_gas).await?;
+
+        Ok((effects, balance_changes))
+    }
+
     pub(crate) async fn fetch_staked_sui(
         &self,
         address: SuiAddress,
@@ -1227,10 +2074,15 @@ impl PgManager {
         before: Option<String>,
     ) -> Result<Option<Connection<String, StakedSui>>, Error> {
         let obj_filter = ObjectFilter {
-            type_: Some(MoveObjectType::staked_sui().to_canonical_string(/* with_prefix */ true)),
+            type_: Some(vec![
+                MoveObjectType::staked_sui().to_canonical_string(/* with_prefix */ true)
+            ]),
             owner: Some(address),
+            type_match: None,
+            kind: None,
             object_ids: None,
             object_keys: None,
+            include_nested: None,
         };
 
         let objs = self
@@ -1244,12 +2096,13 @@ impl PgManager {
             )
             .await?;
 
-        let Some((stored_objs, has_next_page)) = objs else {
+        let Some((stored_objs, has_next_page, checkpoint)) = objs else {
             return Ok(None);
         };
 
         let mut connection = Connection::new(false, has_next_page);
         for stored_obj in stored_objs {
+            let cursor = CursorKey::object(&stored_obj.object_id)?.encode_at(checkpoint);
             let object = Object::try_from(stored_obj)?;
 
             let move_object = MoveObject::try_from(&object).map_err(|_| {
@@ -1266,11 +2119,6 @@ impl PgManager {
                 ))
             })?;
 
-            let cursor = move_object
-                .native
-                .id()
-                .to_canonical_string(/* with_prefix */ true);
-
             connection.edges.push(Edge::new(cursor, stake_object));
         }
 
@@ -1313,6 +2161,11 @@ impl PgManager {
         before: Option<String>,
         filter: Option<EventFilter>,
     ) -> Result<Option<Connection<String, Event>>, Error> {
+        validate_cursor_pagination(&first, &after, &last, &before)?;
+        if let Some(filter) = &filter {
+            validate_event_filter(filter)?;
+        }
+
         let events = self
             .multi_get_events(first, after, last, before, filter)
             .await?;
@@ -1330,6 +2183,83 @@ impl PgManager {
         }
     }
 
+    pub(crate) async fn fetch_address_activity(
+        &self,
+        address: SuiAddress,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Option<Connection<String, AddressActivity>>, Error> {
+        let backward = last.is_some();
+        let Some((activity, has_more)) = self
+            .multi_get_address_activity(address, first, after, last, before)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let tx_sequence_numbers = activity.iter().map(|(tx, _)| *tx).collect();
+        let mut txs: BTreeMap<_, _> = self
+            .multi_get_txs_by_sequence_numbers(tx_sequence_numbers)
+            .await?
+            .into_iter()
+            .map(|stored| (stored.tx_sequence_number, stored))
+            .collect();
+
+        // Pages read from the back have more activity before them, rather than after them.
+        let mut connection = Connection::new(backward && has_more, !backward && has_more);
+        for (tx, kind) in activity {
+            let stored = txs.remove(&tx).ok_or_else(|| {
+                Error::Internal(format!("Transaction {tx} in address activity not found"))
+            })?;
+
+            let cursor = CursorKey::Activity { tx, kind }.encode();
+            let transaction_block = TransactionBlock::try_from(stored)?;
+            let activity = AddressActivity::new(address, kind.try_into()?, transaction_block)?;
+            connection.edges.push(Edge::new(cursor, activity));
+        }
+
+        Ok(Some(connection))
+    }
+
+    pub(crate) async fn fetch_balance_history(
+        &self,
+        address: SuiAddress,
+        coin_type: Option<String>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Option<Connection<String, DailyBalance>>, Error> {
+        validate_cursor_pagination(&first, &after, &last, &before)?;
+        let coin_type = parse_to_type_tag(coin_type)
+            .map_err(|e| Error::InvalidCoinType(e.to_string()))?
+            .to_canonical_string(/* with_prefix */ true);
+
+        let Some((days, has_next_page)) = self
+            .multi_get_balance_history(address, coin_type, first, after, last, before)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let mut connection = Connection::new(false, has_next_page);
+        connection
+            .edges
+            .extend(days.into_iter().map(|(day, checkpoint, balance)| {
+                let cursor = CursorKey::Day(day).encode();
+                let daily_balance = DailyBalance {
+                    date: DateTime::from_ms(day * MS_PER_DAY),
+                    checkpoint_sequence_number: checkpoint as u64,
+                    total_balance: Some(BigInt::from(balance.unwrap_or(0))),
+                };
+                Edge::new(cursor, daily_balance)
+            }));
+
+        Ok(Some(connection))
+    }
+
     pub(crate) async fn fetch_dynamic_fields(
         &self,
         first: Option<u64>,
@@ -1354,17 +2284,19 @@ impl PgManager {
             )
             .await?;
 
-        let Some((stored_objs, has_next_page)) = objs else {
+        let Some((stored_objs, has_next_page, checkpoint)) = objs else {
             return Ok(None);
         };
 
         let mut connection = Connection::new(false, has_next_page);
 
         for stored_obj in stored_objs {
+            // Dynamic fields are paginated by the IDs of their field objects.
+            let cursor = CursorKey::object(&stored_obj.object_id)?.encode_at(checkpoint);
             let df_object_id = stored_obj.df_object_id.as_ref().ok_or_else(|| {
                 Error::Internal("Dynamic field does not have df_object_id".to_string())
             })?;
-            let cursor = SuiAddress::from_bytes(df_object_id)
+            let df_object_id = SuiAddress::from_bytes(df_object_id)
                 .map_err(|e| Error::Internal(format!("{e}")))?;
             let df_kind = match stored_obj.df_kind {
                 None => Err(Error::Internal("Dynamic field type is not set".to_string())),
@@ -1376,10 +2308,10 @@ impl PgManager {
             }?;
 
             connection.edges.push(Edge::new(
-                cursor.to_string(),
+                cursor,
                 DynamicField {
                     stored_object: stored_obj,
-                    df_object_id: cursor,
+                    df_object_id,
                     df_kind,
                 },
             ));
@@ -1508,6 +2440,22 @@ impl TryFrom<StoredCheckpoint> for Checkpoint {
                     _ => None,
                 });
 
+        // Checkpoints indexed before their contents digests and signers were recorded store empty
+        // bytes for both.
+        let validator_signers: Option<Vec<u32>> = (!c.validator_signers.is_empty())
+            .then(|| {
+                bcs::from_bytes(&c.validator_signers).map_err(|e| {
+                    Error::Internal(format!(
+                        "Can't convert validator_signers into committee indices. Error: {e}",
+                    ))
+                })
+            })
+            .transpose()?;
+
+        let contents_digest = (!c.checkpoint_contents_digest.is_empty())
+            .then(|| Digest::try_from(c.checkpoint_contents_digest).map(|d| d.to_string()))
+            .transpose()?;
+
         let end_of_epoch_data: Option<NativeEndOfEpochData> = if c.end_of_epoch {
             c.end_of_epoch_data
                 .map(|data| {
@@ -1549,10 +2497,12 @@ impl TryFrom<StoredCheckpoint> for Checkpoint {
             sequence_number: c.sequence_number as u64,
             timestamp: DateTime::from_ms(c.timestamp_ms),
             validator_signature: Some(c.validator_signature.into()),
+            validator_signers,
             previous_checkpoint_digest: c
                 .previous_checkpoint_digest
                 .map(|d| Digest::try_from(d).map(|digest| digest.to_string()))
                 .transpose()?,
+            contents_digest,
             live_object_set_digest,
             network_total_transactions: Some(c.network_total_transactions as u64),
             rolling_gas_summary: Some(GasCostSummary {
@@ -0,0 +1,222 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use sui_indexer::indexer_reader::IndexerReader;
+
+use crate::metrics::DbMetrics;
+
+/// Number of most recently completed queries whose timings are kept for inspection.
+const RECENT_QUERIES: usize = 1_000;
+
+/// Tracks the queries sent to the database: how many are in flight, and how long recent queries
+/// spent waiting for a connection and running. Timings are also reported to metrics, if there are
+/// any.
+#[derive(Default)]
+pub(crate) struct DbQueryStats {
+    metrics: Option<DbMetrics>,
+    in_flight: AtomicUsize,
+    recent: Mutex<VecDeque<QueryTiming>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct QueryTiming {
+    /// Name of the function that issued the query.
+    pub tag: &'static str,
+    /// Time between the query being submitted and it being handed a connection.
+    pub wait: Duration,
+    /// Time spent running the query, once it had a connection.
+    pub duration: Duration,
+    /// Milliseconds since the Unix epoch at which the query finished running.
+    pub finished_at_ms: i64,
+}
+
+/// Tracks a single query while it is in flight, from when it is submitted until the timer is
+/// dropped.
+pub(crate) struct QueryTimer {
+    stats: Arc<DbQueryStats>,
+    tag: &'static str,
+    submitted: Instant,
+}
+
+impl DbQueryStats {
+    pub(crate) fn new(metrics: DbMetrics) -> Self {
+        Self {
+            metrics: Some(metrics),
+            ..Default::default()
+        }
+    }
+
+    /// Start tracking a query, tagged by the function that defines the closure (of type `Q`) that
+    /// builds or runs it.
+    pub(crate) fn start<Q>(self: &Arc<Self>) -> QueryTimer {
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        if let Some(metrics) = &self.metrics {
+            metrics.queries_in_flight.inc();
+        }
+
+        QueryTimer {
+            stats: self.clone(),
+            tag: query_tag::<Q>(),
+            submitted: Instant::now(),
+        }
+    }
+
+    /// Number of queries that are waiting for, or holding, a connection.
+    pub(crate) fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Up to `limit` of the slowest queries amongst the most recently completed ones, slowest
+    /// first.
+    pub(crate) fn slowest(&self, limit: usize) -> Vec<QueryTiming> {
+        let mut timings: Vec<_> = self.recent.lock().unwrap().iter().cloned().collect();
+        timings.sort_by(|a, b| b.duration.cmp(&a.duration));
+        timings.truncate(limit);
+        timings
+    }
+
+    /// Report the size of `reader`'s connection pool, identified as `pool`, to metrics.
+    pub(crate) fn observe_pool(&self, pool: &str, reader: &IndexerReader) {
+        let Some(metrics) = &self.metrics else {
+            return;
+        };
+
+        let state = reader.pool_state();
+        let labels = &[pool];
+        metrics
+            .pool_max_size
+            .with_label_values(labels)
+            .set(reader.pool_max_size() as i64);
+        metrics
+            .pool_connections
+            .with_label_values(labels)
+            .set(state.connections as i64);
+        metrics
+            .pool_idle_connections
+            .with_label_values(labels)
+            .set(state.idle_connections as i64);
+    }
+
+    fn record(&self, timing: QueryTiming) {
+        if let Some(metrics) = &self.metrics {
+            metrics.pool_wait_latency.observe(timing.wait.as_secs_f64());
+            metrics
+                .query_latency
+                .with_label_values(&[timing.tag])
+                .observe(timing.duration.as_secs_f64());
+        }
+
+        let mut recent = self.recent.lock().unwrap();
+        if recent.len() == RECENT_QUERIES {
+            recent.pop_front();
+        }
+        recent.push_back(timing);
+    }
+}
+
+impl QueryTimer {
+    /// Run `query`, which has just been handed a connection, and record how long it waited for
+    /// that connection, and how long it ran for.
+    pub(crate) fn time<T>(self, query: impl FnOnce() -> T) -> T {
+        let connected = Instant::now();
+        let result = query();
+        let finished_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_millis() as i64);
+
+        self.stats.record(QueryTiming {
+            tag: self.tag,
+            wait: connected - self.submitted,
+            duration: connected.elapsed(),
+            finished_at_ms,
+        });
+
+        result
+    }
+}
+
+impl Drop for QueryTimer {
+    fn drop(&mut self) {
+        self.stats.in_flight.fetch_sub(1, Ordering::Relaxed);
+        if let Some(metrics) = &self.stats.metrics {
+            metrics.queries_in_flight.dec();
+        }
+    }
+}
+
+/// Name of the function that the closure type `Q` is defined in (e.g. `multi_get_coins`), used to
+/// tag queries without having to thread a tag through every call site.
+pub(crate) fn query_tag<Q>() -> &'static str {
+    let mut path = std::any::type_name::<Q>();
+    while let Some(outer) = path.strip_suffix("::{{closure}}") {
+        path = outer;
+    }
+
+    // Drop generic parameters on the function itself, which may contain paths of their own.
+    if let Some(generic) = path.strip_suffix('>') {
+        let mut depth = 1;
+        for (i, c) in generic.char_indices().rev() {
+            match c {
+                '>' => depth += 1,
+                '<' if depth == 1 => {
+                    path = &generic[..i];
+                    break;
+                }
+                '<' => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag_of<Q>(_: &Q) -> &'static str {
+        query_tag::<Q>()
+    }
+
+    fn generic_query<T>() -> &'static str {
+        tag_of(&|| Option::<T>::None)
+    }
+
+    #[test]
+    fn queries_are_tagged_by_their_function() {
+        assert_eq!(tag_of(&|| ()), "queries_are_tagged_by_their_function");
+        assert_eq!(generic_query::<Vec<u8>>(), "generic_query");
+    }
+
+    #[test]
+    fn slowest_recent_queries() {
+        let stats = Arc::new(DbQueryStats::default());
+        for millis in [30, 10, 20] {
+            let timer = stats.start::<()>();
+            assert_eq!(stats.in_flight(), 1);
+            stats.record(QueryTiming {
+                tag: timer.tag,
+                wait: Duration::ZERO,
+                duration: Duration::from_millis(millis),
+                finished_at_ms: 0,
+            });
+        }
+
+        assert_eq!(stats.in_flight(), 0);
+        let slowest: Vec<_> = stats.slowest(2).into_iter().map(|t| t.duration).collect();
+        assert_eq!(
+            slowest,
+            vec![Duration::from_millis(30), Duration::from_millis(20)]
+        );
+    }
+}
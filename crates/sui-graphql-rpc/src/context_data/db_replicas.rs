@@ -0,0 +1,273 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    cell::Cell,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use diesel::RunQueryDsl;
+use sui_indexer::{errors::IndexerError, indexer_reader::IndexerReader};
+use tracing::{info, warn};
+
+/// How often replicas are probed to decide whether they should be sent queries.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+tokio::task_local! {
+    /// The database that reads for the current request are pinned to, once one has been chosen:
+    /// `Some(index)` for a replica, `None` for the primary.
+    static PINNED: Cell<Option<Option<usize>>>;
+}
+
+/// Read replicas of the primary database, that requests are spread across round-robin.
+///
+/// All the reads for a request (run inside `pin_replica`) go to the same replica, so that they
+/// see the same replication position, rather than one read seeing a checkpoint that a later read
+/// on a lagging replica cannot find. Outside of a request, each read picks the next replica.
+///
+/// Replicas that fail a health check, or fail to provide a connection, are taken out of rotation
+/// until they pass a health check again. If no replica is healthy, reads fail over to the
+/// primary, as do reads that cannot connect to their replica. A request whose replica leaves the
+/// rotation is served by the primary from then on. Cloning is cheap, and clones share health and
+/// rotation state.
+pub(crate) struct ReadReplicas<R = IndexerReader> {
+    replicas: Arc<[Replica<R>]>,
+    next: Arc<AtomicUsize>,
+}
+
+struct Replica<R> {
+    reader: R,
+    healthy: AtomicBool,
+}
+
+impl<R> Clone for ReadReplicas<R> {
+    fn clone(&self) -> Self {
+        Self {
+            replicas: self.replicas.clone(),
+            next: self.next.clone(),
+        }
+    }
+}
+
+impl<R> ReadReplicas<R> {
+    pub(crate) fn new(readers: Vec<R>) -> Self {
+        Self {
+            replicas: readers
+                .into_iter()
+                .map(|reader| Replica {
+                    reader,
+                    healthy: AtomicBool::new(true),
+                })
+                .collect(),
+            next: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// The replica to send the next read to, along with its index (to report failures against),
+    /// or `None` if it should go to the primary. Within `pin_replica`, this is the replica the
+    /// request's first read was sent to, for as long as that replica stays healthy.
+    pub(crate) fn next(&self) -> Option<(usize, &R)> {
+        let Ok(pinned) = PINNED.try_with(|pinned| pinned.get()) else {
+            return self.rotate();
+        };
+
+        let index = match pinned {
+            Some(Some(index)) if self.replicas[index].healthy.load(Ordering::Relaxed) => {
+                Some(index)
+            }
+            Some(_) => None,
+            None => self.rotate().map(|(index, _)| index),
+        };
+
+        let _ = PINNED.try_with(|pinned| pinned.set(Some(index)));
+        index.map(|index| (index, &self.replicas[index].reader))
+    }
+
+    /// The next healthy replica in the rotation, along with its index, or `None` if there are no
+    /// healthy replicas.
+    fn rotate(&self) -> Option<(usize, &R)> {
+        let len = self.replicas.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        (0..len)
+            .map(|offset| (start + offset) % len)
+            .find(|&index| self.replicas[index].healthy.load(Ordering::Relaxed))
+            .map(|index| (index, &self.replicas[index].reader))
+    }
+
+    /// All replicas' readers, whether or not they are healthy, in index order.
+    pub(crate) fn readers(&self) -> impl Iterator<Item = &R> {
+        self.replicas.iter().map(|replica| &replica.reader)
+    }
+
+    /// Inspect an error from a query sent to the replica at `index` (if any), and take that
+    /// replica out of rotation if the error suggests it is unreachable.
+    pub(crate) fn report_error(&self, index: Option<usize>, error: &IndexerError) {
+        let Some(index) = index else {
+            return;
+        };
+
+        if is_unreachable(error) && self.replicas[index].healthy.swap(false, Ordering::Relaxed) {
+            warn!(
+                replica = index,
+                "Read replica unreachable, removing from rotation"
+            );
+        }
+    }
+
+    /// Record the outcome of a health check on the replica at `index`, returning it to (or taking
+    /// it out of) rotation.
+    fn record_health(&self, index: usize, healthy: bool) {
+        let was_healthy = self.replicas[index]
+            .healthy
+            .swap(healthy, Ordering::Relaxed);
+        if healthy && !was_healthy {
+            info!(
+                replica = index,
+                "Read replica healthy, returning to rotation"
+            );
+        } else if !healthy && was_healthy {
+            warn!(
+                replica = index,
+                "Read replica failed health check, removing from rotation"
+            );
+        }
+    }
+}
+
+/// Run `request` with all its reads pinned to the same database (see `ReadReplicas::next`).
+pub(crate) async fn pin_replica<F: Future>(request: F) -> F::Output {
+    PINNED.scope(Cell::new(None), request).await
+}
+
+/// Whether `error` means that the database could not be reached, rather than that the query
+/// failed. Only these failures are retried on the primary, because anything else (e.g. a query
+/// that times out) is likely to fail the same way there.
+pub(crate) fn is_unreachable(error: &IndexerError) -> bool {
+    matches!(error, IndexerError::PgPoolConnectionError(_))
+}
+
+impl ReadReplicas<IndexerReader> {
+    /// Health-check every replica in the background, for the lifetime of the process. Does
+    /// nothing if there are no replicas.
+    pub(crate) fn start_health_checks(&self) {
+        if self.replicas.is_empty() {
+            return;
+        }
+
+        let replicas = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(HEALTH_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                replicas.check_health().await;
+            }
+        });
+    }
+
+    async fn check_health(&self) {
+        for (index, replica) in self.replicas.iter().enumerate() {
+            let healthy = replica
+                .reader
+                .run_query_async(|conn| diesel::sql_query("SELECT 1").execute(conn))
+                .await
+                .is_ok();
+
+            self.record_health(index, healthy);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rotation(replicas: &ReadReplicas<&'static str>, n: usize) -> Vec<&'static str> {
+        (0..n)
+            .map(|_| *replicas.next().map(|(_, r)| r).unwrap_or(&"primary"))
+            .collect()
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let replicas = ReadReplicas::new(vec!["a", "b", "c"]);
+        assert_eq!(rotation(&replicas, 6), vec!["a", "b", "c", "a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_no_replicas() {
+        let replicas = ReadReplicas::<&'static str>::new(vec![]);
+        assert_eq!(rotation(&replicas, 2), vec!["primary", "primary"]);
+    }
+
+    #[test]
+    fn test_unreachable_replica_leaves_rotation() {
+        let replicas = ReadReplicas::new(vec!["a", "b"]);
+
+        // Only connection errors suggest that the replica is unreachable.
+        let read_error = IndexerError::PostgresReadError("relation does not exist".to_string());
+        replicas.report_error(Some(1), &read_error);
+        replicas.report_error(None, &read_error);
+        assert_eq!(rotation(&replicas, 4), vec!["a", "b", "a", "b"]);
+
+        let conn_error = IndexerError::PgPoolConnectionError("timed out".to_string());
+        replicas.report_error(Some(1), &conn_error);
+        assert_eq!(rotation(&replicas, 4), vec!["a", "a", "a", "a"]);
+
+        replicas.report_error(Some(0), &conn_error);
+        assert_eq!(rotation(&replicas, 2), vec!["primary", "primary"]);
+    }
+
+    #[tokio::test]
+    async fn test_requests_pin_replica() {
+        let replicas = ReadReplicas::new(vec!["a", "b", "c"]);
+
+        let first = pin_replica(async { rotation(&replicas, 3) }).await;
+        let second = pin_replica(async { rotation(&replicas, 3) }).await;
+        assert_eq!(first, vec!["a", "a", "a"]);
+        assert_eq!(second, vec!["b", "b", "b"]);
+
+        // A request whose replica becomes unreachable moves to the primary, and stays there, even
+        // once the replica is healthy again.
+        let pinned = pin_replica(async {
+            let mut seen = rotation(&replicas, 2);
+            let conn_error = IndexerError::PgPoolConnectionError("timed out".to_string());
+            replicas.report_error(Some(2), &conn_error);
+            seen.extend(rotation(&replicas, 1));
+            replicas.record_health(2, true);
+            seen.extend(rotation(&replicas, 1));
+            seen
+        })
+        .await;
+        assert_eq!(pinned, vec!["c", "c", "primary", "primary"]);
+
+        // Reads outside of a request are not pinned.
+        assert_eq!(rotation(&replicas, 3), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_health_checks_rotate_replicas() {
+        let replicas = ReadReplicas::new(vec!["a", "b", "c"]);
+
+        replicas.record_health(0, false);
+        replicas.record_health(2, false);
+        assert_eq!(rotation(&replicas, 3), vec!["b", "b", "b"]);
+
+        // Clones share health state.
+        replicas.clone().record_health(2, true);
+        let mut seen = rotation(&replicas, 3);
+        seen.sort();
+        assert_eq!(seen, vec!["b", "b", "c"]);
+
+        replicas.record_health(1, false);
+        replicas.record_health(2, false);
+        assert_eq!(rotation(&replicas, 2), vec!["primary", "primary"]);
+
+        replicas.record_health(0, true);
+        assert_eq!(rotation(&replicas, 2), vec!["a", "a"]);
+    }
+}
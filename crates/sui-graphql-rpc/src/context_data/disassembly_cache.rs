@@ -0,0 +1,103 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use lru::LruCache;
+
+use crate::{error::Error, types::sui_address::SuiAddress};
+
+/// Maximum number of module disassemblies that are kept in the cache.
+const CACHE_CAPACITY: usize = 1_000;
+
+/// Identifies a module by the storage ID and version of its package, and its name. Together, the
+/// ID and version identify the package's contents exactly (system packages are upgraded in place,
+/// keeping their ID, but not their version), so a cached disassembly can never go stale.
+type ModuleKey = (SuiAddress, u64, String);
+
+/// Caches the textual disassembly of modules, which is expensive to produce for large modules,
+/// and tends to be requested repeatedly for the same popular packages.
+pub(crate) struct DisassemblyCache {
+    disassemblies: Mutex<LruCache<ModuleKey, Arc<str>>>,
+}
+
+impl DisassemblyCache {
+    pub(crate) fn new() -> Self {
+        let capacity = NonZeroUsize::new(CACHE_CAPACITY).expect("Cache capacity must be non-zero");
+        Self {
+            disassemblies: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// The disassembly of module `name` in version `version` of the package at `storage_id`,
+    /// produced by `disassemble` if it has not been cached already. Failures are not cached.
+    pub(crate) fn get_or_disassemble(
+        &self,
+        storage_id: SuiAddress,
+        version: u64,
+        name: &str,
+        disassemble: impl FnOnce() -> Result<String, Error>,
+    ) -> Result<Arc<str>, Error> {
+        let key = (storage_id, version, name.to_string());
+        if let Some(cached) = self.disassemblies.lock().unwrap().get(&key) {
+            return Ok(cached.clone());
+        }
+
+        // The lock is not held while disassembling, so that a large module does not hold up
+        // requests for other modules. Concurrent misses for the same module may both disassemble
+        // it, producing the same result.
+        let disassembly: Arc<str> = disassemble()?.into();
+        self.disassemblies
+            .lock()
+            .unwrap()
+            .put(key, disassembly.clone());
+
+        Ok(disassembly)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn disassembly_is_cached_per_package_version() {
+        let cache = DisassemblyCache::new();
+        let package = SuiAddress::from_bytes([2u8; 32]).unwrap();
+        let calls = Cell::new(0);
+        let disassemble = |text: &str| {
+            calls.set(calls.get() + 1);
+            Ok(text.to_string())
+        };
+
+        let v1 = cache.get_or_disassemble(package, 1, "m", || disassemble("v1"));
+        assert_eq!(&*v1.unwrap(), "v1");
+
+        let cached = cache.get_or_disassemble(package, 1, "m", || disassemble("unused"));
+        assert_eq!(&*cached.unwrap(), "v1");
+        assert_eq!(calls.get(), 1);
+
+        // A new version of the same package is disassembled afresh.
+        let v2 = cache.get_or_disassemble(package, 2, "m", || disassemble("v2"));
+        assert_eq!(&*v2.unwrap(), "v2");
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn failures_are_not_cached() {
+        let cache = DisassemblyCache::new();
+        let package = SuiAddress::from_bytes([2u8; 32]).unwrap();
+
+        let failed =
+            cache.get_or_disassemble(package, 1, "m", || Err(Error::Internal("boom".to_string())));
+        assert!(failed.is_err());
+
+        let retried = cache.get_or_disassemble(package, 1, "m", || Ok("ok".to_string()));
+        assert_eq!(&*retried.unwrap(), "ok");
+    }
+}
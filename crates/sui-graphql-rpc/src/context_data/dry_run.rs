@@ -0,0 +1,391 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Dry runs transactions by executing them locally, against the latest state of the chain as
+//! recorded in the indexer's database, without committing their effects anywhere.
+
+use std::{collections::HashSet, fmt::Display, sync::Arc};
+
+use async_trait::async_trait;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use sui_execution::GasEstimate;
+use sui_indexer::{
+    errors::IndexerError,
+    indexer_reader::IndexerReader,
+    models_v2::{
+        epoch::StoredEpochInfo,
+        objects::{StoredHistoryObject, StoredObject},
+    },
+    schema_v2::{objects, objects_history},
+    types_v2::ObjectStatus,
+};
+use sui_json_rpc::balance_changes::ObjectProvider;
+use sui_protocol_config::ProtocolConfig;
+use sui_types::{
+    base_types::{EpochId, ObjectID, ObjectRef, SequenceNumber},
+    crypto::default_hash,
+    digests::TransactionDigest,
+    effects::TransactionEffects,
+    error::{SuiError, SuiResult, UserInputError},
+    gas_coin::MIST_PER_SUI,
+    inner_temporary_store::InnerTemporaryStore,
+    metrics::{BytecodeVerifierMetrics, LimitsMetrics},
+    object::{MoveObject, Object, Owner, OBJECT_START_VERSION},
+    storage::{BackingPackageStore, ChildObjectResolver, ObjectStore, PackageObject, ParentSync},
+    transaction::{
+        InputObjectKind, ObjectReadResult, ReceivingObjectReadResult, TransactionData,
+        TransactionDataAPI, VersionedProtocolMessage,
+    },
+};
+
+use super::db_data_provider::PgManager;
+use crate::error::Error;
+
+/// The balance of the coin that pays for gas when the transaction being dry run does not provide
+/// any gas coins of its own (1B SUI).
+const MOCK_GAS_BALANCE: u64 = 1_000_000_000 * MIST_PER_SUI;
+
+/// Reads the objects and packages that a transaction being dry run depends on from the indexer's
+/// database. Its reads block, so it must only be used from a blocking task (see
+/// `IndexerReader::spawn_blocking`).
+pub(crate) struct DbExecutionStore(pub IndexerReader);
+
+/// Objects read while calculating the balance changes of a dry run: Objects the transaction wrote
+/// or read as inputs are found in its temporary store, and everything else is read from the
+/// database.
+pub(crate) struct DryRunObjects<'a> {
+    pub pg: &'a PgManager,
+    pub inner_temporary_store: &'a InnerTemporaryStore,
+}
+
+/// Execute `tx_data` (without committing its effects) under `protocol_config`, in the epoch
+/// described by `epoch`, reading its inputs from `store`. If the transaction does not pay for gas
+/// with coins of its own, `mock_gas` is the ID of the coin that pays on its behalf.
+///
+/// Problems with the transaction itself (failed validity checks, missing or malformed inputs) are
+/// reported as client errors, while failures to read from the database are internal errors.
+/// Failures during execution are not errors, they are reported in the transaction's effects.
+pub(crate) fn dry_run(
+    store: &DbExecutionStore,
+    protocol_config: &ProtocolConfig,
+    epoch: &StoredEpochInfo,
+    tx_data: TransactionData,
+    mock_gas: Option<ObjectID>,
+) -> Result<(TransactionEffects, InnerTemporaryStore), Error> {
+    if tx_data.kind().is_system_tx() {
+        return Err(Error::Client(
+            "Dry runs do not support system transactions".to_string(),
+        ));
+    }
+
+    tx_data
+        .check_version_supported(protocol_config)
+        .map_err(invalid_tx)?;
+    tx_data
+        .validity_check_no_gas_check(protocol_config)
+        .map_err(invalid_tx)?;
+
+    let input_object_kinds = tx_data.input_objects().map_err(invalid_tx)?;
+    let mut input_objects = Vec::with_capacity(input_object_kinds.len());
+    for kind in input_object_kinds {
+        let object = match kind {
+            InputObjectKind::MovePackage(id) => store.fetch_package(&id)?,
+            InputObjectKind::SharedMoveObject { id, .. } => store.fetch_object(&id, None)?,
+            // Owned and immutable inputs are read at their live versions (like shared inputs),
+            // so that references to stale versions fail the version check.
+            InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => store.fetch_object(&id, None)?,
+        }
+        .ok_or_else(|| invalid_tx(kind.object_not_found_error()))?;
+
+        input_objects.push(ObjectReadResult::new(kind, object.into()));
+    }
+
+    let mut receiving_objects = vec![];
+    for object_ref in tx_data.receiving_objects() {
+        // The digest (and version) of the receiving object are checked along with the rest of
+        // the transaction's inputs.
+        let (id, version, _) = object_ref;
+        let object = store.fetch_object(&id, None)?.ok_or_else(|| {
+            invalid_tx(UserInputError::ObjectNotFound {
+                object_id: id,
+                version: Some(version),
+            })
+        })?;
+
+        receiving_objects.push(ReceivingObjectReadResult::new(object_ref, object.into()));
+    }
+
+    // Metrics gathered during dry runs are not reported anywhere.
+    let registry = prometheus::Registry::new();
+    let verifier_metrics = Arc::new(BytecodeVerifierMetrics::new(&registry));
+    let limits_metrics = Arc::new(LimitsMetrics::new(&registry));
+
+    let reference_gas_price = epoch.reference_gas_price as u64;
+    let (gas_coins, (gas_status, checked_input_objects)) = if let Some(mock_gas) = mock_gas {
+        let gas_object = Object::new_move(
+            MoveObject::new_gas_coin(OBJECT_START_VERSION, mock_gas, MOCK_GAS_BALANCE),
+            Owner::AddressOwner(tx_data.sender()),
+            TransactionDigest::genesis_marker(),
+        );
+
+        (
+            vec![gas_object.compute_object_reference()],
+            sui_transaction_checks::check_transaction_input_with_given_gas(
+                protocol_config,
+                reference_gas_price,
+                &tx_data,
+                input_objects.into(),
+                receiving_objects.into(),
+                gas_object,
+                &verifier_metrics,
+            )
+            .map_err(invalid_tx)?,
+        )
+    } else {
+        (
+            tx_data.gas().to_vec(),
+            sui_transaction_checks::check_transaction_input(
+                protocol_config,
+                reference_gas_price,
+                &tx_data,
+                input_objects.into(),
+                receiving_objects.into(),
+                &verifier_metrics,
+            )
+            .map_err(invalid_tx)?,
+        )
+    };
+
+    let executor = sui_execution::executor(protocol_config, /* silent */ true)
+        .map_err(|e| Error::Internal(format!("Unable to create executor: {e}")))?;
+
+    let transaction_digest = TransactionDigest::new(default_hash(&tx_data));
+    let (transaction_kind, transaction_signer, _) = tx_data.execution_parts();
+    let GasEstimate {
+        effects,
+        inner_temporary_store,
+    } = executor.estimate_gas_for_transaction(
+        store,
+        protocol_config,
+        limits_metrics,
+        &HashSet::new(),
+        &(epoch.epoch as EpochId),
+        epoch.epoch_start_timestamp as u64,
+        checked_input_objects,
+        gas_coins,
+        gas_status,
+        transaction_kind,
+        transaction_signer,
+        transaction_digest,
+    );
+
+    Ok((effects, inner_temporary_store))
+}
+
+/// Problems with a transaction that prevent it from being dry run are the client's fault.
+pub(crate) fn invalid_tx(e: impl Display) -> Error {
+    Error::Client(format!("Invalid transaction: {e}"))
+}
+
+impl DbExecutionStore {
+    /// The object with ID `id` at `version`, or at its latest version if `version` is not
+    /// provided. Objects that are wrapped or deleted (at that version) are not found.
+    pub(crate) fn fetch_object(
+        &self,
+        id: &ObjectID,
+        version: Option<SequenceNumber>,
+    ) -> Result<Option<Object>, IndexerError> {
+        let id = id.to_vec();
+        let Some(version) = version else {
+            let stored = self.0.run_query(|conn| {
+                objects::dsl::objects
+                    .filter(objects::dsl::object_id.eq(id))
+                    .first::<StoredObject>(conn)
+                    .optional()
+            })?;
+
+            return stored.map(Object::try_from).transpose();
+        };
+
+        let stored = self.0.run_query(|conn| {
+            objects_history::dsl::objects_history
+                .filter(objects_history::dsl::object_id.eq(id))
+                .filter(objects_history::dsl::object_version.eq(version.value() as i64))
+                .first::<StoredHistoryObject>(conn)
+                .optional()
+        })?;
+
+        live_object(stored)
+    }
+
+    /// The latest version of the object with ID `id`, at or before `version`, if the object was
+    /// live at that version.
+    pub(crate) fn fetch_object_at_or_before(
+        &self,
+        id: &ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<Object>, IndexerError> {
+        let id = id.to_vec();
+        let stored = self.0.run_query(|conn| {
+            objects_history::dsl::objects_history
+                .filter(objects_history::dsl::object_id.eq(id))
+                .filter(objects_history::dsl::object_version.le(version.value() as i64))
+                .order(objects_history::dsl::object_version.desc())
+                .first::<StoredHistoryObject>(conn)
+                .optional()
+        })?;
+
+        live_object(stored)
+    }
+
+    /// The latest version of the package with ID `id`, as an object.
+    pub(crate) fn fetch_package(&self, id: &ObjectID) -> Result<Option<Object>, IndexerError> {
+        Ok(self.fetch_object(id, None)?.filter(|o| o.is_package()))
+    }
+}
+
+/// Convert a version of an object from its history into an object, if it was live at that
+/// version.
+fn live_object(stored: Option<StoredHistoryObject>) -> Result<Option<Object>, IndexerError> {
+    let Some(stored) = stored else {
+        return Ok(None);
+    };
+
+    if stored.object_status != ObjectStatus::Active as i16 {
+        return Ok(None);
+    }
+
+    Ok(Some(Object::try_from(StoredObject::try_from(stored)?)?))
+}
+
+fn storage_error(e: IndexerError) -> SuiError {
+    SuiError::GenericStorageError(e.to_string())
+}
+
+impl BackingPackageStore for DbExecutionStore {
+    fn get_package_object(&self, package_id: &ObjectID) -> SuiResult<Option<PackageObject>> {
+        Ok(self
+            .fetch_package(package_id)
+            .map_err(storage_error)?
+            .map(PackageObject::new))
+    }
+}
+
+impl ObjectStore for DbExecutionStore {
+    fn get_object(&self, object_id: &ObjectID) -> SuiResult<Option<Object>> {
+        self.fetch_object(object_id, None).map_err(storage_error)
+    }
+
+    fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        self.fetch_object(object_id, Some(version))
+            .map_err(storage_error)
+    }
+}
+
+impl ChildObjectResolver for DbExecutionStore {
+    fn read_child_object(
+        &self,
+        parent: &ObjectID,
+        child: &ObjectID,
+        child_version_upper_bound: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        let Some(child_object) = self
+            .fetch_object_at_or_before(child, child_version_upper_bound)
+            .map_err(storage_error)?
+        else {
+            return Ok(None);
+        };
+
+        let parent = *parent;
+        if child_object.owner != Owner::ObjectOwner(parent.into()) {
+            return Err(SuiError::InvalidChildObjectAccess {
+                object: *child,
+                given_parent: parent,
+                actual_owner: child_object.owner,
+            });
+        }
+
+        Ok(Some(child_object))
+    }
+
+    fn get_object_received_at_version(
+        &self,
+        owner: &ObjectID,
+        receiving_object_id: &ObjectID,
+        receive_object_at_version: SequenceNumber,
+        _epoch_id: EpochId,
+    ) -> SuiResult<Option<Object>> {
+        let Some(recv_object) = self
+            .fetch_object(receiving_object_id, Some(receive_object_at_version))
+            .map_err(storage_error)?
+        else {
+            return Ok(None);
+        };
+
+        // An object that is not owned by `owner` is treated as though it does not exist. The
+        // indexer does not record whether the object has already been received at this version,
+        // but in that case, the version is no longer the object's latest, so it fails input
+        // checks before it can be received.
+        if recv_object.owner != Owner::AddressOwner((*owner).into()) {
+            return Ok(None);
+        }
+
+        Ok(Some(recv_object))
+    }
+}
+
+impl ParentSync for DbExecutionStore {
+    fn get_latest_parent_entry_ref_deprecated(
+        &self,
+        object_id: ObjectID,
+    ) -> SuiResult<Option<ObjectRef>> {
+        // The indexer does not keep tombstones, so only references to live objects are found.
+        Ok(self
+            .fetch_object(&object_id, None)
+            .map_err(storage_error)?
+            .map(|o| o.compute_object_reference()))
+    }
+}
+
+#[async_trait]
+impl ObjectProvider for DryRunObjects<'_> {
+    type Error = Error;
+
+    async fn get_object(&self, id: &ObjectID, version: &SequenceNumber) -> Result<Object, Error> {
+        let store = self.inner_temporary_store;
+        for object in [store.written.get(id), store.input_objects.get(id)]
+            .into_iter()
+            .flatten()
+        {
+            if object.version() == *version {
+                return Ok(object.clone());
+            }
+        }
+
+        let (id, version) = (*id, *version);
+        self.pg
+            .inner
+            .spawn_blocking(move |this| DbExecutionStore(this).fetch_object(&id, Some(version)))
+            .await?
+            .ok_or_else(|| Error::Internal(format!("Object {id} not found at version {version}")))
+    }
+
+    async fn find_object_lt_or_eq_version(
+        &self,
+        id: &ObjectID,
+        version: &SequenceNumber,
+    ) -> Result<Option<Object>, Error> {
+        let (id, version) = (*id, *version);
+        Ok(self
+            .pg
+            .inner
+            .spawn_blocking(move |this| {
+                DbExecutionStore(this).fetch_object_at_or_before(&id, version)
+            })
+            .await?)
+    }
+}
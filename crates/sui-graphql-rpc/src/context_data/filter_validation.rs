@@ -0,0 +1,441 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks that filters are satisfiable and bounded in cost, run before any query is built from
+//! them, so that problems with a filter are reported to the user precisely, rather than surfacing
+//! as failures (or silently empty results) from the database.
+
+use std::str::FromStr;
+
+use crate::{
+    config::Limits,
+    error::Error,
+    types::{
+        digest::Digest,
+        event::EventFilter,
+        object::{ObjectFilter, ObjectKind},
+        sui_address::SuiAddress,
+        transaction_block::TransactionBlockFilter,
+        type_filter::{ModuleFilter, TypeFilter, TypeMatch},
+    },
+};
+
+use super::db_data_provider::DbValidationError;
+
+pub(crate) fn validate_tx_block_filter(
+    filter: &TransactionBlockFilter,
+    limits: &Limits,
+) -> Result<(), Error> {
+    if filter.at_checkpoint.is_some()
+        && (filter.before_checkpoint.is_some() || filter.after_checkpoint.is_some())
+    {
+        return Err(DbValidationError::InvalidCheckpointCombination.into());
+    }
+
+    if filter.before_checkpoint == Some(0) {
+        return Err(DbValidationError::BeforeGenesisCheckpoint.into());
+    }
+
+    if let (Some(before), Some(after)) = (filter.before_checkpoint, filter.after_checkpoint) {
+        if before <= after {
+            return Err(DbValidationError::InvalidCheckpointOrder.into());
+        }
+    }
+
+    if let (Some(before), Some(after)) = (&filter.before_time, &filter.after_time) {
+        if before.timestamp_ms() <= after.timestamp_ms() {
+            return Err(DbValidationError::InvalidTimeOrder.into());
+        }
+    }
+
+    validate_package_dependencies(
+        filter.package.as_ref(),
+        filter.module.as_ref(),
+        filter.function.as_ref(),
+    )?;
+
+    if filter.paid_address.is_some() {
+        return Err(DbValidationError::UnsupportedPaidAddress.into());
+    }
+
+    if let Some(transaction_ids) = &filter.transaction_ids {
+        let max = limits.max_multi_get_size;
+        if transaction_ids.len() as u64 > max {
+            return Err(
+                DbValidationError::MultiGetSizeExceeded(transaction_ids.len() as u64, max).into(),
+            );
+        }
+
+        for id in transaction_ids {
+            Digest::from_str(id)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn validate_event_filter(filter: &EventFilter) -> Result<(), Error> {
+    if let Some(digest) = &filter.transaction_digest {
+        Digest::from_str(digest)?;
+    }
+
+    if let Some(module) = &filter.emitting_module {
+        ModuleFilter::parse(module)?;
+    }
+
+    let Some(event_types) = &filter.event_type else {
+        if filter.event_type_match.is_some() {
+            return Err(DbValidationError::TypeMatchWithoutType.into());
+        }
+
+        return Ok(());
+    };
+
+    let type_match = filter.event_type_match.unwrap_or_default();
+    for (event_type, type_filter) in event_types
+        .iter()
+        .zip(validate_type_filters(event_types, type_match)?)
+    {
+        if let TypeFilter::Primitive(_) = type_filter {
+            return Err(DbValidationError::InvalidType(format!(
+                "Events cannot have primitive type '{event_type}'"
+            ))
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn validate_obj_filter(filter: &ObjectFilter) -> Result<(), Error> {
+    if filter.object_keys.is_some() {
+        return Err(DbValidationError::UnsupportedObjectKeys.into());
+    }
+
+    if filter.owner.is_some()
+        && matches!(
+            filter.kind,
+            Some(ObjectKind::Shared | ObjectKind::Immutable)
+        )
+    {
+        return Err(DbValidationError::OwnerFilterOnUnownedKind.into());
+    }
+
+    // Without an owner, the only indexed way to find shared or immutable objects is by their type
+    // or ID, otherwise every object would need to be scanned to find them.
+    if matches!(
+        filter.kind,
+        Some(ObjectKind::Shared | ObjectKind::Immutable)
+    ) && filter.type_.is_none()
+        && filter.object_ids.is_none()
+    {
+        return Err(DbValidationError::UnboundedUnownedKind.into());
+    }
+
+    if filter.include_nested == Some(true) && filter.owner.is_none() {
+        return Err(DbValidationError::NestedWithoutOwner.into());
+    }
+
+    match &filter.type_ {
+        Some(types) => {
+            validate_type_filters(types, filter.type_match.unwrap_or_default())?;
+        }
+        None if filter.type_match.is_some() => {
+            return Err(DbValidationError::TypeMatchWithoutType.into());
+        }
+        None => {}
+    }
+
+    Ok(())
+}
+
+fn validate_package_dependencies(
+    package: Option<&SuiAddress>,
+    module: Option<&String>,
+    function: Option<&String>,
+) -> Result<(), Error> {
+    if function.is_some() {
+        if package.is_none() || module.is_none() {
+            return Err(DbValidationError::RequiresPackageAndModule.into());
+        }
+    } else if module.is_some() && package.is_none() {
+        return Err(DbValidationError::RequiresPackage.into());
+    }
+
+    Ok(())
+}
+
+/// Parse `types`, and make sure each of them can be matched under `type_match`.
+fn validate_type_filters(
+    types: &[String],
+    type_match: TypeMatch,
+) -> Result<Vec<TypeFilter>, DbValidationError> {
+    let type_filters = TypeFilter::parse_any(types)?;
+    for type_filter in &type_filters {
+        type_filter.pattern(type_match)?;
+    }
+
+    Ok(type_filters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::date_time::DateTime;
+
+    fn tx_error(filter: TransactionBlockFilter) -> Option<String> {
+        validate_tx_block_filter(&filter, &Limits::default())
+            .err()
+            .map(|e| e.to_string())
+    }
+
+    fn event_error(filter: EventFilter) -> Option<String> {
+        validate_event_filter(&filter).err().map(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_tx_block_filter_checkpoints() {
+        assert_eq!(
+            tx_error(TransactionBlockFilter {
+                at_checkpoint: Some(5),
+                after_checkpoint: Some(4),
+                ..Default::default()
+            }),
+            Some(DbValidationError::InvalidCheckpointCombination.to_string()),
+        );
+
+        assert_eq!(
+            tx_error(TransactionBlockFilter {
+                before_checkpoint: Some(0),
+                ..Default::default()
+            }),
+            Some(DbValidationError::BeforeGenesisCheckpoint.to_string()),
+        );
+
+        assert_eq!(
+            tx_error(TransactionBlockFilter {
+                after_checkpoint: Some(5),
+                before_checkpoint: Some(5),
+                ..Default::default()
+            }),
+            Some(DbValidationError::InvalidCheckpointOrder.to_string()),
+        );
+
+        assert_eq!(
+            tx_error(TransactionBlockFilter {
+                after_time: DateTime::from_ms(2_000),
+                before_time: DateTime::from_ms(1_000),
+                ..Default::default()
+            }),
+            Some(DbValidationError::InvalidTimeOrder.to_string()),
+        );
+
+        assert_eq!(
+            tx_error(TransactionBlockFilter {
+                after_checkpoint: Some(4),
+                before_checkpoint: Some(6),
+                ..Default::default()
+            }),
+            None,
+        );
+    }
+
+    #[test]
+    fn test_tx_block_filter_functions() {
+        let package = Some(SuiAddress::from_str("0x2").unwrap());
+        let module = Some("coin".to_string());
+        let function = Some("split".to_string());
+
+        assert_eq!(
+            tx_error(TransactionBlockFilter {
+                package,
+                function: function.clone(),
+                ..Default::default()
+            }),
+            Some(DbValidationError::RequiresPackageAndModule.to_string()),
+        );
+
+        assert_eq!(
+            tx_error(TransactionBlockFilter {
+                module: module.clone(),
+                ..Default::default()
+            }),
+            Some(DbValidationError::RequiresPackage.to_string()),
+        );
+
+        assert_eq!(
+            tx_error(TransactionBlockFilter {
+                package,
+                module,
+                function,
+                ..Default::default()
+            }),
+            None,
+        );
+    }
+
+    #[test]
+    fn test_tx_block_filter_transaction_ids() {
+        let digest = Digest::from_array([1; 32]).to_string();
+        let max = Limits::default().max_multi_get_size as usize;
+
+        assert_eq!(
+            tx_error(TransactionBlockFilter {
+                transaction_ids: Some(vec![digest.clone(); max]),
+                ..Default::default()
+            }),
+            None,
+        );
+
+        assert_eq!(
+            tx_error(TransactionBlockFilter {
+                transaction_ids: Some(vec![digest; max + 1]),
+                ..Default::default()
+            }),
+            Some(DbValidationError::MultiGetSizeExceeded(max as u64 + 1, max as u64).to_string()),
+        );
+
+        assert!(tx_error(TransactionBlockFilter {
+            transaction_ids: Some(vec!["not-a-digest".to_string()]),
+            ..Default::default()
+        })
+        .is_some());
+
+        assert_eq!(
+            tx_error(TransactionBlockFilter {
+                transaction_ids: Some(vec!["11111".to_string()]),
+                ..Default::default()
+            }),
+            Some(
+                Error::InvalidDigestLength {
+                    expected: 32,
+                    actual: 5,
+                }
+                .to_string()
+            ),
+        );
+    }
+
+    #[test]
+    fn test_tx_block_filter_paid_address() {
+        assert_eq!(
+            tx_error(TransactionBlockFilter {
+                paid_address: Some(SuiAddress::from_str("0x1").unwrap()),
+                ..Default::default()
+            }),
+            Some(DbValidationError::UnsupportedPaidAddress.to_string()),
+        );
+    }
+
+    #[test]
+    fn test_event_filter() {
+        assert_eq!(
+            event_error(EventFilter {
+                event_type: Some(vec!["0x2::coin::Coin".to_string()]),
+                ..Default::default()
+            }),
+            None,
+        );
+
+        assert!(event_error(EventFilter {
+            emitting_module: Some("0x2::coin::Coin".to_string()),
+            ..Default::default()
+        })
+        .is_some());
+
+        assert!(event_error(EventFilter {
+            event_type: Some(vec!["u64".to_string()]),
+            ..Default::default()
+        })
+        .is_some());
+
+        assert!(event_error(EventFilter {
+            event_type: Some(vec!["0x2::coin".to_string()]),
+            event_type_match: Some(TypeMatch::Exact),
+            ..Default::default()
+        })
+        .is_some());
+
+        assert_eq!(
+            event_error(EventFilter {
+                event_type_match: Some(TypeMatch::Exact),
+                ..Default::default()
+            }),
+            Some(DbValidationError::TypeMatchWithoutType.to_string()),
+        );
+
+        assert_eq!(
+            event_error(EventFilter {
+                event_type: Some(vec![]),
+                ..Default::default()
+            }),
+            Some(DbValidationError::NoTypes.to_string()),
+        );
+    }
+
+    #[test]
+    fn test_obj_filter() {
+        let owner = Some(SuiAddress::from_str("0x1").unwrap());
+
+        assert!(validate_obj_filter(&ObjectFilter {
+            owner,
+            kind: Some(ObjectKind::Shared),
+            ..Default::default()
+        })
+        .is_err());
+
+        assert_eq!(
+            validate_obj_filter(&ObjectFilter {
+                kind: Some(ObjectKind::Immutable),
+                ..Default::default()
+            })
+            .err()
+            .map(|e| e.to_string()),
+            Some(DbValidationError::UnboundedUnownedKind.to_string()),
+        );
+
+        assert!(validate_obj_filter(&ObjectFilter {
+            kind: Some(ObjectKind::Shared),
+            type_: Some(vec!["0x3::sui_system::SuiSystemState".to_string()]),
+            ..Default::default()
+        })
+        .is_ok());
+
+        assert!(validate_obj_filter(&ObjectFilter {
+            kind: Some(ObjectKind::Immutable),
+            object_ids: Some(vec![SuiAddress::from_str("0x2").unwrap()]),
+            ..Default::default()
+        })
+        .is_ok());
+
+        assert!(validate_obj_filter(&ObjectFilter {
+            include_nested: Some(true),
+            ..Default::default()
+        })
+        .is_err());
+
+        assert!(validate_obj_filter(&ObjectFilter {
+            type_match: Some(TypeMatch::Exact),
+            ..Default::default()
+        })
+        .is_err());
+
+        assert_eq!(
+            validate_obj_filter(&ObjectFilter {
+                type_: Some(vec![]),
+                ..Default::default()
+            })
+            .err()
+            .map(|e| e.to_string()),
+            Some(DbValidationError::NoTypes.to_string()),
+        );
+
+        assert!(validate_obj_filter(&ObjectFilter {
+            owner,
+            type_: Some(vec!["0x2::coin::Coin<0x2::sui::SUI>".to_string()]),
+            type_match: Some(TypeMatch::Exact),
+            ..Default::default()
+        })
+        .is_ok());
+    }
+}
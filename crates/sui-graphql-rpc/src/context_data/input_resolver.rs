@@ -0,0 +1,359 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use lru::LruCache;
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+};
+use sui_package_resolver::{OpenSignature, OpenSignatureBody, PackageStore, Resolver};
+use sui_types::{
+    is_system_package,
+    transaction::{Argument, CallArg, Command, ProgrammableMoveCall, ProgrammableTransaction},
+};
+
+use super::package_cache::PackageCache;
+use crate::error::Error;
+
+/// Maximum number of functions whose parameter signatures are kept in the cache.
+const CACHE_CAPACITY: usize = 10_000;
+
+/// Identifies a function by the storage ID of its package, its module name and its name.
+type FunctionKey = (AccountAddress, String, String);
+
+/// Works out the Move types of a programmable transaction block's pure inputs, so that their BCS
+/// bytes can be decoded, by looking at how each input is used: as an argument to a Move function
+/// (whose signature is read from its package), as a `SplitCoins` amount, as the recipient of a
+/// `TransferObjects`, or as an element of a typed `MakeMoveVec`.
+///
+/// Function signatures are cached, as a transaction's inputs are typically inspected alongside
+/// many other transactions calling the same functions.
+pub(crate) struct InputResolver {
+    signatures: Mutex<LruCache<FunctionKey, Option<Arc<[OpenSignature]>>>>,
+}
+
+impl InputResolver {
+    pub(crate) fn new() -> Self {
+        let capacity = NonZeroUsize::new(CACHE_CAPACITY).expect("Cache capacity must be non-zero");
+        Self {
+            signatures: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// The type of each of `ptb`'s inputs, if it is a pure input whose type could be inferred from
+    /// its usage, or `None` otherwise. If an input is used at more than one type, its first use
+    /// determines its type.
+    ///
+    /// Failing to read the signature of a function that a pure input is passed to does not fail
+    /// the whole transaction: That input's type is replaced by the error's message, if no other use
+    /// determines its type.
+    pub(crate) async fn pure_input_types(
+        &self,
+        resolver: &Resolver<PackageCache>,
+        ptb: &ProgrammableTransaction,
+    ) -> Vec<Result<Option<TypeTag>, String>> {
+        let mut parameters = vec![];
+        for command in &ptb.commands {
+            if let Command::MoveCall(call) = command {
+                parameters.push(
+                    self.parameters(resolver, call)
+                        .await
+                        .map_err(|e| e.to_string()),
+                );
+            }
+        }
+
+        infer_pure_input_types(ptb, parameters)
+    }
+
+    /// Parameter types of the function `call` targets, with its type parameters instantiated by
+    /// the call's type arguments. `None` if the function could not be found.
+    async fn parameters(
+        &self,
+        resolver: &Resolver<PackageCache>,
+        call: &ProgrammableMoveCall,
+    ) -> Result<Option<Vec<Option<TypeTag>>>, Error> {
+        let key = (
+            AccountAddress::from(call.package),
+            call.module.to_string(),
+            call.function.to_string(),
+        );
+
+        let cached = self.signatures.lock().unwrap().get(&key).cloned();
+        let signature = match cached {
+            Some(signature) => signature,
+            None => {
+                let signature = fetch_signature(resolver, &key).await?;
+
+                // System packages can be upgraded in place, changing the signatures of their
+                // non-public functions, so only functions from other packages are cached.
+                if !is_system_package(key.0) {
+                    self.signatures.lock().unwrap().put(key, signature.clone());
+                }
+
+                signature
+            }
+        };
+
+        Ok(signature.map(|parameters| {
+            parameters
+                .iter()
+                .map(|param| instantiate(&param.body, &call.type_arguments))
+                .collect()
+        }))
+    }
+}
+
+/// Read the parameter signatures of a function out of its package.
+async fn fetch_signature(
+    resolver: &Resolver<PackageCache>,
+    (package, module, function): &FunctionKey,
+) -> Result<Option<Arc<[OpenSignature]>>, Error> {
+    let contents = resolver
+        .package_store()
+        .fetch(*package)
+        .await
+        .map_err(|e| Error::Internal(format!("Failed to fetch package {package}: {e}")))?;
+
+    let Ok(compiled) = contents.module(module) else {
+        return Ok(None);
+    };
+
+    let def = compiled.function_def(function).map_err(|e| {
+        Error::Internal(format!(
+            "Failed to read signature of {package}::{module}::{function}: {e}"
+        ))
+    })?;
+
+    Ok(def.map(|def| def.parameters.into()))
+}
+
+/// Assign types to pure inputs based on the commands that use them. `parameters` holds the
+/// (instantiated) parameter types of each `MoveCall` command, in the order they appear in `ptb`,
+/// or the error encountered fetching them, which is reported against any pure input passed to that
+/// call, that is not typed by another command.
+fn infer_pure_input_types(
+    ptb: &ProgrammableTransaction,
+    parameters: Vec<Result<Option<Vec<Option<TypeTag>>>, String>>,
+) -> Vec<Result<Option<TypeTag>, String>> {
+    let mut types = vec![None; ptb.inputs.len()];
+    let mut errors = vec![None; ptb.inputs.len()];
+    let mut assign = |arg: &Argument, tag: Option<&TypeTag>| {
+        let (Argument::Input(ix), Some(tag)) = (arg, tag) else {
+            return;
+        };
+
+        if let Some(slot @ None) = types.get_mut(*ix as usize) {
+            *slot = Some(tag.clone());
+        }
+    };
+
+    let mut parameters = parameters.into_iter();
+    for command in &ptb.commands {
+        match command {
+            Command::MoveCall(call) => match parameters.next() {
+                Some(Ok(Some(params))) => {
+                    for (arg, tag) in call.arguments.iter().zip(&params) {
+                        assign(arg, tag.as_ref());
+                    }
+                }
+
+                Some(Err(error)) => {
+                    for arg in &call.arguments {
+                        if let Argument::Input(ix) = arg {
+                            if let Some(slot @ None) = errors.get_mut(*ix as usize) {
+                                *slot = Some(error.clone());
+                            }
+                        }
+                    }
+                }
+
+                Some(Ok(None)) | None => {}
+            },
+
+            Command::SplitCoins(_, amounts) => {
+                for amount in amounts {
+                    assign(amount, Some(&TypeTag::U64));
+                }
+            }
+
+            Command::TransferObjects(_, address) => assign(address, Some(&TypeTag::Address)),
+
+            Command::MakeMoveVec(Some(tag), elements) => {
+                for element in elements {
+                    assign(element, Some(tag));
+                }
+            }
+
+            Command::MakeMoveVec(None, _)
+            | Command::MergeCoins(_, _)
+            | Command::Publish(_, _)
+            | Command::Upgrade(_, _, _, _) => {}
+        }
+    }
+
+    // Only pure inputs are decoded, object inputs are left untyped.
+    types
+        .into_iter()
+        .zip(errors)
+        .zip(&ptb.inputs)
+        .map(|((ty, error), input)| match (input, ty, error) {
+            (CallArg::Pure(_), Some(ty), _) => Ok(Some(ty)),
+            (CallArg::Pure(_), None, Some(error)) => Err(error),
+            _ => Ok(None),
+        })
+        .collect()
+}
+
+/// Substitute `type_args` for the type parameters in `sig`, to get a concrete type. Returns `None`
+/// if `sig` refers to a type parameter that was not supplied.
+fn instantiate(sig: &OpenSignatureBody, type_args: &[TypeTag]) -> Option<TypeTag> {
+    use OpenSignatureBody as O;
+    use TypeTag as T;
+
+    Some(match sig {
+        O::Address => T::Address,
+        O::Bool => T::Bool,
+        O::U8 => T::U8,
+        O::U16 => T::U16,
+        O::U32 => T::U32,
+        O::U64 => T::U64,
+        O::U128 => T::U128,
+        O::U256 => T::U256,
+        O::Vector(sig) => T::Vector(Box::new(instantiate(sig, type_args)?)),
+        O::TypeParameter(ix) => type_args.get(*ix as usize)?.clone(),
+        O::Struct(key, params) => T::Struct(Box::new(StructTag {
+            address: key.package,
+            module: Identifier::new(key.module.as_ref()).ok()?,
+            name: Identifier::new(key.name.as_ref()).ok()?,
+            type_params: params
+                .iter()
+                .map(|param| instantiate(param, type_args))
+                .collect::<Option<_>>()?,
+        })),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_types::{
+        base_types::ObjectID, programmable_transaction_builder::ProgrammableTransactionBuilder,
+    };
+
+    use super::*;
+
+    #[test]
+    fn infer_from_native_commands() {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let amount = builder.pure(42u64).unwrap();
+        let recipient = builder.pure(AccountAddress::ONE).unwrap();
+        let element = builder.pure(true).unwrap();
+        builder.pure(7u8).unwrap();
+
+        builder.command(Command::SplitCoins(Argument::GasCoin, vec![amount]));
+        builder.command(Command::MakeMoveVec(Some(TypeTag::Bool), vec![element]));
+        builder.command(Command::TransferObjects(
+            vec![Argument::Result(0)],
+            recipient,
+        ));
+
+        let ptb = builder.finish();
+        assert_eq!(
+            infer_pure_input_types(&ptb, vec![]),
+            vec![
+                Ok(Some(TypeTag::U64)),
+                Ok(Some(TypeTag::Address)),
+                Ok(Some(TypeTag::Bool)),
+                Ok(None),
+            ],
+        );
+    }
+
+    #[test]
+    fn infer_from_move_call() {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let first = builder.pure(1u64).unwrap();
+        let second = builder.pure(vec![1u8, 2, 3]).unwrap();
+
+        builder.command(Command::move_call(
+            ObjectID::ZERO,
+            Identifier::new("m").unwrap(),
+            Identifier::new("f").unwrap(),
+            vec![],
+            vec![first, second],
+        ));
+
+        // The first use of an input determines its type.
+        builder.command(Command::SplitCoins(Argument::GasCoin, vec![first]));
+
+        let ptb = builder.finish();
+        let params = vec![Ok(Some(vec![
+            Some(TypeTag::U32),
+            Some(TypeTag::Vector(Box::new(TypeTag::U8))),
+        ]))];
+
+        assert_eq!(
+            infer_pure_input_types(&ptb, params),
+            vec![
+                Ok(Some(TypeTag::U32)),
+                Ok(Some(TypeTag::Vector(Box::new(TypeTag::U8)))),
+            ],
+        );
+    }
+
+    #[test]
+    fn infer_from_failed_move_call() {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let unresolved = builder.pure(1u64).unwrap();
+        let amount = builder.pure(2u64).unwrap();
+        let unused = builder.pure(3u64).unwrap();
+
+        builder.command(Command::move_call(
+            ObjectID::ZERO,
+            Identifier::new("m").unwrap(),
+            Identifier::new("f").unwrap(),
+            vec![],
+            vec![unresolved, amount],
+        ));
+
+        // Another use can still determine the type of an input passed to a failed call.
+        builder.command(Command::SplitCoins(Argument::GasCoin, vec![amount]));
+
+        // A failed call does not affect the types of inputs passed to other calls.
+        builder.command(Command::move_call(
+            ObjectID::ZERO,
+            Identifier::new("m").unwrap(),
+            Identifier::new("g").unwrap(),
+            vec![],
+            vec![unused],
+        ));
+
+        let ptb = builder.finish();
+        let error = "Failed to fetch package".to_string();
+        let params = vec![Err(error.clone()), Ok(None)];
+
+        assert_eq!(
+            infer_pure_input_types(&ptb, params),
+            vec![Err(error), Ok(Some(TypeTag::U64)), Ok(None)],
+        );
+    }
+
+    #[test]
+    fn instantiate_type_parameters() {
+        use OpenSignatureBody as O;
+
+        let sig = O::Vector(Box::new(O::TypeParameter(1)));
+        assert_eq!(
+            instantiate(&sig, &[TypeTag::U8, TypeTag::Bool]),
+            Some(TypeTag::Vector(Box::new(TypeTag::Bool))),
+        );
+
+        assert_eq!(instantiate(&O::TypeParameter(2), &[TypeTag::U8]), None);
+    }
+}
@@ -3,6 +3,13 @@
 
 pub(crate) mod db_backend;
 pub(crate) mod db_data_provider;
+pub(crate) mod db_query_stats;
+pub(crate) mod db_replicas;
+pub(crate) mod disassembly_cache;
+pub(crate) mod dry_run;
+pub(crate) mod filter_validation;
+pub(crate) mod input_resolver;
+pub(crate) mod name_service;
 pub(crate) mod package_cache;
 #[cfg(feature = "pg_backend")]
 pub(crate) mod pg_backend;
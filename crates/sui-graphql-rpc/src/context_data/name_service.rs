@@ -0,0 +1,228 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::BTreeMap,
+    hash::Hash,
+    num::NonZeroUsize,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use lru::LruCache;
+use sui_json_rpc::name_service::{Domain, NameRecord, NameServiceConfig};
+use sui_protocol_config::Chain;
+use sui_types::{base_types::ObjectID, dynamic_field::Field, object::Object as NativeObject};
+use tokio::sync::OnceCell;
+
+use super::db_data_provider::PgManager;
+use crate::{
+    error::Error,
+    types::{address::Address, sui_address::SuiAddress},
+};
+
+/// Maximum number of names (and separately, addresses) whose records are kept in the cache.
+const CACHE_CAPACITY: usize = 10_000;
+
+/// How long a cached record is served before it is read again from the registry. Records change
+/// rarely, but when they do, the change should be visible reasonably quickly.
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Resolves SuiNS names to addresses, and addresses to their default names, by reading records
+/// out of dynamic fields on the name service's registries.
+///
+/// The registries to read from are chosen once per network: if the RPC is serving a network with
+/// an entry in `network_configs`, that entry is used, otherwise `default_config` is used.
+pub(crate) struct NameServiceResolver {
+    default_config: NameServiceConfig,
+    network_configs: BTreeMap<Chain, NameServiceConfig>,
+    config: OnceCell<NameServiceConfig>,
+    addresses: TtlCache<Domain, Option<SuiAddress>>,
+    names: TtlCache<SuiAddress, Option<String>>,
+}
+
+struct TtlCache<K: Hash + Eq, V> {
+    ttl: Duration,
+    entries: Mutex<LruCache<K, (Instant, V)>>,
+}
+
+impl NameServiceResolver {
+    pub(crate) fn new(
+        default_config: NameServiceConfig,
+        network_configs: BTreeMap<Chain, NameServiceConfig>,
+    ) -> Self {
+        Self {
+            default_config,
+            network_configs,
+            config: OnceCell::new(),
+            addresses: TtlCache::new(CACHE_CAPACITY, CACHE_TTL),
+            names: TtlCache::new(CACHE_CAPACITY, CACHE_TTL),
+        }
+    }
+
+    /// Resolves the address that `name` points to, if it has been registered and has a target
+    /// address set. Names are cached by the domain they parse to, so that different spellings of
+    /// the same name share a cache entry.
+    pub(crate) async fn resolve_address(
+        &self,
+        pg: &PgManager,
+        name: String,
+    ) -> Result<Option<Address>, Error> {
+        let domain = parse_domain(&name)?;
+        if let Some(address) = self.addresses.get(&domain) {
+            return Ok(address.map(|address| Address { address }));
+        }
+
+        let record_id = self.config(pg).await?.record_field_id(&domain);
+        let address = match fetch_field(pg, record_id).await? {
+            Some(object) => object
+                .to_rust::<Field<Domain, NameRecord>>()
+                .ok_or_else(|| Error::Internal(format!("Malformed Object {record_id}")))?
+                .value
+                .target_address
+                .map(|address| SuiAddress::from_array(address.to_inner())),
+            None => None,
+        };
+
+        self.addresses.insert(domain, address);
+        Ok(address.map(|address| Address { address }))
+    }
+
+    /// The domain that `address` has explicitly configured as its default, if any.
+    pub(crate) async fn default_name(
+        &self,
+        pg: &PgManager,
+        address: SuiAddress,
+    ) -> Result<Option<String>, Error> {
+        if let Some(name) = self.names.get(&address) {
+            return Ok(name);
+        }
+
+        let reverse_record_id = self
+            .config(pg)
+            .await?
+            .reverse_record_field_id(address.into());
+
+        let name = match fetch_field(pg, reverse_record_id).await? {
+            Some(object) => Some(
+                object
+                    .to_rust::<Field<SuiAddress, Domain>>()
+                    .ok_or_else(|| {
+                        Error::Internal(format!("Malformed Object {reverse_record_id}"))
+                    })?
+                    .value
+                    .to_string(),
+            ),
+            None => None,
+        };
+
+        self.names.insert(address, name.clone());
+        Ok(name)
+    }
+
+    /// The registry configuration for the network this RPC is serving. Only needs to consult the
+    /// database (to identify the network) the first time it is called, and only if there are
+    /// network-specific configurations to choose between.
+    async fn config(&self, pg: &PgManager) -> Result<&NameServiceConfig, Error> {
+        if self.network_configs.is_empty() {
+            return Ok(&self.default_config);
+        }
+
+        self.config
+            .get_or_try_init(|| async {
+                let chain = pg.fetch_chain().await?;
+                Ok::<_, Error>(
+                    self.network_configs
+                        .get(&chain)
+                        .unwrap_or(&self.default_config)
+                        .clone(),
+                )
+            })
+            .await
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> TtlCache<K, V> {
+    fn new(capacity: usize, ttl: Duration) -> Self {
+        let capacity = NonZeroUsize::new(capacity).expect("Cache capacity must be non-zero");
+        Self {
+            ttl,
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        let mut entries = self.entries.lock().unwrap();
+        let (inserted, value) = entries.get(key)?;
+        if inserted.elapsed() < self.ttl {
+            return Some(value.clone());
+        }
+
+        entries.pop(key);
+        None
+    }
+
+    fn insert(&self, key: K, value: V) {
+        self.entries
+            .lock()
+            .unwrap()
+            .put(key, (Instant::now(), value));
+    }
+}
+
+/// Read the latest version of a registry record (a dynamic field object), through the query
+/// builder.
+async fn fetch_field(pg: &PgManager, field_id: ObjectID) -> Result<Option<NativeObject>, Error> {
+    Ok(pg
+        .fetch_obj(SuiAddress::from(field_id), None)
+        .await?
+        .map(|object| object.native))
+}
+
+/// Parse `name` as a SuiNS domain. Domains are case-insensitive, so `name` is lowercased first.
+fn parse_domain(name: &str) -> Result<Domain, Error> {
+    Ok(name.to_lowercase().parse::<Domain>()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_keyed_on_domain() {
+        let cache = TtlCache::new(10, CACHE_TTL);
+        let address = SuiAddress::from_array([1; 32]);
+        cache.insert(parse_domain("example.sui").unwrap(), Some(address));
+
+        // Spellings of the same name hit the same entry.
+        let domain = parse_domain("Example.SUI").unwrap();
+        assert_eq!(domain.to_string(), "example.sui");
+        assert_eq!(cache.get(&domain), Some(Some(address)));
+
+        // Subdomains are distinct names.
+        let subdomain = parse_domain("sub.example.sui").unwrap();
+        assert_eq!(cache.get(&subdomain), None);
+    }
+
+    #[test]
+    fn test_cache_expiry() {
+        let cache = TtlCache::new(10, Duration::ZERO);
+        let domain = parse_domain("example.sui").unwrap();
+        cache.insert(domain.clone(), None::<SuiAddress>);
+        assert_eq!(cache.get(&domain), None);
+    }
+
+    #[test]
+    fn test_cache_eviction() {
+        let cache = TtlCache::new(1, CACHE_TTL);
+        let (a, b) = (
+            parse_domain("a.sui").unwrap(),
+            parse_domain("b.sui").unwrap(),
+        );
+
+        cache.insert(a.clone(), None::<SuiAddress>);
+        cache.insert(b.clone(), None);
+        assert_eq!(cache.get(&a), None);
+        assert_eq!(cache.get(&b), Some(None));
+    }
+}
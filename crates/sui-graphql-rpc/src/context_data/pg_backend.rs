@@ -2,38 +2,110 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{
-    db_backend::{BalanceQuery, Explain, Explained, GenericQueryBuilder},
-    db_data_provider::{DbValidationError, TypeFilterError},
+    db_backend::{
+        ActivityKind, AddressActivityQuery, BalanceHistoryQuery, BalanceQuery,
+        EpochAggregatesQuery, Explain, Explained, GenericQueryBuilder, HistoricalBalanceQuery,
+        MS_PER_DAY,
+    },
+    db_data_provider::DbValidationError,
+    db_query_stats::query_tag,
+    db_replicas::is_unreachable,
 };
 use crate::{
     context_data::db_data_provider::PgManager,
-    error::Error,
+    error::{code, Error},
     types::{
-        digest::Digest, event::EventFilter, object::ObjectFilter, sui_address::SuiAddress,
+        digest::Digest,
+        event::EventFilter,
+        object::ObjectFilter,
         transaction_block::TransactionBlockFilter,
+        type_filter::{ModuleFilter, TypeFilter, TypeMatch, TypePattern},
     },
 };
 use async_trait::async_trait;
 use diesel::{
+    expression::SqlLiteral,
     pg::Pg,
     query_builder::{AstPass, QueryFragment},
-    BoolExpressionMethods, ExpressionMethods, PgConnection, QueryDsl, QueryResult, RunQueryDsl,
-    TextExpressionMethods,
+    sql_types::{BigInt, Bool, Bytea, Nullable, SmallInt, Text},
+    BoolExpressionMethods, BoxableExpression, ExpressionMethods, PgConnection, QueryDsl,
+    QueryResult, RunQueryDsl, TextExpressionMethods,
 };
-use std::str::FromStr;
+use std::{ops::RangeInclusive, str::FromStr};
 use sui_indexer::{
+    errors::IndexerError,
+    indexer_reader::IndexerReader,
     schema_v2::{
-        checkpoints, epochs, events, objects, transactions, tx_calls, tx_changed_objects,
-        tx_input_objects, tx_recipients, tx_senders,
+        checkpoints, epochs, events, objects, objects_history, packages, transactions, tx_calls,
+        tx_changed_objects, tx_input_objects, tx_recipients, tx_senders,
     },
-    types_v2::OwnerType,
+    types_v2::{ObjectStatus, OwnerType},
 };
-use sui_types::parse_sui_struct_tag;
+use sui_types::{sui_system_state::SUI_SYSTEM_MODULE_NAME, SUI_SYSTEM_PACKAGE_ID};
 use tap::TapFallible;
-use tracing::{info, warn};
+use tracing::{field, info, info_span, warn, Instrument, Span};
 
 pub(crate) const EXPLAIN_COSTING_LOG_TARGET: &str = "gql-explain-costing";
 
+/// Maximum number of objects that the expansion of nested object ownership visits.
+const MAX_NESTED_OBJECTS: u32 = 1_000;
+
+/// Span covering a single query sent to the database, tagged by the function that issued it (of
+/// type `Q`), and the pool it was sent to. The estimated cost of the query is recorded on the span
+/// once it is known, for queries that are costed, and so is the number of rows it returned, for
+/// queries that report it (see [`record_rows`]).
+fn db_query_span<Q>(replica: Option<usize>) -> Span {
+    info_span!(
+        "db_query",
+        tag = query_tag::<Q>(),
+        pool = %PgManager::pool_name(replica),
+        cost = field::Empty,
+        rows = field::Empty,
+    )
+}
+
+/// Record the number of rows a query loaded on the `db_query` span it ran under. Meant to be
+/// mapped over the result of loading rows, from within the closure that runs the query.
+pub(crate) fn record_rows<T>(rows: Option<Vec<T>>) -> Option<Vec<T>> {
+    Span::current().record("rows", rows.as_ref().map_or(0, Vec::len));
+    rows
+}
+
+/// Filter `$query` to rows whose `$column` holds the canonical representation of a type matching
+/// any of `$patterns` (`TypePattern`s). The column must be a text column in `$table`, and
+/// predicates on it have SQL type `$sql_type` (`Bool`, or `Nullable<Bool>` if the column is
+/// nullable). An empty list of patterns leaves the query unfiltered.
+macro_rules! filter_on_types {
+    ($query:expr, $table:ty, $column:expr, $sql_type:ty, $patterns:expr) => {{
+        type Predicate = Box<dyn BoxableExpression<$table, Pg, SqlType = $sql_type>>;
+
+        let mut predicate: Option<Predicate> = None;
+        for pattern in $patterns {
+            let matches: Predicate = match pattern {
+                TypePattern::Exact(repr) => Box::new($column.eq(repr)),
+                TypePattern::Prefix(prefix) => {
+                    Box::new($column.like(format!("{}%", escape_like(&prefix))))
+                }
+                TypePattern::Generic(repr) => Box::new(
+                    $column
+                        .like(format!("{}<%", escape_like(&repr)))
+                        .or($column.eq(repr)),
+                ),
+            };
+
+            predicate = Some(match predicate {
+                None => matches,
+                Some(predicate) => Box::new(predicate.or(matches)),
+            });
+        }
+
+        match predicate {
+            Some(predicate) => $query.filter(predicate),
+            None => $query,
+        }
+    }};
+}
+
 pub(crate) struct PgQueryBuilder;
 
 impl GenericQueryBuilder<Pg> for PgQueryBuilder {
@@ -68,6 +140,25 @@ impl GenericQueryBuilder<Pg> for PgQueryBuilder {
             .limit(1)
             .into_boxed()
     }
+    fn get_epoch_aggregates(epoch: i64) -> EpochAggregatesQuery<'static, Pg> {
+        checkpoints::dsl::checkpoints
+            .select((
+                diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::BigInt>>(
+                    "MIN(sequence_number)",
+                ),
+                diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::BigInt>>(
+                    "MAX(sequence_number)",
+                ),
+                diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::BigInt>>(
+                    "MIN(network_total_transactions - cardinality(tx_digests))",
+                ),
+                diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::BigInt>>(
+                    "MAX(network_total_transactions)",
+                ),
+            ))
+            .filter(checkpoints::dsl::epoch.eq(epoch))
+            .into_boxed()
+    }
     fn get_checkpoint_by_digest(digest: Vec<u8>) -> checkpoints::BoxedQuery<'static, Pg> {
         checkpoints::dsl::checkpoints
             .filter(checkpoints::dsl::checkpoint_digest.eq(digest))
@@ -207,12 +298,6 @@ impl GenericQueryBuilder<Pg> for PgQueryBuilder {
 
                 query = query.filter(transactions::dsl::tx_sequence_number.eq_any(subquery));
             }
-            if filter.paid_address.is_some() {
-                return Err(Error::Internal(
-                    "Paid address filter not supported".to_string(),
-                ));
-            }
-
             if let Some(input_object) = filter.input_object {
                 let subquery = tx_input_objects::dsl::tx_input_objects
                     .filter(tx_input_objects::dsl::object_id.eq(input_object.into_vec()))
@@ -231,6 +316,45 @@ impl GenericQueryBuilder<Pg> for PgQueryBuilder {
 
         Ok(query)
     }
+    fn multi_get_txs_by_sequence_numbers(
+        tx_sequence_numbers: Vec<i64>,
+    ) -> transactions::BoxedQuery<'static, Pg> {
+        transactions::dsl::transactions
+            .filter(transactions::dsl::tx_sequence_number.eq_any(tx_sequence_numbers))
+            .into_boxed()
+    }
+    fn multi_get_txs_by_digests(digests: Vec<Vec<u8>>) -> transactions::BoxedQuery<'static, Pg> {
+        transactions::dsl::transactions
+            .filter(transactions::dsl::transaction_digest.eq_any(digests))
+            .into_boxed()
+    }
+    fn multi_get_address_activity(
+        address: Vec<u8>,
+        before: Option<(i64, i16)>,
+        after: Option<(i64, i16)>,
+        descending_order: bool,
+        limit: i64,
+    ) -> AddressActivityQuery {
+        AddressActivityQuery {
+            address,
+            before,
+            after,
+            descending_order,
+            limit,
+        }
+    }
+    fn multi_get_balance_history(
+        address: Vec<u8>,
+        coin_type: String,
+        days: RangeInclusive<i64>,
+    ) -> BalanceHistoryQuery {
+        BalanceHistoryQuery {
+            address,
+            coin_type,
+            start_ms: days.start() * MS_PER_DAY,
+            end_ms: (days.end() + 1) * MS_PER_DAY,
+        }
+    }
     fn multi_get_coins(
         before: Option<Vec<u8>>,
         after: Option<Vec<u8>>,
@@ -251,12 +375,54 @@ impl GenericQueryBuilder<Pg> for PgQueryBuilder {
 
         query
     }
+    fn multi_get_coins_by_balance(
+        before: Option<(i64, Vec<u8>)>,
+        after: Option<(i64, Vec<u8>)>,
+        limit: i64,
+        address: Option<Vec<u8>>,
+        coin_type: String,
+    ) -> objects::BoxedQuery<'static, Pg> {
+        use objects::dsl::{coin_balance, object_id};
+
+        let mut query = objects::dsl::objects.into_boxed();
+        if let Some((balance, id)) = after {
+            query = query
+                .filter(
+                    coin_balance
+                        .lt(balance)
+                        .or(coin_balance.eq(balance).and(object_id.gt(id))),
+                )
+                .order((coin_balance.desc(), object_id.asc()));
+        } else if let Some((balance, id)) = before {
+            query = query
+                .filter(
+                    coin_balance
+                        .gt(balance)
+                        .or(coin_balance.eq(balance).and(object_id.lt(id))),
+                )
+                .order((coin_balance.asc(), object_id.desc()));
+        } else {
+            query = query.order((coin_balance.desc(), object_id.asc()));
+        }
+        query = query.limit(limit + 1);
+
+        if let Some(address) = address {
+            query = query
+                .filter(objects::dsl::owner_id.eq(address))
+                // Leverage index on objects table
+                .filter(objects::dsl::owner_type.eq(OwnerType::Address as i16));
+        }
+        query = query.filter(objects::dsl::coin_type.eq(coin_type));
+
+        query
+    }
     fn multi_get_objs(
         before: Option<Vec<u8>>,
         after: Option<Vec<u8>>,
         limit: i64,
         filter: Option<ObjectFilter>,
         owner_type: Option<OwnerType>,
+        nested_depth: Option<u32>,
     ) -> Result<objects::BoxedQuery<'static, Pg>, Error> {
         let mut query = order_objs(before, after);
         query = query.limit(limit + 1);
@@ -276,84 +442,149 @@ impl GenericQueryBuilder<Pg> for PgQueryBuilder {
             );
         }
 
+        let owner_type = resolve_owner_type(owner_type, filter.kind.map(OwnerType::from))?;
         if let Some(owner) = filter.owner {
-            query = query.filter(objects::dsl::owner_id.eq(owner.into_vec()));
-
-            match owner_type {
-                Some(OwnerType::Address) => {
-                    query = query.filter(objects::dsl::owner_type.eq(OwnerType::Address as i16));
-                }
-                Some(OwnerType::Object) => {
-                    query = query.filter(objects::dsl::owner_type.eq(OwnerType::Object as i16));
-                }
-                None => {
-                    query = query.filter(
-                        objects::dsl::owner_type
-                            .eq(OwnerType::Address as i16)
-                            .or(objects::dsl::owner_type.eq(OwnerType::Object as i16)),
-                    );
-                }
-                _ => Err(DbValidationError::InvalidOwnerType)?,
+            let owner_types = owned_types(owner_type)?;
+            if let Some(depth) = nested_depth {
+                query = query.filter(owned_transitively(owner.into_vec(), &owner_types, depth));
+            } else {
+                query = query
+                    .filter(objects::dsl::owner_id.eq(owner.into_vec()))
+                    .filter(objects::dsl::owner_type.eq_any(owner_types));
             }
+        } else if let Some(owner_type) = owner_type {
+            // Shared and immutable objects have no owner, so they are selected by owner type alone,
+            // and paginated by object ID like any other objects.
+            query = query.filter(objects::dsl::owner_type.eq(owner_type as i16));
         }
 
-        if let Some(object_type) = filter.type_ {
-            let format = "package[::module[::type[<type_params>]]]";
-            let parts: Vec<_> = object_type.splitn(3, "::").collect();
+        if let Some(object_types) = filter.type_ {
+            let patterns = type_patterns(&object_types, filter.type_match)?;
 
-            if parts.iter().any(|&part| part.is_empty()) {
-                return Err(DbValidationError::InvalidType(
-                    TypeFilterError::MissingComponents(object_type, format).to_string(),
-                ))?;
-            }
+            query = filter_on_types!(
+                query,
+                objects::table,
+                objects::dsl::object_type,
+                Nullable<Bool>,
+                patterns
+            );
+        }
 
-            if parts.len() == 1 {
-                // We check for a leading 0x to determine if it is an address
-                // And otherwise process it as a primitive type
-                if parts[0].starts_with("0x") {
-                    let package = SuiAddress::from_str(parts[0])
-                        .map_err(|e| DbValidationError::InvalidType(e.to_string()))?;
-                    query = query.filter(objects::dsl::object_type.like(format!("{}::%", package)));
-                } else {
-                    query = query.filter(objects::dsl::object_type.eq(parts[0].to_string()));
-                }
-            } else if parts.len() == 2 {
-                // Only package addresses are allowed if there are two or more parts
-                let package = SuiAddress::from_str(parts[0])
-                    .map_err(|e| DbValidationError::InvalidType(e.to_string()))?;
-                query = query.filter(
-                    objects::dsl::object_type.like(format!("{}::{}::%", package, parts[1])),
-                );
-            } else if parts.len() == 3 {
-                let validated_type = parse_sui_struct_tag(&object_type)
-                    .map_err(|e| DbValidationError::InvalidType(e.to_string()))?;
-
-                if validated_type.type_params.is_empty() {
-                    query = query.filter(
-                        objects::dsl::object_type
-                            .like(format!(
-                                "{}<%",
-                                validated_type.to_canonical_string(/* with_prefix */ true)
-                            ))
-                            .or(objects::dsl::object_type
-                                .eq(validated_type.to_canonical_string(/* with_prefix */ true))),
-                    );
-                } else {
-                    query = query.filter(
-                        objects::dsl::object_type
-                            .eq(validated_type.to_canonical_string(/* with_prefix */ true)),
-                    );
-                }
-            } else {
-                return Err(DbValidationError::InvalidType(
-                    TypeFilterError::TooManyComponents(object_type, 3, format).to_string(),
-                )
-                .into());
-            }
+        Ok(query)
+    }
+    fn multi_get_objs_at_checkpoint(
+        before: Option<Vec<u8>>,
+        after: Option<Vec<u8>>,
+        limit: i64,
+        filter: Option<ObjectFilter>,
+        owner_type: Option<OwnerType>,
+        checkpoint: i64,
+    ) -> Result<objects_history::BoxedQuery<'static, Pg>, Error> {
+        let mut query = objects_history::dsl::objects_history
+            .filter(objects_history::dsl::object_status.eq(ObjectStatus::Active as i16))
+            .filter(objects_history::dsl::checkpoint_sequence_number.le(checkpoint))
+            .filter(latest_as_of_checkpoint(checkpoint))
+            .limit(limit + 1)
+            .into_boxed();
+
+        if let Some(after) = after {
+            query = query
+                .filter(objects_history::dsl::object_id.gt(after))
+                .order(objects_history::dsl::object_id.asc());
+        } else if let Some(before) = before {
+            query = query
+                .filter(objects_history::dsl::object_id.lt(before))
+                .order(objects_history::dsl::object_id.desc());
+        } else {
+            query = query.order(objects_history::dsl::object_id.asc());
+        }
+
+        let Some(filter) = filter else {
+            return Ok(query);
+        };
+
+        if let Some(object_ids) = filter.object_ids {
+            query = query.filter(
+                objects_history::dsl::object_id.eq_any(
+                    object_ids
+                        .into_iter()
+                        .map(|id| id.into_vec())
+                        .collect::<Vec<_>>(),
+                ),
+            );
+        }
+
+        let owner_type = resolve_owner_type(owner_type, filter.kind.map(OwnerType::from))?;
+        if let Some(owner) = filter.owner {
+            query = query
+                .filter(objects_history::dsl::owner_id.eq(owner.into_vec()))
+                .filter(objects_history::dsl::owner_type.eq_any(owned_types(owner_type)?));
+        } else if let Some(owner_type) = owner_type {
+            query = query.filter(objects_history::dsl::owner_type.eq(owner_type as i16));
+        }
+
+        if let Some(object_types) = filter.type_ {
+            let patterns = type_patterns(&object_types, filter.type_match)?;
+
+            query = filter_on_types!(
+                query,
+                objects_history::table,
+                objects_history::dsl::object_type,
+                Nullable<Bool>,
+                patterns
+            );
         }
 
         Ok(query)
     }
+    fn multi_get_objs_by_ids(object_ids: Vec<Vec<u8>>) -> objects::BoxedQuery<'static, Pg> {
+        objects::dsl::objects
+            .filter(objects::dsl::object_id.eq_any(object_ids))
+            .into_boxed()
+    }
+    fn multi_get_history_objs(
+        refs: Vec<(Vec<u8>, i64, Vec<u8>)>,
+    ) -> objects_history::BoxedQuery<'static, Pg> {
+        let mut query = objects_history::dsl::objects_history.into_boxed();
+        for (object_id, version, digest) in refs {
+            query = query.or_filter(
+                objects_history::dsl::object_id
+                    .eq(object_id)
+                    .and(objects_history::dsl::object_version.eq(version))
+                    .and(objects_history::dsl::object_digest.eq(digest)),
+            );
+        }
+
+        query
+    }
+    fn multi_get_object_versions(
+        object_id: Vec<u8>,
+        before: Option<i64>,
+        after: Option<i64>,
+        descending_order: bool,
+        limit: i64,
+    ) -> objects_history::BoxedQuery<'static, Pg> {
+        let mut query = objects_history::dsl::objects_history
+            .filter(objects_history::dsl::object_id.eq(object_id))
+            .filter(objects_history::dsl::object_status.eq(ObjectStatus::Active as i16))
+            .into_boxed();
+
+        if let Some(after) = after {
+            query = query.filter(objects_history::dsl::object_version.gt(after));
+        }
+
+        if let Some(before) = before {
+            query = query.filter(objects_history::dsl::object_version.lt(before));
+        }
+
+        query = if descending_order {
+            query.order(objects_history::dsl::object_version.desc())
+        } else {
+            query.order(objects_history::dsl::object_version.asc())
+        };
+
+        query.limit(limit + 1)
+    }
     fn multi_get_balances(address: Vec<u8>) -> BalanceQuery<'static, Pg> {
         let query = objects::dsl::objects
             .group_by(objects::dsl::coin_type)
@@ -377,6 +608,31 @@ impl GenericQueryBuilder<Pg> for PgQueryBuilder {
         let query = PgQueryBuilder::multi_get_balances(address);
         query.filter(objects::dsl::coin_type.eq(coin_type))
     }
+    fn multi_get_historical_balances(
+        address: Vec<u8>,
+        checkpoint: i64,
+    ) -> HistoricalBalanceQuery<'static, Pg> {
+        // A version of a coin only counts towards the balance if it is the coin's latest version
+        // as of the checkpoint.
+        objects_history::dsl::objects_history
+            .group_by(objects_history::dsl::coin_type)
+            .select((
+                diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::BigInt>>(
+                    "CAST(SUM(coin_balance) AS BIGINT)",
+                ),
+                diesel::dsl::sql::<diesel::sql_types::Nullable<diesel::sql_types::BigInt>>(
+                    "COUNT(*)",
+                ),
+                objects_history::dsl::coin_type,
+            ))
+            .filter(objects_history::dsl::owner_id.eq(address))
+            .filter(objects_history::dsl::owner_type.eq(OwnerType::Address as i16))
+            .filter(objects_history::dsl::coin_type.is_not_null())
+            .filter(objects_history::dsl::object_status.eq(ObjectStatus::Active as i16))
+            .filter(objects_history::dsl::checkpoint_sequence_number.le(checkpoint))
+            .filter(latest_as_of_checkpoint(checkpoint))
+            .into_boxed()
+    }
     fn multi_get_checkpoints(
         before: Option<i64>,
         after: Option<i64>,
@@ -464,92 +720,38 @@ impl GenericQueryBuilder<Pg> for PgQueryBuilder {
 
         // Filters on the package and/ or module that emitted some event
         if let Some(pm) = filter.emitting_module {
-            let format = "package[::module]";
-            let parts: Vec<_> = pm.splitn(2, "::").collect();
-
-            if parts.iter().any(|&part| part.is_empty()) {
-                return Err(DbValidationError::InvalidType(
-                    TypeFilterError::MissingComponents(pm, format).to_string(),
-                ))?;
-            }
-
-            let p = SuiAddress::from_str(parts[0])
-                .map_err(|e| DbValidationError::InvalidType(e.to_string()))?;
-
-            match parts.len() {
-                1 => {
-                    query = query.filter(events::dsl::package.eq(p.into_vec()));
+            match ModuleFilter::parse(&pm)? {
+                ModuleFilter::Package(package) => {
+                    query = query.filter(events::dsl::package.eq(package.into_vec()));
                 }
-                2 => {
-                    query = query.filter(events::dsl::package.eq(p.into_vec()));
-                    query = query.filter(events::dsl::module.eq(parts[1].to_string()));
-                }
-                _ => {
-                    return Err(DbValidationError::InvalidType(
-                        TypeFilterError::TooManyComponents(pm, 2, format).to_string(),
-                    )
-                    .into());
+                ModuleFilter::Module(package, module) => {
+                    query = query.filter(events::dsl::package.eq(package.into_vec()));
+                    query = query.filter(events::dsl::module.eq(module));
                 }
             }
         }
 
-        // Filters on the event type
-        if let Some(event_type) = filter.event_type {
-            let parts: Vec<_> = event_type.splitn(3, "::").collect();
-
-            if parts.iter().any(|&part| part.is_empty()) {
-                return Err(DbValidationError::InvalidType(
-                    TypeFilterError::MissingComponents(
-                        event_type,
-                        "package[::module[::type[<type_params>]]]",
-                    )
-                    .to_string(),
-                ))?;
-            }
+        // Filters on every version of the package that emitted some event, by looking up all the
+        // packages that share its original ID.
+        if let Some(original_id) = filter.emitting_package_lineage {
+            let subquery = packages::dsl::packages
+                .filter(packages::dsl::original_id.eq(original_id.into_vec()))
+                .select(packages::dsl::package_id);
 
-            let p = SuiAddress::from_str(parts[0])
-                .map_err(|e| DbValidationError::InvalidType(e.to_string()))?;
+            query = query.filter(events::dsl::package.eq_any(subquery));
+        }
 
-            match parts.len() {
-                1 => query = query.filter(events::dsl::event_type.like(format!("{}::%", p))),
-                2 => {
-                    query = query
-                        .filter(events::dsl::event_type.like(format!("{}::{}::%", p, parts[1])))
-                }
-                3 => {
-                    let validated_type = parse_sui_struct_tag(&event_type)
-                        .map_err(|e| DbValidationError::InvalidType(e.to_string()))?;
-
-                    if validated_type.type_params.is_empty() {
-                        query = query.filter(
-                            events::dsl::event_type
-                                .like(format!(
-                                    "{}<%",
-                                    validated_type.to_canonical_string(/* with_prefix */ true)
-                                ))
-                                .or(events::dsl::event_type
-                                    .eq(validated_type
-                                        .to_canonical_string(/* with_prefix */ true))),
-                        );
-                    } else {
-                        query = query.filter(
-                            events::dsl::event_type
-                                .eq(validated_type.to_canonical_string(/* with_prefix */ true)),
-                        );
-                    }
-                }
-                _ => {
-                    return Err(DbValidationError::InvalidType(
-                        TypeFilterError::TooManyComponents(
-                            event_type,
-                            3,
-                            "package[::module[::type[<type_params>]]]",
-                        )
-                        .to_string(),
-                    )
-                    .into());
-                }
-            }
+        // Filters on the event type
+        if let Some(event_types) = filter.event_type {
+            let patterns = type_patterns(&event_types, filter.event_type_match)?;
+
+            query = filter_on_types!(
+                query,
+                events::table,
+                events::dsl::event_type,
+                Bool,
+                patterns
+            );
         }
 
         Ok(query)
@@ -571,11 +773,229 @@ where
     }
 }
 
+impl RunQueryDsl<PgConnection> for AddressActivityQuery {}
+
+/// Activity is gathered from the indexes on transaction senders, recipients and Move calls, with
+/// each index contributing rows of a different `ActivityKind`, combined with `UNION ALL`.
+impl QueryFragment<Pg> for AddressActivityQuery {
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, Pg>) -> QueryResult<()> {
+        let staking_call = format!(
+            "EXISTS (\
+                SELECT 1 FROM tx_calls c \
+                WHERE c.tx_sequence_number = s.tx_sequence_number \
+                AND c.package = '\\x{}'::BYTEA \
+                AND c.module = '{}' \
+                AND c.func IN ({})\
+            )",
+            hex::encode(SUI_SYSTEM_PACKAGE_ID),
+            SUI_SYSTEM_MODULE_NAME,
+            STAKING_FUNCTIONS
+                .iter()
+                .map(|f| format!("'{f}'"))
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+
+        out.push_sql("SELECT tx_sequence_number, kind FROM (");
+
+        out.push_sql(&format!(
+            "SELECT s.tx_sequence_number, CAST({} AS SMALLINT) AS kind \
+             FROM tx_senders s WHERE s.sender = ",
+            ActivityKind::Sent as i16,
+        ));
+        out.push_bind_param::<Bytea, _>(&self.address)?;
+        out.push_sql(&format!(" AND NOT {staking_call}"));
+
+        out.push_sql(&format!(
+            " UNION ALL \
+             SELECT s.tx_sequence_number, CAST({} AS SMALLINT) AS kind \
+             FROM tx_senders s WHERE s.sender = ",
+            ActivityKind::Staking as i16,
+        ));
+        out.push_bind_param::<Bytea, _>(&self.address)?;
+        out.push_sql(&format!(" AND {staking_call}"));
+
+        // Every transaction an address sends also modifies its gas coin, so transactions sent by
+        // the address are excluded from the ones that it received objects in.
+        out.push_sql(&format!(
+            " UNION ALL \
+             SELECT r.tx_sequence_number, CAST({} AS SMALLINT) AS kind \
+             FROM tx_recipients r WHERE r.recipient = ",
+            ActivityKind::Received as i16,
+        ));
+        out.push_bind_param::<Bytea, _>(&self.address)?;
+        out.push_sql(
+            " AND NOT EXISTS (\
+                SELECT 1 FROM tx_senders s \
+                WHERE s.tx_sequence_number = r.tx_sequence_number AND s.sender = ",
+        );
+        out.push_bind_param::<Bytea, _>(&self.address)?;
+        out.push_sql(")) activity");
+
+        let mut connective = " WHERE";
+        if let Some((tx, kind)) = &self.after {
+            out.push_sql(connective);
+            out.push_sql(" (tx_sequence_number, kind) > (");
+            out.push_bind_param::<BigInt, _>(tx)?;
+            out.push_sql(", ");
+            out.push_bind_param::<SmallInt, _>(kind)?;
+            out.push_sql(")");
+            connective = " AND";
+        }
+
+        if let Some((tx, kind)) = &self.before {
+            out.push_sql(connective);
+            out.push_sql(" (tx_sequence_number, kind) < (");
+            out.push_bind_param::<BigInt, _>(tx)?;
+            out.push_sql(", ");
+            out.push_bind_param::<SmallInt, _>(kind)?;
+            out.push_sql(")");
+        }
+
+        let order = if self.descending_order { "DESC" } else { "ASC" };
+
+        out.push_sql(&format!(
+            " ORDER BY tx_sequence_number {order}, kind {order} LIMIT {}",
+            self.limit + 1,
+        ));
+
+        Ok(())
+    }
+}
+
+impl RunQueryDsl<PgConnection> for BalanceHistoryQuery {}
+
+/// Days are found by grouping checkpoints by their timestamps, and each day's balance is summed
+/// over the versions of the address's coins that were the latest as of the day's last checkpoint
+/// (as in `multi_get_historical_balances`). The checkpoints are bounded to the page's days, and
+/// both scans are supported by indices (`checkpoints_timestamp_ms` and `objects_history_coin`).
+impl QueryFragment<Pg> for BalanceHistoryQuery {
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, Pg>) -> QueryResult<()> {
+        out.push_sql(&format!(
+            "SELECT day, checkpoint, (\
+                SELECT CAST(SUM(h.coin_balance) AS BIGINT) FROM objects_history h \
+                WHERE h.owner_type = {} AND h.object_status = {} \
+                AND h.checkpoint_sequence_number <= days.checkpoint \
+                AND NOT EXISTS (\
+                    SELECT 1 FROM objects_history newer \
+                    WHERE newer.object_id = h.object_id \
+                    AND newer.object_version > h.object_version \
+                    AND newer.checkpoint_sequence_number <= days.checkpoint\
+                ) \
+                AND h.owner_id = ",
+            OwnerType::Address as i16,
+            ObjectStatus::Active as i16,
+        ));
+        out.push_bind_param::<Bytea, _>(&self.address)?;
+        out.push_sql(" AND h.coin_type = ");
+        out.push_bind_param::<Text, _>(&self.coin_type)?;
+        out.push_sql(&format!(
+            ") AS balance FROM (\
+                SELECT timestamp_ms / {MS_PER_DAY} AS day, MAX(sequence_number) AS checkpoint \
+                FROM checkpoints WHERE timestamp_ms >= "
+        ));
+        out.push_bind_param::<BigInt, _>(&self.start_ms)?;
+        out.push_sql(" AND timestamp_ms < ");
+        out.push_bind_param::<BigInt, _>(&self.end_ms)?;
+        out.push_sql(" GROUP BY day) days ORDER BY day");
+
+        Ok(())
+    }
+}
+
+/// Functions in `0x3::sui_system` that add or withdraw stake.
+const STAKING_FUNCTIONS: &[&str] = &[
+    "request_add_stake",
+    "request_add_stake_mul_coin",
+    "request_withdraw_stake",
+];
+
+/// Failures encountered while running a query against the database, classified by whether the
+/// client can expect the same request to succeed if it is retried.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum DbError {
+    #[error("No database connection available: {0}")]
+    PoolExhausted(String),
+    #[error("Database query timed out: {0}")]
+    StatementTimeout(String),
+    #[error("Database query conflicted with a concurrent update: {0}")]
+    SerializationFailure(String),
+    #[error("Failed to decode data read from the database: {0}")]
+    Decode(String),
+    #[error("Internal error occurred while querying the database: {0}")]
+    Other(String),
+}
+
+impl DbError {
+    /// Whether the same request may succeed if it is retried later, without modification.
+    pub(crate) fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            DbError::PoolExhausted(_)
+                | DbError::StatementTimeout(_)
+                | DbError::SerializationFailure(_)
+        )
+    }
+
+    pub(crate) fn code(&self) -> &'static str {
+        match self {
+            DbError::PoolExhausted(_) => code::DB_POOL_EXHAUSTED,
+            DbError::StatementTimeout(_) => code::DB_STATEMENT_TIMEOUT,
+            DbError::SerializationFailure(_) => code::DB_SERIALIZATION_FAILURE,
+            DbError::Decode(_) => code::DB_DECODE_ERROR,
+            DbError::Other(_) => code::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Classify a database error by its Postgres error message. By the time they reach us,
+    /// errors from the query itself have been flattened into strings by the `IndexerReader`.
+    fn from_message(message: String) -> Self {
+        if message.contains("statement timeout") {
+            DbError::StatementTimeout(message)
+        } else if message.contains("could not serialize access")
+            || message.contains("conflict with recovery")
+        {
+            // Queries against a read replica can also be cancelled if they conflict with
+            // replication, which Postgres reports as a serialization failure.
+            DbError::SerializationFailure(message)
+        } else if message.contains("Unexpected null for non-null column")
+            || message.contains("Error deserializing field")
+        {
+            DbError::Decode(message)
+        } else {
+            DbError::Other(message)
+        }
+    }
+}
+
+impl From<IndexerError> for DbError {
+    fn from(e: IndexerError) -> Self {
+        use diesel::result::{DatabaseErrorKind as K, Error as E};
+        match e {
+            IndexerError::PgPoolConnectionError(_) => DbError::PoolExhausted(e.to_string()),
+            IndexerError::PostgresError(E::DatabaseError(K::SerializationFailure, _)) => {
+                DbError::SerializationFailure(e.to_string())
+            }
+            IndexerError::PostgresError(E::DeserializationError(_))
+            | IndexerError::SerdeError(_)
+            | IndexerError::BcsError(_)
+            | IndexerError::PersistentStorageDataCorruptionError(_) => {
+                DbError::Decode(e.to_string())
+            }
+            IndexerError::PostgresReadError(message) => DbError::from_message(message),
+            _ => DbError::from_message(e.to_string()),
+        }
+    }
+}
+
 #[async_trait]
 pub trait PgQueryExecutor {
+    /// Run `query` on the next read replica (or the primary, if none are healthy). Queries that
+    /// cannot connect to their replica are retried on the primary, which is why `query` must be
+    /// `Clone`.
     async fn run_query_async<T, E, F>(&self, query: F) -> Result<T, Error>
     where
-        F: FnOnce(&mut PgConnection) -> Result<T, E> + Send + 'static,
+        F: FnOnce(&mut PgConnection) -> Result<T, E> + Clone + Send + 'static,
         E: From<diesel::result::Error> + std::error::Error + Send + 'static,
         T: Send + 'static;
 
@@ -591,7 +1011,7 @@ pub trait PgQueryExecutor {
             + diesel::query_builder::QueryId
             + Send
             + 'static,
-        EF: FnOnce(QResult) -> F + Send + 'static,
+        EF: Fn(QResult) -> F + Send + 'static,
         F: FnOnce(&mut PgConnection) -> Result<T, E> + Send + 'static,
         E: From<diesel::result::Error> + std::error::Error + Send + 'static,
         T: Send + 'static;
@@ -601,14 +1021,24 @@ pub trait PgQueryExecutor {
 impl PgQueryExecutor for PgManager {
     async fn run_query_async<T, E, F>(&self, query: F) -> Result<T, Error>
     where
-        F: FnOnce(&mut PgConnection) -> Result<T, E> + Send + 'static,
+        F: FnOnce(&mut PgConnection) -> Result<T, E> + Clone + Send + 'static,
         E: From<diesel::result::Error> + std::error::Error + Send + 'static,
         T: Send + 'static,
     {
-        self.inner
-            .run_query_async(query)
-            .await
-            .map_err(|e| Error::Internal(e.to_string()))
+        let (replica, reader) = self.read_reader();
+        let result = match self.run_query_on(replica, reader, query.clone()).await {
+            Err(e) if replica.is_some() && is_unreachable(&e) => {
+                self.replicas.report_error(replica, &e);
+                warn!(
+                    replica,
+                    "Query failed on read replica, retrying on primary: {e}"
+                );
+                self.run_query_on(None, &self.inner, query).await
+            }
+            result => result,
+        };
+
+        result.map_err(|e| Error::Db(e.into()))
     }
 
     /// Takes a query_builder_fn that returns Result<QueryFragment> and a lambda to execute the query
@@ -626,14 +1056,22 @@ impl PgQueryExecutor for PgManager {
             + diesel::query_builder::QueryId
             + Send
             + 'static,
-        EF: FnOnce(QResult) -> F + Send + 'static,
+        EF: Fn(QResult) -> F + Send + 'static,
         F: FnOnce(&mut PgConnection) -> Result<T, E> + Send + 'static,
         E: From<diesel::result::Error> + std::error::Error + Send + 'static,
         T: Send + 'static,
     {
         let max_db_query_cost = self.limits.max_db_query_cost;
-        self.inner
+        let primary = self.inner.clone();
+        let replicas = self.replicas.clone();
+        let stats = self.stats.clone();
+        let (replica, reader) = self.read_reader();
+        let span = db_query_span::<Q>(replica);
+        let query_span = span.clone();
+        reader
             .spawn_blocking(move |this| {
+                let _guard = query_span.enter();
+                let mut estimated_cost = None;
                 let query = query_builder_fn()?;
                 let explain_result: Option<String> = this
                     .run_query(|conn| query.explain().get_result(conn))
@@ -656,6 +1094,8 @@ impl PgQueryExecutor for PgManager {
                         .ok(); // Fine to not propagate this error as explain-based costing is not critical today
 
                     if let Some(cost) = cost {
+                        query_span.record("cost", cost);
+                        estimated_cost = Some(cost);
                         if cost > max_db_query_cost as f64 {
                             warn!(
                                 target: EXPLAIN_COSTING_LOG_TARGET,
@@ -674,13 +1114,86 @@ impl PgQueryExecutor for PgManager {
 
                 let query = query_builder_fn()?;
                 let execute_closure = execute_fn(query);
-                this.run_query(execute_closure)
-                    .map_err(|e| Error::Internal(e.to_string()))
+                let timer = stats.start::<Q>();
+                let result = this.run_query(move |conn| timer.time(|| execute_closure(conn)));
+
+                stats.observe_pool(&PgManager::pool_name(replica), &this);
+                let result = match result {
+                    Err(e) if replica.is_some() && is_unreachable(&e) => {
+                        replicas.report_error(replica, &e);
+                        warn!(
+                            replica,
+                            "Query failed on read replica, retrying on primary: {e}"
+                        );
+
+                        // The retry gets a span of its own, so that the rows it returns are not
+                        // attributed to the replica.
+                        let retry_span = db_query_span::<Q>(None);
+                        if let Some(cost) = estimated_cost {
+                            retry_span.record("cost", cost);
+                        }
+
+                        let _guard = retry_span.enter();
+                        let query = query_builder_fn()?;
+                        let execute_closure = execute_fn(query);
+                        let timer = stats.start::<Q>();
+                        let result =
+                            primary.run_query(move |conn| timer.time(|| execute_closure(conn)));
+
+                        stats.observe_pool(&PgManager::pool_name(None), &primary);
+                        result
+                    }
+                    result => result,
+                };
+
+                result.map_err(|e| Error::Db(e.into()))
             })
+            .instrument(span)
             .await
     }
 }
 
+impl PgManager {
+    /// The reader to send the next read query to: a healthy replica if there is one, otherwise the
+    /// primary. If a replica is chosen, its index is also returned, to report errors against.
+    fn read_reader(&self) -> (Option<usize>, &IndexerReader) {
+        match self.replicas.next() {
+            Some((index, reader)) => (Some(index), reader),
+            None => (None, &self.inner),
+        }
+    }
+
+    /// Run `query` using `reader`, which is the replica at index `replica`, or the primary.
+    async fn run_query_on<T, E, F>(
+        &self,
+        replica: Option<usize>,
+        reader: &IndexerReader,
+        query: F,
+    ) -> Result<T, IndexerError>
+    where
+        F: FnOnce(&mut PgConnection) -> Result<T, E> + Send + 'static,
+        E: From<diesel::result::Error> + std::error::Error + Send + 'static,
+        T: Send + 'static,
+    {
+        let timer = self.stats.start::<F>();
+        let span = db_query_span::<F>(replica);
+        let query_span = span.clone();
+        let result = reader
+            .run_query_async(move |conn| {
+                // The query runs on a blocking thread, which does not inherit the span that the
+                // future is instrumented with.
+                let _guard = query_span.enter();
+                timer.time(|| query(conn))
+            })
+            .instrument(span)
+            .await;
+
+        self.stats
+            .observe_pool(&PgManager::pool_name(replica), reader);
+        result
+    }
+}
+
 pub fn extract_cost(explain_result: &str) -> Result<f64, Error> {
     let parsed: serde_json::Value =
         serde_json::from_str(explain_result).map_err(|e| Error::Internal(e.to_string()))?;
@@ -714,11 +1227,111 @@ fn order_objs(before: Option<Vec<u8>>, after: Option<Vec<u8>>) -> objects::Boxed
     query
 }
 
+/// Parse `types` into the patterns that match each of them under `type_match` (matching by prefix,
+/// if no `type_match` is given).
+fn type_patterns(
+    types: &[String],
+    type_match: Option<TypeMatch>,
+) -> Result<Vec<TypePattern>, Error> {
+    let type_match = type_match.unwrap_or_default();
+    Ok(TypeFilter::parse_any(types)?
+        .iter()
+        .map(|type_filter| type_filter.pattern(type_match))
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+/// The kind of objects being fetched may be implied by the query (e.g. fetching dynamic fields
+/// implies object-owned objects), or requested explicitly in the filter.
+fn resolve_owner_type(
+    implied: Option<OwnerType>,
+    requested: Option<OwnerType>,
+) -> Result<Option<OwnerType>, Error> {
+    match (implied, requested) {
+        (Some(implied), Some(requested)) if implied as i16 != requested as i16 => {
+            Err(DbValidationError::ConflictingObjectKind.into())
+        }
+        (implied, requested) => Ok(implied.or(requested)),
+    }
+}
+
+/// The owner types (as stored) of objects of `owner_type` that have an owner, or of any objects
+/// with an owner, if no owner type is given.
+fn owned_types(owner_type: Option<OwnerType>) -> Result<Vec<i16>, Error> {
+    Ok(match owner_type {
+        Some(OwnerType::Address) => vec![OwnerType::Address as i16],
+        Some(OwnerType::Object) => vec![OwnerType::Object as i16],
+        None => vec![OwnerType::Address as i16, OwnerType::Object as i16],
+        _ => Err(DbValidationError::InvalidOwnerType)?,
+    })
+}
+
+/// Predicate selecting rows of `objects_history` that hold the latest version of their object as
+/// of `checkpoint` -- i.e. the object was not subsequently modified, transferred, wrapped or
+/// deleted by the time the checkpoint was executed.
+fn latest_as_of_checkpoint(checkpoint: i64) -> SqlLiteral<Bool> {
+    diesel::dsl::sql::<Bool>(&format!(
+        "NOT EXISTS (\
+            SELECT 1 FROM objects_history newer \
+            WHERE newer.object_id = objects_history.object_id \
+            AND newer.object_version > objects_history.object_version \
+            AND newer.checkpoint_sequence_number <= {checkpoint}\
+        )"
+    ))
+}
+
+/// Predicate selecting objects owned by `owner` (as one of `owner_types`), as well as objects
+/// owned by those objects, transitively, up to `depth` levels of nesting. Ownership chains are
+/// followed with a recursive CTE, bounded by `depth` in how deep it goes, and by
+/// `MAX_NESTED_OBJECTS` in how many objects it visits overall, so that an owner with many objects
+/// at each level cannot make the expansion arbitrarily expensive. Postgres only evaluates as many
+/// rows of the CTE as the outer `LIMIT` fetches, so the recursion stops once that many objects
+/// have been found.
+fn owned_transitively(
+    owner: Vec<u8>,
+    owner_types: &[i16],
+    depth: u32,
+) -> Box<dyn BoxableExpression<objects::table, Pg, SqlType = Bool>> {
+    let owner_types = owner_types
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Box::new(
+        diesel::dsl::sql::<Bool>(
+            "objects.object_id IN (\
+                WITH RECURSIVE owned (object_id, depth) AS (\
+                    SELECT object_id, 0 FROM objects WHERE owner_id = ",
+        )
+        .bind::<Bytea, _>(owner)
+        .sql(&format!(
+            " AND owner_type IN ({owner_types}) \
+                UNION ALL \
+                SELECT o.object_id, owned.depth + 1 FROM objects o \
+                JOIN owned ON o.owner_id = owned.object_id \
+                WHERE o.owner_type = {} AND owned.depth < {depth}\
+            ) SELECT object_id FROM owned LIMIT {MAX_NESTED_OBJECTS})",
+            OwnerType::Object as i16,
+        )),
+    )
+}
+
+/// Escape the characters in `literal` that have special meaning in a `LIKE` pattern, so that it
+/// only matches itself. Module and type names may contain underscores, which would otherwise
+/// match any character.
+fn escape_like(literal: &str) -> String {
+    literal
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
 pub(crate) type QueryBuilder = PgQueryBuilder;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::sui_address::SuiAddress;
 
     #[test]
     fn test_invalid_json() {
@@ -761,4 +1374,107 @@ mod tests {
         let result = extract_cost(explain_result).unwrap();
         assert_eq!(result, 1.0);
     }
+
+    #[test]
+    fn test_db_error_classification() {
+        let classify = |e: IndexerError| {
+            let e = DbError::from(e);
+            (e.code(), e.is_retryable())
+        };
+
+        assert_eq!(
+            classify(IndexerError::PgPoolConnectionError("timed out".to_string())),
+            (code::DB_POOL_EXHAUSTED, true),
+        );
+        assert_eq!(
+            classify(IndexerError::PostgresReadError(
+                "canceling statement due to statement timeout".to_string()
+            )),
+            (code::DB_STATEMENT_TIMEOUT, true),
+        );
+        assert_eq!(
+            classify(IndexerError::PostgresReadError(
+                "canceling statement due to conflict with recovery".to_string()
+            )),
+            (code::DB_SERIALIZATION_FAILURE, true),
+        );
+        assert_eq!(
+            classify(IndexerError::PostgresReadError(
+                "Unexpected null for non-null column".to_string()
+            )),
+            (code::DB_DECODE_ERROR, false),
+        );
+        assert_eq!(
+            classify(IndexerError::PostgresReadError(
+                "relation \"objects\" does not exist".to_string()
+            )),
+            (code::INTERNAL_SERVER_ERROR, false),
+        );
+    }
+
+    #[test]
+    fn test_balance_history_bounds() {
+        let query = PgQueryBuilder::multi_get_balance_history(
+            vec![0; 32],
+            "0x2::sui::SUI".to_string(),
+            19_700..=19_709,
+        );
+
+        assert_eq!(query.start_ms, 19_700 * MS_PER_DAY);
+        assert_eq!(query.end_ms, 19_710 * MS_PER_DAY);
+
+        let sql = diesel::debug_query::<Pg, _>(&query).to_string();
+        assert!(sql.contains("FROM checkpoints WHERE timestamp_ms >= $3 AND timestamp_ms < $4"));
+        assert!(sql.contains("GROUP BY day) days ORDER BY day"));
+        assert!(!sql.contains("LIMIT"));
+    }
+
+    #[test]
+    fn test_nested_ownership_expansion() {
+        let sql = |nested_depth| {
+            let filter = ObjectFilter {
+                owner: Some(SuiAddress::from_bytes([1; 32]).unwrap()),
+                ..Default::default()
+            };
+            let query =
+                PgQueryBuilder::multi_get_objs(None, None, 10, Some(filter), None, nested_depth)
+                    .unwrap();
+            diesel::debug_query::<Pg, _>(&query).to_string()
+        };
+
+        let direct = sql(None);
+        assert!(!direct.contains("WITH RECURSIVE"));
+        assert!(direct.contains("\"objects\".\"owner_id\" = $1"));
+
+        let nested = sql(Some(3));
+        assert!(nested.contains("WITH RECURSIVE owned"));
+        assert!(nested.contains("WHERE owner_id = $1 AND owner_type IN (1, 2)"));
+        assert!(nested.contains("owned.depth < 3"));
+        assert!(nested.contains(&format!("FROM owned LIMIT {MAX_NESTED_OBJECTS})")));
+        assert!(!nested.contains("\"objects\".\"owner_id\" ="));
+    }
+
+    #[test]
+    fn test_objects_at_checkpoint() {
+        let filter = ObjectFilter {
+            owner: Some(SuiAddress::from_bytes([1; 32]).unwrap()),
+            ..Default::default()
+        };
+        let query = PgQueryBuilder::multi_get_objs_at_checkpoint(
+            None,
+            Some(vec![2; 32]),
+            10,
+            Some(filter),
+            None,
+            42,
+        )
+        .unwrap();
+        let sql = diesel::debug_query::<Pg, _>(&query).to_string();
+
+        assert!(sql.contains("\"objects_history\".\"checkpoint_sequence_number\" <= $2"));
+        assert!(sql.contains("AND newer.checkpoint_sequence_number <= 42"));
+        assert!(sql.contains("\"objects_history\".\"object_id\" > $3"));
+        assert!(sql.contains("\"objects_history\".\"owner_id\" = $4"));
+        assert!(sql.contains("ORDER BY \"objects_history\".\"object_id\" ASC LIMIT $5"));
+    }
 }
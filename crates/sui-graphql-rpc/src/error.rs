@@ -6,7 +6,7 @@ use async_graphql_axum::GraphQLResponse;
 use sui_indexer::errors::IndexerError;
 use sui_json_rpc::name_service::DomainParseError;
 
-use crate::context_data::db_data_provider::DbValidationError;
+use crate::context_data::{db_data_provider::DbValidationError, pg_backend::DbError};
 
 /// Error codes for the `extensions.code` field of a GraphQL error that originates from outside
 /// GraphQL.
@@ -14,8 +14,15 @@ use crate::context_data::db_data_provider::DbValidationError;
 pub(crate) mod code {
     pub const BAD_REQUEST: &str = "BAD_REQUEST";
     pub const BAD_USER_INPUT: &str = "BAD_USER_INPUT";
+    pub const DB_DECODE_ERROR: &str = "DB_DECODE_ERROR";
+    pub const DB_POOL_EXHAUSTED: &str = "DB_POOL_EXHAUSTED";
+    pub const DB_SERIALIZATION_FAILURE: &str = "DB_SERIALIZATION_FAILURE";
+    pub const DB_STATEMENT_TIMEOUT: &str = "DB_STATEMENT_TIMEOUT";
+    pub const FEATURE_DISABLED: &str = "FEATURE_DISABLED";
+    pub const FORBIDDEN: &str = "FORBIDDEN";
     pub const GRAPHQL_VALIDATION_FAILED: &str = "GRAPHQL_VALIDATION_FAILED";
     pub const INTERNAL_SERVER_ERROR: &str = "INTERNAL_SERVER_ERROR";
+    pub const TOO_MANY_REQUESTS: &str = "TOO_MANY_REQUESTS";
 }
 
 /// Create a GraphQL Response containing an Error.
@@ -97,6 +104,10 @@ pub enum Error {
     #[error("{0}")]
     // Catch-all for client-fault errors
     Client(String),
+    #[error("This query is only available to administrators of this service")]
+    AdminOnly,
+    #[error(transparent)]
+    Db(#[from] DbError),
     #[error("Internal error occurred while processing request: {0}")]
     Internal(String),
 }
@@ -122,6 +133,13 @@ impl ErrorExtensions for Error {
             | Error::Client(_) => {
                 e.set("code", code::BAD_USER_INPUT);
             }
+            Error::AdminOnly => {
+                e.set("code", code::FORBIDDEN);
+            }
+            Error::Db(db) => {
+                e.set("code", db.code());
+                e.set("retryable", db.is_retryable());
+            }
             Error::Internal(_) => {
                 e.set("code", code::INTERNAL_SERVER_ERROR);
             }
@@ -1,18 +1,21 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
+use std::{collections::BTreeSet, sync::Arc};
 
 use async_graphql::{
     extensions::{Extension, ExtensionContext, ExtensionFactory, NextResolve, ResolveInfo},
-    ServerError, ServerResult, Value,
+    registry::Registry,
+    OutputType, ServerError, ServerResult, Value,
 };
 use async_trait::async_trait;
 
 use crate::{
     config::ServiceConfig,
-    error::{code, graphql_error},
+    error::{code, graphql_error, Error},
     functional_group::functional_group,
+    mutation::Mutation,
+    types::query::Query,
 };
 
 pub(crate) struct FeatureGate;
@@ -39,7 +42,9 @@ impl Extension for FeatureGate {
         } = &info;
 
         let ServiceConfig {
-            disabled_features, ..
+            disabled_features,
+            disabled_fields,
+            ..
         } = ctx.data().map_err(|_| {
             graphql_error(
                 code::INTERNAL_SERVER_ERROR,
@@ -52,28 +57,62 @@ impl Extension for FeatureGate {
         // `Context`). This is (probably) required to hide disabled types and interfaces in the
         // schema.
 
-        if let Some(group) = functional_group(parent_type, name) {
-            if disabled_features.contains(&group) {
-                return if *is_for_introspection {
-                    Ok(None)
-                } else {
-                    Err(ServerError::new(
-                        format!(
-                            "Cannot query field \"{name}\" on type \"{parent_type}\". \
-                             Feature {} is disabled.",
-                            group.name(),
-                        ),
-                        // TODO: Fork `async-graphl` to add field position information to
-                        // `ResolveInfo`, so the error can take advantage of it.  Similarly for
-                        // utilising the `path_node` to set the error path.
-                        None,
-                    ))
-                };
-            }
+        let disabled = if let Some(group) =
+            functional_group(parent_type, name).filter(|g| disabled_features.contains(g))
+        {
+            Some(format!("Feature {} is disabled.", group.name()))
+        } else if disabled_fields.contains(&format!("{parent_type}.{name}")) {
+            Some("Field is disabled.".to_string())
+        } else {
+            None
+        };
+
+        let Some(reason) = disabled else {
+            return next.run(ctx, info).await;
+        };
+
+        if *is_for_introspection {
+            Ok(None)
+        } else {
+            // TODO: Fork `async-graphl` to add field position information to `ResolveInfo`, so the
+            // error can take advantage of it.  Similarly for utilising the `path_node` to set the
+            // error path.
+            Err(graphql_error(
+                code::FEATURE_DISABLED,
+                format!("Cannot query field \"{name}\" on type \"{parent_type}\". {reason}"),
+            ))
         }
+    }
+}
 
-        next.run(ctx, info).await
+/// Check that every field in `disabled_fields` (identified as `Type.field`) exists in the schema,
+/// so that a mistyped field in the service's configuration is caught at startup, rather than
+/// silently leaving the field it was meant to disable enabled.
+pub(crate) fn check_disabled_fields(disabled_fields: &BTreeSet<String>) -> Result<(), Error> {
+    for field in disabled_fields {
+        if !is_schema_field(field) {
+            return Err(Error::Internal(format!(
+                "Disabled field '{field}' does not appear in the schema. Expected a field \
+                 identified as 'Type.field'."
+            )));
+        }
     }
+
+    Ok(())
+}
+
+/// Whether `field`, identified as `Type.field`, appears in the schema.
+pub(crate) fn is_schema_field(field: &str) -> bool {
+    let mut registry = Registry::default();
+    Query::create_type_info(&mut registry);
+    Mutation::create_type_info(&mut registry);
+
+    field.split_once('.').is_some_and(|(type_, name)| {
+        registry
+            .concrete_type_by_name(type_)
+            .and_then(|meta_type| meta_type.field_by_name(name))
+            .is_some()
+    })
 }
 
 #[cfg(test)]
@@ -121,4 +160,53 @@ mod tests {
             ]"#]];
         expect.assert_eq(&format!("{errs:#?}"));
     }
+
+    #[tokio::test]
+    async fn test_accessing_a_disabled_individual_field() {
+        let errs: Vec<_> = Schema::build(Query, Mutation, EmptySubscription)
+            .data(ServiceConfig {
+                disabled_fields: BTreeSet::from_iter(["Query.protocolConfig".to_string()]),
+                ..Default::default()
+            })
+            .extension(FeatureGate)
+            .finish()
+            .execute("{ protocolConfig(protocolVersion: 1) { protocolVersion } }")
+            .await
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| {
+                (
+                    e.message,
+                    e.extensions.and_then(|ext| ext.get("code").cloned()),
+                )
+            })
+            .collect();
+
+        let expect = expect![[r#"
+            [
+                (
+                    "Cannot query field \"protocolConfig\" on type \"Query\". Field is disabled.",
+                    Some(
+                        String(
+                            "FEATURE_DISABLED",
+                        ),
+                    ),
+                ),
+            ]"#]];
+        expect.assert_eq(&format!("{errs:#?}"));
+    }
+
+    #[test]
+    fn test_check_disabled_fields() {
+        let fields = |fs: &[&str]| fs.iter().map(|f| f.to_string()).collect::<BTreeSet<_>>();
+
+        check_disabled_fields(&fields(&[])).unwrap();
+        check_disabled_fields(&fields(&["Query.eventConnection", "Address.balance"])).unwrap();
+        check_disabled_fields(&fields(&["Mutation.executeTransactionBlock"])).unwrap();
+
+        assert!(check_disabled_fields(&fields(&["Query.notAField"])).is_err());
+        assert!(check_disabled_fields(&fields(&["NotAType.balance"])).is_err());
+        assert!(check_disabled_fields(&fields(&["eventConnection"])).is_err());
+    }
 }
@@ -3,5 +3,7 @@
 
 pub(crate) mod feature_gate;
 pub(crate) mod logger;
+pub(crate) mod page_size;
 pub mod query_limits_checker;
+pub(crate) mod rate_limiter;
 pub(crate) mod timeout;
@@ -0,0 +1,196 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextResolve, ResolveInfo},
+    ServerResult, Value,
+};
+use async_trait::async_trait;
+
+use crate::{config::PageCategory, error::Error};
+
+tokio::task_local! {
+    /// The field currently being resolved, identified as `Type.field`.
+    static FIELD: String;
+}
+
+/// Keeps track of the field being resolved, so that page size and ownership depth overrides for
+/// individual connection fields can be applied by the data provider while it serves that field.
+pub(crate) struct PageSizeScope;
+
+impl ExtensionFactory for PageSizeScope {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(PageSizeScope)
+    }
+}
+
+#[async_trait]
+impl Extension for PageSizeScope {
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        let field = format!("{}.{}", info.parent_type, info.name);
+        FIELD.scope(field, next.run(ctx, info)).await
+    }
+}
+
+/// The field currently being resolved, identified as `Type.field`, if any (and if the
+/// `PageSizeScope` extension is installed).
+pub(crate) fn current_field() -> Option<String> {
+    FIELD.try_with(|field| field.clone()).ok()
+}
+
+/// Connection fields whose pages are read from the database, and are therefore subject to the
+/// service's maximum page sizes, identified as `Type.field`. Fields are identified by the concrete
+/// type they are resolved on, so fields on interfaces are overridden through the types that
+/// implement them.
+const PAGE_LIMITED_FIELDS: &[&str] = &[
+    "Address.activityConnection",
+    "Address.balanceHistory",
+    "Address.coinConnection",
+    "Address.objectConnection",
+    "Address.stakedSuiConnection",
+    "Address.transactionBlockConnection",
+    "Checkpoint.transactionBlockConnection",
+    "Epoch.checkpointConnection",
+    "Epoch.transactionBlockConnection",
+    "Object.coinConnection",
+    "Object.dynamicFieldConnection",
+    "Object.objectConnection",
+    "Object.stakedSuiConnection",
+    "Object.versionConnection",
+    "Owner.coinConnection",
+    "Owner.dynamicFieldConnection",
+    "Owner.objectConnection",
+    "Owner.stakedSuiConnection",
+    "Query.checkpointConnection",
+    "Query.coinConnection",
+    "Query.eventConnection",
+    "Query.objectConnection",
+    "Query.transactionBlockConnection",
+];
+
+/// Object connection fields that can expand nested ownership (see `ObjectFilter.includeNested`),
+/// identified as `Type.field`.
+const NESTED_OBJECT_FIELDS: &[&str] = &[
+    "Address.objectConnection",
+    "Object.objectConnection",
+    "Owner.objectConnection",
+    "Query.objectConnection",
+];
+
+/// Make sure every page size override applies to a category of connection, or to a connection
+/// field whose page size is limited, so that typos are caught at startup rather than silently
+/// ignored.
+pub(crate) fn check_max_page_sizes(max_page_sizes: &BTreeMap<String, u64>) -> Result<(), Error> {
+    for key in max_page_sizes.keys() {
+        let is_category = PageCategory::all().iter().any(|c| c.name() == key);
+        if !is_category && !PAGE_LIMITED_FIELDS.contains(&key.as_str()) {
+            return Err(Error::Internal(format!(
+                "Page size override '{key}' does not match a category of connection or a \
+                 connection field with a limited page size. Expected one of {:?}, or one of \
+                 {PAGE_LIMITED_FIELDS:?}.",
+                PageCategory::all()
+                    .iter()
+                    .map(|c| c.name())
+                    .collect::<Vec<_>>(),
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Make sure every ownership depth override applies to the objects category, or to an object
+/// connection field that can expand nested ownership.
+pub(crate) fn check_max_ownership_depths(
+    max_ownership_depths: &BTreeMap<String, u32>,
+) -> Result<(), Error> {
+    let category = PageCategory::Objects.name();
+    for key in max_ownership_depths.keys() {
+        if key != category && !NESTED_OBJECT_FIELDS.contains(&key.as_str()) {
+            return Err(Error::Internal(format!(
+                "Ownership depth override '{key}' does not match a connection that can expand \
+                 nested ownership. Expected '{category}', or one of {NESTED_OBJECT_FIELDS:?}.",
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::extensions::feature_gate::is_schema_field;
+
+    use super::*;
+
+    #[test]
+    fn test_check_max_page_sizes() {
+        let valid = BTreeMap::from([
+            ("events".to_string(), 10),
+            ("Address.coinConnection".to_string(), 5),
+        ]);
+        assert!(check_max_page_sizes(&valid).is_ok());
+
+        let bad_category = BTreeMap::from([("evnets".to_string(), 10)]);
+        assert!(check_max_page_sizes(&bad_category).is_err());
+
+        let bad_field = BTreeMap::from([("Address.coinConection".to_string(), 5)]);
+        assert!(check_max_page_sizes(&bad_field).is_err());
+
+        // Connections that are paginated in memory are not limited.
+        let unlimited_field = BTreeMap::from([("MovePackage.moduleConnection".to_string(), 5)]);
+        assert!(check_max_page_sizes(&unlimited_field).is_err());
+
+        // Fields are identified by the type they are resolved on, not the interface.
+        let interface_field = BTreeMap::from([("ObjectOwner.coinConnection".to_string(), 5)]);
+        assert!(check_max_page_sizes(&interface_field).is_err());
+    }
+
+    #[test]
+    fn test_check_max_ownership_depths() {
+        let valid = BTreeMap::from([
+            ("objects".to_string(), 2),
+            ("Query.objectConnection".to_string(), 1),
+        ]);
+        assert!(check_max_ownership_depths(&valid).is_ok());
+
+        let bad_category = BTreeMap::from([("coins".to_string(), 2)]);
+        assert!(check_max_ownership_depths(&bad_category).is_err());
+
+        let bad_field = BTreeMap::from([("Address.coinConnection".to_string(), 2)]);
+        assert!(check_max_ownership_depths(&bad_field).is_err());
+    }
+
+    #[test]
+    fn test_limited_fields_in_schema() {
+        for field in PAGE_LIMITED_FIELDS.iter().chain(NESTED_OBJECT_FIELDS) {
+            assert!(is_schema_field(field), "{field} is not in the schema");
+        }
+
+        for field in NESTED_OBJECT_FIELDS {
+            assert!(
+                PAGE_LIMITED_FIELDS.contains(field),
+                "{field} is not page limited"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_current_field() {
+        assert_eq!(current_field(), None);
+
+        let field = FIELD
+            .scope("Query.eventConnection".to_string(), async {
+                current_field()
+            })
+            .await;
+        assert_eq!(field.as_deref(), Some("Query.eventConnection"));
+    }
+}
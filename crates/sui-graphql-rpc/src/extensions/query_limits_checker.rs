@@ -7,6 +7,7 @@ use crate::error::code;
 use crate::error::code::INTERNAL_SERVER_ERROR;
 use crate::error::graphql_error;
 use crate::error::graphql_error_at_pos;
+use crate::extensions::rate_limiter::QueryCost;
 use crate::metrics::RequestMetrics;
 use async_graphql::extensions::NextParseQuery;
 use async_graphql::extensions::NextRequest;
@@ -179,6 +180,9 @@ impl Extension for QueryLimitsChecker {
                 num_fragments: doc.fragments.len() as u32,
             });
         }
+        if let Some(cost) = ctx.data_opt::<QueryCost>() {
+            cost.record(running_costs.num_nodes);
+        }
         if let Some(metrics) = ctx.data_opt::<Arc<RequestMetrics>>() {
             metrics.num_nodes.observe(running_costs.num_nodes as f64);
             metrics.query_depth.observe(running_costs.depth as f64);
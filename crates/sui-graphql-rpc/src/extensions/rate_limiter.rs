@@ -0,0 +1,324 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    net::{IpAddr, SocketAddr},
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextExecute},
+    Response,
+};
+use axum::{
+    headers,
+    http::{HeaderName, HeaderValue},
+};
+use lru::LruCache;
+
+use crate::{
+    config::{RateLimits, ServiceConfig},
+    error::{code, graphql_error},
+};
+
+pub static API_KEY_HEADER: HeaderName = HeaderName::from_static("x-sui-rpc-api-key");
+
+/// Maximum number of clients whose budgets are tracked at once. When this is exceeded, the least
+/// recently seen client is forgotten (and starts again with a full budget when next seen).
+const MAX_TRACKED_CLIENTS: usize = 100_000;
+
+/// Identifies the client a request was made on behalf of, by an API key if one was supplied.
+#[derive(Clone, Debug)]
+pub(crate) struct ApiKey(pub String);
+
+/// The cost of a request's query, as measured by the query limits checker. Inserted into each
+/// request's data so that the rate limiter can charge the query's cost against its client's
+/// budget.
+#[derive(Debug, Default)]
+pub(crate) struct QueryCost(AtomicU32);
+
+/// Limits the rate at which each client can issue requests, and the rate at which they can spend
+/// query cost (measured in query nodes), using a token bucket per client for each.
+///
+/// Clients are identified by their API key, if they supply one that the service is configured
+/// with (see `ServiceConfig::client_api_keys`), and otherwise by their IP address, so that clients
+/// cannot escape their budgets by making up new keys. Requests that exceed either budget fail with a `TOO_MANY_REQUESTS` error, without being
+/// executed.
+#[derive(Clone)]
+pub(crate) struct RateLimiter {
+    clients: Arc<Mutex<LruCache<ClientId, ClientBudget>>>,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+enum ClientId {
+    ApiKey(String),
+    Ip(IpAddr),
+}
+
+struct ClientBudget {
+    requests: TokenBucket,
+    query_nodes: TokenBucket,
+}
+
+/// A bucket holding up to `burst` tokens, refilled continuously at `rate` tokens per second.
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl headers::Header for ApiKey {
+    fn name() -> &'static HeaderName {
+        &API_KEY_HEADER
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, headers::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let value = values.next().ok_or_else(headers::Error::invalid)?;
+        let key = value.to_str().map_err(|_| headers::Error::invalid())?;
+        Ok(ApiKey(key.to_string()))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, _: &mut E) {
+        unimplemented!()
+    }
+}
+
+impl QueryCost {
+    pub(crate) fn record(&self, nodes: u32) {
+        self.0.store(nodes, Ordering::Relaxed);
+    }
+
+    fn nodes(&self) -> u32 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl RateLimiter {
+    pub(crate) fn new() -> Self {
+        let capacity =
+            NonZeroUsize::new(MAX_TRACKED_CLIENTS).expect("Client capacity must be non-zero");
+        Self {
+            clients: Arc::new(Mutex::new(LruCache::new(capacity))),
+        }
+    }
+
+    /// Charge one request, costing `nodes`, to `client`'s budgets. Returns an error message
+    /// describing the budget that was exceeded, if either was. Nothing is charged to either budget
+    /// unless both can afford the request.
+    fn charge(
+        &self,
+        limits: &RateLimits,
+        client: ClientId,
+        nodes: u32,
+        now: Instant,
+    ) -> Result<(), String> {
+        let mut clients = self.clients.lock().unwrap();
+        if !clients.contains(&client) {
+            let budget = ClientBudget {
+                requests: TokenBucket::full(limits.request_burst, now),
+                query_nodes: TokenBucket::full(limits.query_node_burst, now),
+            };
+            clients.put(client.clone(), budget);
+        }
+
+        let budget = clients.get_mut(&client).expect("Budget inserted above");
+
+        budget
+            .requests
+            .refill(limits.requests_per_second, limits.request_burst, now);
+        budget
+            .query_nodes
+            .refill(limits.query_nodes_per_second, limits.query_node_burst, now);
+
+        if budget.requests.tokens < 1.0 {
+            return Err(format!(
+                "Too many requests. The limit is {} requests per second",
+                limits.requests_per_second,
+            ));
+        }
+
+        if budget.query_nodes.tokens < nodes as f64 {
+            return Err(format!(
+                "Query cost budget exhausted. The limit is {} query nodes per second",
+                limits.query_nodes_per_second,
+            ));
+        }
+
+        budget.requests.tokens -= 1.0;
+        budget.query_nodes.tokens -= nodes as f64;
+        Ok(())
+    }
+}
+
+impl ExtensionFactory for RateLimiter {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(self.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for RateLimiter {
+    /// Requests are charged once they have been parsed and validated (so that their cost is
+    /// known), but before they are executed.
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let cfg = ctx
+            .data::<ServiceConfig>()
+            .expect("No service config provided in schema data");
+
+        let api_key = ctx.data_opt::<ApiKey>();
+        let addr = ctx.data_opt::<SocketAddr>();
+        let Some(client) = ClientId::identify(cfg, api_key, addr) else {
+            // Requests that did not come through the HTTP handler (e.g. internal health checks)
+            // cannot be attributed to a client.
+            return next.run(ctx, operation_name).await;
+        };
+
+        let nodes = ctx.data_opt::<QueryCost>().map_or(0, QueryCost::nodes);
+        match self.charge(&cfg.rate_limits, client, nodes, Instant::now()) {
+            Ok(()) => next.run(ctx, operation_name).await,
+            Err(message) => {
+                Response::from_errors(vec![graphql_error(code::TOO_MANY_REQUESTS, message)])
+            }
+        }
+    }
+}
+
+impl ClientId {
+    /// Identify the client by `api_key`, if it is one of the keys the service is configured with,
+    /// and otherwise by the IP address the request came from, if it is known.
+    fn identify(
+        cfg: &ServiceConfig,
+        api_key: Option<&ApiKey>,
+        addr: Option<&SocketAddr>,
+    ) -> Option<Self> {
+        match (api_key, addr) {
+            (Some(ApiKey(key)), _)
+                if cfg.client_api_keys.contains(key) || cfg.admin_api_keys.contains(key) =>
+            {
+                Some(ClientId::ApiKey(key.clone()))
+            }
+            (_, Some(addr)) => Some(ClientId::Ip(addr.ip())),
+            (_, None) => None,
+        }
+    }
+}
+
+impl TokenBucket {
+    fn full(burst: u32, now: Instant) -> Self {
+        Self {
+            tokens: burst as f64,
+            refilled_at: now,
+        }
+    }
+
+    fn refill(&mut self, rate: u32, burst: u32, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * rate as f64).min(burst as f64);
+        self.refilled_at = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, time::Duration};
+
+    use super::*;
+
+    fn limits() -> RateLimits {
+        RateLimits {
+            requests_per_second: 2,
+            request_burst: 2,
+            query_nodes_per_second: 10,
+            query_node_burst: 20,
+        }
+    }
+
+    fn client(key: &str) -> ClientId {
+        ClientId::ApiKey(key.to_string())
+    }
+
+    #[test]
+    fn request_budget_refills_over_time() {
+        let limiter = RateLimiter::new();
+        let limits = limits();
+        let start = Instant::now();
+
+        assert!(limiter.charge(&limits, client("a"), 1, start).is_ok());
+        assert!(limiter.charge(&limits, client("a"), 1, start).is_ok());
+        assert!(limiter.charge(&limits, client("a"), 1, start).is_err());
+
+        // Other clients have their own budgets.
+        assert!(limiter.charge(&limits, client("b"), 1, start).is_ok());
+
+        // Half a second at two requests per second buys one more request.
+        let later = start + Duration::from_millis(500);
+        assert!(limiter.charge(&limits, client("a"), 1, later).is_ok());
+        assert!(limiter.charge(&limits, client("a"), 1, later).is_err());
+    }
+
+    #[test]
+    fn unknown_api_keys_fall_back_to_ip() {
+        let cfg = ServiceConfig {
+            client_api_keys: BTreeSet::from(["partner".to_string()]),
+            admin_api_keys: BTreeSet::from(["operator".to_string()]),
+            ..Default::default()
+        };
+
+        let addr: SocketAddr = "127.0.0.1:8000".parse().unwrap();
+        let ip = ClientId::Ip(addr.ip());
+        let identify = |key: Option<&str>| {
+            let key = key.map(|k| ApiKey(k.to_string()));
+            ClientId::identify(&cfg, key.as_ref(), Some(&addr))
+        };
+
+        assert_eq!(identify(Some("partner")), Some(client("partner")));
+        assert_eq!(identify(Some("operator")), Some(client("operator")));
+        assert_eq!(identify(Some("made-up")), Some(ip.clone()));
+        assert_eq!(identify(None), Some(ip.clone()));
+        assert_eq!(
+            ClientId::identify(&cfg, Some(&ApiKey("made-up".to_string())), None),
+            None,
+        );
+
+        // Requests with made-up keys share the budget of their IP address.
+        let limiter = RateLimiter::new();
+        let limits = limits();
+        let start = Instant::now();
+        for key in ["x", "y"] {
+            let client = identify(Some(key)).unwrap();
+            assert!(limiter.charge(&limits, client, 1, start).is_ok());
+        }
+
+        let client = identify(Some("z")).unwrap();
+        assert!(limiter.charge(&limits, client, 1, start).is_err());
+        assert!(limiter
+            .charge(&limits, identify(Some("partner")).unwrap(), 1, start)
+            .is_ok());
+    }
+
+    #[test]
+    fn query_cost_budget_is_enforced() {
+        let limiter = RateLimiter::new();
+        let limits = limits();
+        let start = Instant::now();
+
+        assert!(limiter.charge(&limits, client("a"), 15, start).is_ok());
+
+        // Rejected requests are not charged against the request budget.
+        assert!(limiter.charge(&limits, client("a"), 15, start).is_err());
+        assert!(limiter.charge(&limits, client("a"), 5, start).is_ok());
+    }
+}
@@ -20,6 +20,10 @@ pub(crate) enum FunctionalGroup {
     /// Coin metadata, per-address coin and balance information.
     Coins,
 
+    /// Executing transactions without committing their effects, against the service's
+    /// database. Disabled by default.
+    DryRun,
+
     /// Querying an object's dynamic fields.
     DynamicFields,
 
@@ -47,6 +51,7 @@ impl FunctionalGroup {
         static ALL: &[FunctionalGroup] = &[
             G::Analytics,
             G::Coins,
+            G::DryRun,
             G::DynamicFields,
             G::NameService,
             G::Subscriptions,
@@ -67,6 +72,7 @@ fn functional_groups() -> &'static BTreeMap<(&'static str, &'static str), Functi
         BTreeMap::from_iter([
             (("Address", "balance"), G::Coins),
             (("Address", "balanceConnection"), G::Coins),
+            (("Address", "balanceHistory"), G::Coins),
             (("Address", "coinConnection"), G::Coins),
             (("Address", "defaultNameServiceName"), G::NameService),
             // (("Address", "nameServiceConnection"), G::NameService),
@@ -75,6 +81,7 @@ fn functional_groups() -> &'static BTreeMap<(&'static str, &'static str), Functi
             (("Epoch", "protocolConfigs"), G::SystemState),
             (("Epoch", "referenceGasPrice"), G::SystemState),
             (("Epoch", "validatorSet"), G::SystemState),
+            (("Mutation", "dryRunTransactionBlock"), G::DryRun),
             (("SuiSystemStateSummary", "protocolConfigs"), G::SystemState),
             (
                 ("SuiSystemStateSummary", "referenceGasPrice"),
@@ -106,6 +113,7 @@ fn functional_groups() -> &'static BTreeMap<(&'static str, &'static str), Functi
             (("Query", "coinMetadata"), G::Coins),
             (("Query", "moveCallMetrics"), G::Analytics),
             (("Query", "networkMetrics"), G::Analytics),
+            (("Query", "networkTotals"), G::Analytics),
             (("Query", "protocolConfig"), G::SystemState),
             (("Query", "resolveNameServiceAddress"), G::NameService),
             (("Subscription", "events"), G::Subscriptions),
@@ -129,7 +137,7 @@ mod tests {
     use async_graphql::registry::Registry;
     use async_graphql::OutputType;
 
-    use crate::types::query::Query;
+    use crate::{mutation::Mutation, types::query::Query};
 
     use super::*;
 
@@ -140,6 +148,7 @@ mod tests {
     fn test_groups_match_schema() {
         let mut registry = Registry::default();
         Query::create_type_info(&mut registry);
+        Mutation::create_type_info(&mut registry);
 
         let unimplemented = BTreeSet::from_iter([
             ("Checkpoint", "addressMetrics"),
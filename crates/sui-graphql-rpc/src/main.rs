@@ -7,7 +7,7 @@ use std::path::PathBuf;
 use clap::Parser;
 use sui_graphql_rpc::commands::Command;
 use sui_graphql_rpc::config::{ConnectionConfig, ServerConfig, ServiceConfig};
-use sui_graphql_rpc::config::{Ide, TxExecFullNodeConfig};
+use sui_graphql_rpc::config::{Ide, InternalFeatureConfig, TxExecFullNodeConfig};
 use sui_graphql_rpc::schema_sdl_export;
 use sui_graphql_rpc::server::graphiql_server::{
     start_graphiql_server, start_graphiql_server_from_cfg_path,
@@ -55,6 +55,7 @@ async fn main() {
         Command::StartServer {
             ide_title,
             db_url,
+            db_replica_urls,
             port,
             host,
             config,
@@ -62,11 +63,12 @@ async fn main() {
             prom_host,
             prom_port,
         } => {
-            let connection = ConnectionConfig::new(port, host, db_url, None, prom_host, prom_port);
+            let connection = ConnectionConfig::new(port, host, db_url, None, prom_host, prom_port)
+                .with_db_replica_urls(db_replica_urls);
             let service_config = service_config(config);
-            let _guard = telemetry_subscribers::TelemetryConfig::new()
-                .with_env()
-                .init();
+            let telemetry = telemetry_subscribers::TelemetryConfig::new().with_env();
+            let open_telemetry = telemetry.enable_otlp_tracing;
+            let _guard = telemetry.init();
 
             println!("Starting server...");
             let server_config = ServerConfig {
@@ -74,6 +76,8 @@ async fn main() {
                 service: service_config,
                 ide: Ide::new(ide_title),
                 tx_exec_full_node: TxExecFullNodeConfig::new(node_rpc_url),
+                internal_features: InternalFeatureConfig::default()
+                    .with_open_telemetry(open_telemetry),
                 ..ServerConfig::default()
             };
 
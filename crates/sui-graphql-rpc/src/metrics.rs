@@ -1,7 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use prometheus::{register_histogram_with_registry, Histogram, Registry};
+use prometheus::{
+    register_histogram_vec_with_registry, register_histogram_with_registry,
+    register_int_gauge_vec_with_registry, register_int_gauge_with_registry, Histogram,
+    HistogramVec, IntGauge, IntGaugeVec, Registry,
+};
 
 #[derive(Clone, Debug)]
 pub struct RequestMetrics {
@@ -11,6 +15,17 @@ pub struct RequestMetrics {
     pub(crate) _db_query_cost: Histogram,
 }
 
+/// Metrics on the service's use of its database connection pools.
+#[derive(Clone, Debug)]
+pub struct DbMetrics {
+    pub(crate) pool_max_size: IntGaugeVec,
+    pub(crate) pool_connections: IntGaugeVec,
+    pub(crate) pool_idle_connections: IntGaugeVec,
+    pub(crate) queries_in_flight: IntGauge,
+    pub(crate) pool_wait_latency: Histogram,
+    pub(crate) query_latency: HistogramVec,
+}
+
 // TODO: finetune buckets as we learn more about the distribution of queries
 const NUM_NODES_BUCKETS: &[f64] = &[
     1., 2., 4., 8., 12., 16., 24., 32., 48., 64., 96., 128., 256., 512., 1024.,
@@ -25,6 +40,9 @@ const QUERY_PAYLOAD_SIZE_BUCKETS: &[f64] = &[
 const DB_QUERY_COST_BUCKETS: &[f64] = &[
     1., 2., 4., 8., 12., 16., 24., 32., 48., 64., 96., 128., 256., 512., 1024.,
 ];
+const DB_LATENCY_SEC_BUCKETS: &[f64] = &[
+    0.001, 0.002, 0.005, 0.01, 0.02, 0.05, 0.1, 0.2, 0.5, 1., 2., 5., 10., 20., 40.,
+];
 
 impl RequestMetrics {
     pub fn new(registry: &Registry) -> Self {
@@ -60,3 +78,52 @@ impl RequestMetrics {
         }
     }
 }
+
+impl DbMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            pool_max_size: register_int_gauge_vec_with_registry!(
+                "db_pool_max_size",
+                "Maximum number of connections in each DB connection pool",
+                &["pool"],
+                registry,
+            )
+            .unwrap(),
+            pool_connections: register_int_gauge_vec_with_registry!(
+                "db_pool_connections",
+                "Number of connections currently open in each DB connection pool",
+                &["pool"],
+                registry,
+            )
+            .unwrap(),
+            pool_idle_connections: register_int_gauge_vec_with_registry!(
+                "db_pool_idle_connections",
+                "Number of idle connections in each DB connection pool",
+                &["pool"],
+                registry,
+            )
+            .unwrap(),
+            queries_in_flight: register_int_gauge_with_registry!(
+                "db_queries_in_flight",
+                "Number of DB queries waiting for, or holding, a connection",
+                registry,
+            )
+            .unwrap(),
+            pool_wait_latency: register_histogram_with_registry!(
+                "db_pool_wait_latency",
+                "Time spent waiting for a DB connection before running a query, in seconds",
+                DB_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            query_latency: register_histogram_vec_with_registry!(
+                "db_query_latency",
+                "Time spent running DB queries, in seconds, by query tag",
+                &["tag"],
+                DB_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
@@ -1,14 +1,21 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{error::Error, types::execution_result::ExecutionResult};
+use crate::{
+    context_data::db_data_provider::PgManager,
+    error::Error,
+    types::{dry_run_result::DryRunResult, execution_result::ExecutionResult},
+};
 use async_graphql::*;
 use fastcrypto::encoding::Encoding;
 use fastcrypto::{encoding::Base64, traits::ToFromBytes};
 use sui_json_rpc_types::SuiTransactionBlockResponseOptions;
 use sui_sdk::SuiClient;
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
-use sui_types::{signature::GenericSignature, transaction::Transaction};
+use sui_types::{
+    signature::GenericSignature,
+    transaction::{Transaction, TransactionData},
+};
 
 pub struct Mutation;
 
@@ -30,29 +37,8 @@ impl Mutation {
         tx_bytes: String,
         signatures: Vec<String>,
     ) -> Result<ExecutionResult> {
-        let sui_sdk_client: &Option<SuiClient> = ctx
-            .data()
-            .map_err(|_| Error::Internal("Unable to fetch Sui SDK client".to_string()))
-            .extend()?;
-        let sui_sdk_client = sui_sdk_client
-            .as_ref()
-            .ok_or_else(|| Error::Internal("Sui SDK client not initialized".to_string()))
-            .extend()?;
-        let tx_data = bcs::from_bytes(
-            &Base64::decode(&tx_bytes)
-                .map_err(|e| {
-                    Error::Client(format!(
-                        "Unable to deserialize transaction bytes from Base64: {e}"
-                    ))
-                })
-                .extend()?,
-        )
-        .map_err(|e| {
-            Error::Client(format!(
-                "Unable to deserialize transaction bytes as BCS: {e}"
-            ))
-        })
-        .extend()?;
+        let sui_sdk_client = sui_sdk_client(ctx).extend()?;
+        let tx_data = decode_tx_data(&tx_bytes).extend()?;
 
         let mut sigs = Vec::new();
         for sig in signatures {
@@ -94,4 +80,72 @@ impl Mutation {
             digest: result.digest.to_string(),
         })
     }
+
+    /// Execute a transaction against the latest state of the network, without committing its
+    /// effects on chain, and without requiring signatures.
+    ///
+    /// `txBytes` is a `TransactionData` struct that has been BCS-encoded
+    ///     and then Base64-encoded.
+    ///
+    /// Returns the status, gas costs, balance changes and effects the transaction would produce,
+    /// or the error (and failing command) that would cause it to fail.
+    async fn dry_run_transaction_block(
+        &self,
+        ctx: &Context<'_>,
+        tx_bytes: String,
+    ) -> Result<DryRunResult> {
+        let tx_data = decode_tx_data(&tx_bytes).extend()?;
+        let (effects, balance_changes) = ctx
+            .data_unchecked::<PgManager>()
+            .dry_run_tx(tx_data.clone())
+            .await
+            .extend()?;
+
+        Ok(DryRunResult::new(tx_data, effects, balance_changes))
+    }
+}
+
+/// The client used to talk to the fullnode that transactions are executed on. Mutations are
+/// only available if the service was configured with a fullnode to execute transactions on.
+fn sui_sdk_client<'ctx>(ctx: &Context<'ctx>) -> Result<&'ctx SuiClient, Error> {
+    let sui_sdk_client: &Option<SuiClient> = ctx
+        .data()
+        .map_err(|_| Error::Internal("Unable to fetch Sui SDK client".to_string()))?;
+    sui_sdk_client
+        .as_ref()
+        .ok_or_else(|| Error::Internal("Sui SDK client not initialized".to_string()))
+}
+
+/// Deserialize `TransactionData` from its Base64-encoded BCS representation, `tx_bytes`.
+fn decode_tx_data(tx_bytes: &str) -> Result<TransactionData, Error> {
+    let bytes = Base64::decode(tx_bytes).map_err(|e| {
+        Error::Client(format!(
+            "Unable to deserialize transaction bytes from Base64: {e}"
+        ))
+    })?;
+
+    bcs::from_bytes(&bytes).map_err(|e| {
+        Error::Client(format!(
+            "Unable to deserialize transaction bytes as BCS: {e}"
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_tx_data_client_errors() {
+        assert!(matches!(
+            decode_tx_data("not base64!"),
+            Err(Error::Client(_))
+        ));
+
+        // Valid Base64, but not a valid BCS-encoded `TransactionData`.
+        assert!(matches!(
+            decode_tx_data(&Base64::encode([0u8; 4])),
+            Err(Error::Client(_))
+        ));
+    }
 }
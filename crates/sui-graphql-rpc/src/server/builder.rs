@@ -5,25 +5,37 @@ use crate::config::{MAX_CONCURRENT_REQUESTS, RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD}
 use crate::context_data::package_cache::DbPackageStore;
 use crate::mutation::Mutation;
 use crate::{
-    config::ServerConfig,
-    context_data::db_data_provider::PgManager,
-    error::Error,
+    config::{Health, ServerConfig},
+    context_data::{
+        db_data_provider::PgManager,
+        db_replicas::{pin_replica, ReadReplicas},
+        disassembly_cache::DisassemblyCache,
+        input_resolver::InputResolver,
+        name_service::NameServiceResolver,
+    },
+    error::{code, Error},
     extensions::{
-        feature_gate::FeatureGate,
+        feature_gate::{check_disabled_fields, FeatureGate},
         logger::Logger,
+        page_size::{check_max_ownership_depths, check_max_page_sizes, PageSizeScope},
         query_limits_checker::{QueryLimitsChecker, ShowUsage},
+        rate_limiter::{ApiKey, QueryCost, RateLimiter},
         timeout::Timeout,
     },
-    metrics::RequestMetrics,
+    metrics::{DbMetrics, RequestMetrics},
     server::version::{check_version_middleware, set_version_middleware},
-    types::query::{Query, SuiGraphQLSchema},
+    types::{
+        query::{Query, SuiGraphQLSchema},
+        service_status::ServiceStatus,
+    },
 };
 use async_graphql::extensions::ApolloTracing;
 use async_graphql::extensions::Tracing;
 use async_graphql::EmptySubscription;
 use async_graphql::{extensions::ExtensionFactory, Schema, SchemaBuilder};
 use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
-use axum::http::HeaderMap;
+use axum::headers::HeaderMapExt;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use axum::routing::{post, MethodRouter, Route};
 use axum::{
@@ -158,13 +170,37 @@ impl ServerBuilder {
         let mut builder =
             ServerBuilder::new(config.connection.port, config.connection.host.clone());
 
-        let name_service_config = config.name_service.clone();
+        let name_service = NameServiceResolver::new(
+            config.name_service.clone(),
+            config.name_service_networks.clone(),
+        );
         let reader = PgManager::reader_with_config(
             config.connection.db_url.clone(),
             config.connection.db_pool_size,
         )
         .map_err(|e| Error::Internal(format!("Failed to create pg connection pool: {}", e)))?;
-        let pg_conn_pool = PgManager::new(reader.clone(), config.service.limits);
+        let replicas = config
+            .connection
+            .db_replica_urls
+            .iter()
+            .map(|db_url| {
+                PgManager::reader_with_config(db_url.clone(), config.connection.db_pool_size)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| {
+                Error::Internal(format!(
+                    "Failed to create replica pg connection pool: {}",
+                    e
+                ))
+            })?;
+        let replicas = ReadReplicas::new(replicas);
+        replicas.start_health_checks();
+        check_max_page_sizes(&config.service.max_page_sizes)?;
+        check_max_ownership_depths(&config.service.max_ownership_depths)?;
+        let pg_conn_pool = PgManager::new(reader.clone(), config.service.limits)
+            .with_replicas(replicas)
+            .with_max_page_sizes(config.service.max_page_sizes.clone())
+            .with_max_ownership_depths(config.service.max_ownership_depths.clone());
         let package_store = DbPackageStore(reader);
         let package_cache = PackageStoreWithLruCache::new(package_store);
 
@@ -200,41 +236,55 @@ impl ServerBuilder {
         let registry = registry_service.default_registry();
 
         let metrics = RequestMetrics::new(&registry);
+        let pg_conn_pool = pg_conn_pool.with_metrics(DbMetrics::new(&registry));
+
+        // The health check reads from the database directly, rather than through the schema.
+        builder = builder
+            .layer(axum::Extension(pg_conn_pool.clone()))
+            .layer(axum::Extension(config.service.health));
 
         builder = builder
             .context_data(config.service.clone())
             .context_data(pg_conn_pool)
             .context_data(Resolver::new(package_cache))
             .context_data(sui_sdk_client)
-            .context_data(name_service_config)
+            .context_data(name_service)
+            .context_data(InputResolver::new())
+            .context_data(DisassemblyCache::new())
             .context_data(Arc::new(metrics))
             .context_data(config.clone());
 
         if config.internal_features.feature_gate {
+            check_disabled_fields(&config.service.disabled_fields)?;
             builder = builder.extension(FeatureGate);
         }
+        // Overrides for individual fields need to know which field is being resolved.
+        if !config.service.max_page_sizes.is_empty()
+            || !config.service.max_ownership_depths.is_empty()
+        {
+            builder = builder.extension(PageSizeScope);
+        }
         if config.internal_features.logger {
             builder = builder.extension(Logger::default());
         }
         if config.internal_features.query_limits_checker {
             builder = builder.extension(QueryLimitsChecker::default());
         }
+        if config.internal_features.rate_limiter {
+            builder = builder.extension(RateLimiter::new());
+        }
         if config.internal_features.query_timeout {
             builder = builder.extension(Timeout);
         }
-        if config.internal_features.tracing {
+        // Resolver spans are exported over OTLP alongside database query spans by the tracing
+        // subscriber, so OpenTelemetry support relies on the same extension.
+        if config.internal_features.tracing || config.internal_features.open_telemetry {
             builder = builder.extension(Tracing);
         }
         if config.internal_features.apollo_tracing {
             builder = builder.extension(ApolloTracing);
         }
 
-        // TODO: uncomment once impl
-        // if config.internal_features.open_telemetry {
-        //     let tracer;
-        //     builder = builder.extension(OpenTelemetry::new(tracer));
-        // }
-
         Ok(builder)
     }
 }
@@ -244,40 +294,76 @@ async fn graphql_handler(
     schema: axum::Extension<SuiGraphQLSchema>,
     headers: HeaderMap,
     req: GraphQLRequest,
-) -> GraphQLResponse {
+) -> axum::response::Response {
     let mut req = req.into_inner();
     if headers.contains_key(ShowUsage::name()) {
         req.data.insert(ShowUsage)
     }
+    if let Some(api_key) = headers.typed_get::<ApiKey>() {
+        req.data.insert(api_key)
+    }
     // Capture the IP address of the client
     // Note: if a load balancer is used it must be configured to forward the client IP address
     req.data.insert(addr);
-    schema.execute(req).await.into()
+    req.data.insert(QueryCost::default());
+
+    // All of the request's reads go to the same replica, so that they are consistent.
+    let response = pin_replica(schema.execute(req)).await;
+    let rate_limited = response.errors.iter().any(|error| {
+        error.extensions.as_ref().and_then(|ext| ext.get("code"))
+            == Some(&async_graphql::Value::from(code::TOO_MANY_REQUESTS))
+    });
+
+    let response = GraphQLResponse::from(response);
+    if rate_limited {
+        (StatusCode::TOO_MANY_REQUESTS, response).into_response()
+    } else {
+        response.into_response()
+    }
 }
 
+/// Reports the same signals as `Query.serviceStatus`, responding with `503 Service Unavailable`
+/// if the service is unhealthy, so that load balancers and monitors can gate traffic on it. The
+/// status is gathered without going through the GraphQL schema (or its extensions), so that the
+/// check stays cheap, and is unaffected by limits and disabled fields. A service that was not
+/// given access to its database is reported as down.
 async fn health_checks(
-    schema: axum::Extension<SuiGraphQLSchema>,
+    pg: Option<axum::Extension<PgManager>>,
+    health: Option<axum::Extension<Health>>,
 ) -> impl axum::response::IntoResponse {
-    // Simple request to check if the DB is up
-    // TODO: add more checks
-    let req = r#"
-        query {
-            chainIdentifier
-        }
-        "#;
-    let db_up = match schema.execute(req).await.is_ok() {
-        true => "UP",
-        false => "DOWN",
+    let health = health.map(|h| h.0).unwrap_or_default();
+    let status = match &pg {
+        Some(pg) => Some(ServiceStatus::check(pg, &health).await),
+        None => None,
     };
+
+    let healthy = status.as_ref().is_some_and(|s| s.healthy);
+    let db_up = status.as_ref().is_some_and(|s| s.db_up);
     let uptime = get_or_init_server_start_time()
         .await
         .elapsed()
         .as_secs_f64();
-    format!(
-        r#"{{"status": "UP","uptime": {},"checks": {{"DB": "{}",}}}}
-        "#,
-        uptime, db_up
-    )
+
+    let body = serde_json::json!({
+        "status": if healthy { "UP" } else { "DOWN" },
+        "uptime": uptime,
+        "checks": {
+            "DB": if db_up { "UP" } else { "DOWN" },
+            "checkpoint": status
+                .as_ref()
+                .and_then(|s| s.latest_checkpoint.as_ref())
+                .map(|c| c.sequence_number),
+            "checkpointLagMs": status.as_ref().and_then(|s| s.checkpoint_lag_ms),
+        },
+    });
+
+    let code = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (code, axum::Json(body))
 }
 
 // One server per proc, so this is okay
@@ -290,7 +376,7 @@ pub mod tests {
     use super::*;
     use crate::{
         config::{ConnectionConfig, Limits, ServiceConfig},
-        context_data::db_data_provider::PgManager,
+        context_data::{db_data_provider::PgManager, pg_backend::PgQueryExecutor},
         extensions::query_limits_checker::QueryLimitsChecker,
         extensions::timeout::Timeout,
         metrics::RequestMetrics,
@@ -300,10 +386,12 @@ pub mod tests {
         extensions::{Extension, ExtensionContext, NextExecute},
         Response,
     };
+    use diesel::{QueryDsl, RunQueryDsl};
     use rand::{rngs::StdRng, SeedableRng};
     use simulacrum::Simulacrum;
     use std::sync::Arc;
     use std::time::Duration;
+    use sui_indexer::schema_v2::checkpoints;
 
     async fn prep_cluster() -> (ConnectionConfig, ExecutorCluster) {
         let rng = StdRng::from_seed([12; 32]);
@@ -324,6 +412,39 @@ pub mod tests {
         )
     }
 
+    pub async fn test_read_replica_fallback_impl() {
+        let (connection_config, _cluster) = prep_cluster().await;
+
+        // The replica accepts connections, but has none of the indexer's tables, so every query
+        // sent to it fails, and must be retried on the primary.
+        let db_url = connection_config.db_url.clone();
+        let (server_url, _) = db_url.rsplit_once('/').unwrap();
+        let replica_url = format!("{server_url}/postgres");
+
+        let primary = PgManager::reader(db_url).expect("Failed to create pg connection pool");
+        let replica = PgManager::reader(replica_url).expect("Failed to create pg connection pool");
+        let replicas = ReadReplicas::new(vec![replica]);
+        let pg_manager = PgManager::new(primary, Limits::default()).with_replicas(replicas.clone());
+
+        // Queries that are costed before they run.
+        let checkpoint = pg_manager.fetch_checkpoint(None, Some(0)).await.unwrap();
+        assert_eq!(checkpoint.map(|c| c.sequence_number), Some(0));
+
+        // Queries that are not.
+        let checkpoints = pg_manager
+            .run_query_async(|conn| {
+                checkpoints::dsl::checkpoints
+                    .count()
+                    .get_result::<i64>(conn)
+            })
+            .await
+            .unwrap();
+        assert!(checkpoints > 0);
+
+        // The replica is reachable, so it stays in rotation, despite its failures.
+        assert!(replicas.next().is_some());
+    }
+
     pub async fn test_timeout_impl() {
         let (connection_config, _cluster) = prep_cluster().await;
 
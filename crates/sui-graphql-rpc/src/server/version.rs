@@ -11,9 +11,9 @@ use axum::{
 
 use crate::error::{code, graphql_error_response};
 
-const RPC_VERSION_FULL: &str = env!("CARGO_PKG_VERSION");
-const RPC_VERSION_YEAR: &str = env!("CARGO_PKG_VERSION_MAJOR");
-const RPC_VERSION_MONTH: &str = env!("CARGO_PKG_VERSION_MINOR");
+pub(crate) const RPC_VERSION_FULL: &str = env!("CARGO_PKG_VERSION");
+pub(crate) const RPC_VERSION_YEAR: &str = env!("CARGO_PKG_VERSION_MAJOR");
+pub(crate) const RPC_VERSION_MONTH: &str = env!("CARGO_PKG_VERSION_MINOR");
 
 pub(crate) static VERSION_HEADER: HeaderName = HeaderName::from_static("x-sui-rpc-version");
 
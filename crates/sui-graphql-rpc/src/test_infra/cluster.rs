@@ -7,6 +7,7 @@ use crate::config::Limits;
 use crate::config::ServerConfig;
 use crate::config::ServiceConfig;
 use crate::server::graphiql_server::start_graphiql_server;
+use std::collections::BTreeSet;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
@@ -137,6 +138,7 @@ pub async fn start_graphql_server_with_fn_rpc(
         service: ServiceConfig {
             // Use special limits for testing
             limits: Limits::default_for_simulator_testing(),
+            disabled_features: BTreeSet::new(),
             ..ServiceConfig::default()
         },
         ..ServerConfig::default()
@@ -2,13 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use async_graphql::{connection::Connection, *};
-use sui_json_rpc::name_service::NameServiceConfig;
 
-use crate::{context_data::db_data_provider::PgManager, error::Error};
+use crate::{
+    context_data::{db_data_provider::PgManager, name_service::NameServiceResolver},
+    error::Error,
+};
 
 use super::{
-    balance::Balance,
-    coin::Coin,
+    address_activity::AddressActivity,
+    balance::{Balance, DailyBalance},
+    coin::{Coin, CoinOrder},
     dynamic_field::{DynamicField, DynamicFieldName},
     object::{Object, ObjectFilter},
     stake::StakedSui,
@@ -60,6 +63,23 @@ impl Address {
             .extend()
     }
 
+    /// The transactions this address was involved in, most recent last, each labelled with how
+    /// the address was involved: sending it, staking, or receiving coins or other objects from
+    /// another address.
+    async fn activity_connection(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Option<Connection<String, AddressActivity>>> {
+        ctx.data_unchecked::<PgManager>()
+            .fetch_address_activity(self.address, first, after, last, before)
+            .await
+            .extend()
+    }
+
     // =========== Owner interface methods =============
 
     pub async fn address(&self) -> SuiAddress {
@@ -92,6 +112,10 @@ impl Address {
             .extend()
     }
 
+    /// The balances of all coin types owned by the address. Coins of the same type are grouped
+    /// together into one Balance.
+    ///
+    /// If `atCheckpoint` is provided, the balances are as of the end of that checkpoint.
     pub async fn balance_connection(
         &self,
         ctx: &Context<'_>,
@@ -99,9 +123,28 @@ impl Address {
         after: Option<String>,
         last: Option<u64>,
         before: Option<String>,
+        at_checkpoint: Option<u64>,
     ) -> Result<Option<Connection<String, Balance>>> {
         ctx.data_unchecked::<PgManager>()
-            .fetch_balances(self.address, first, after, last, before)
+            .fetch_balances(self.address, first, after, last, before, at_checkpoint)
+            .await
+            .extend()
+    }
+
+    /// The address's balance of coin type `type` (defaults to `0x2::sui::SUI`) at the end of each
+    /// day (UTC) that checkpoints were produced on, earliest first, for charting how it changed
+    /// over time.
+    pub async fn balance_history(
+        &self,
+        ctx: &Context<'_>,
+        type_: Option<String>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Option<Connection<String, DailyBalance>>> {
+        ctx.data_unchecked::<PgManager>()
+            .fetch_balance_history(self.address, type_, first, after, last, before)
             .await
             .extend()
     }
@@ -109,6 +152,7 @@ impl Address {
     /// The coin objects for the given address.
     /// The type field is a string of the inner type of the coin
     /// by which to filter (e.g., 0x2::sui::SUI).
+    /// Coins are paginated by object ID, unless another `order` is requested.
     pub async fn coin_connection(
         &self,
         ctx: &Context<'_>,
@@ -117,9 +161,10 @@ impl Address {
         last: Option<u64>,
         before: Option<String>,
         type_: Option<String>,
+        order: Option<CoinOrder>,
     ) -> Result<Option<Connection<String, Coin>>> {
         ctx.data_unchecked::<PgManager>()
-            .fetch_coins(Some(self.address), type_, first, after, last, before)
+            .fetch_coins(Some(self.address), type_, order, first, after, last, before)
             .await
             .extend()
     }
@@ -140,8 +185,8 @@ impl Address {
     }
 
     pub async fn default_name_service_name(&self, ctx: &Context<'_>) -> Result<Option<String>> {
-        ctx.data_unchecked::<PgManager>()
-            .default_name_service_name(ctx.data_unchecked::<NameServiceConfig>(), self.address)
+        ctx.data_unchecked::<NameServiceResolver>()
+            .default_name(ctx.data_unchecked::<PgManager>(), self.address)
             .await
             .extend()
     }
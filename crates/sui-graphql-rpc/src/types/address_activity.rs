@@ -0,0 +1,68 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::*;
+
+use super::{
+    balance_change::BalanceChange, sui_address::SuiAddress, transaction_block::TransactionBlock,
+};
+use crate::{context_data::db_backend::ActivityKind, error::Error};
+
+/// A transaction that an address was involved in, and how it was involved.
+#[derive(SimpleObject, Clone)]
+pub(crate) struct AddressActivity {
+    /// How the address was involved in the transaction.
+    pub kind: AddressActivityKind,
+    pub transaction_block: TransactionBlock,
+}
+
+/// How an address was involved in a transaction.
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum AddressActivityKind {
+    /// The address sent the transaction.
+    Sent,
+    /// The address sent the transaction to add or withdraw stake.
+    Staking,
+    /// The transaction was sent by another address, and increased this address's balance.
+    ReceivedCoins,
+    /// The transaction was sent by another address, and transferred objects to this address
+    /// without increasing its balance.
+    ReceivedObjects,
+}
+
+impl AddressActivity {
+    /// Describe how `address` was involved in `transaction_block`, given the `kind` of activity it
+    /// was found by. Received transactions are split further by whether they credited the
+    /// address's balance.
+    pub(crate) fn new(
+        address: SuiAddress,
+        kind: ActivityKind,
+        transaction_block: TransactionBlock,
+    ) -> Result<Self, Error> {
+        let kind = match kind {
+            ActivityKind::Sent => AddressActivityKind::Sent,
+            ActivityKind::Staking => AddressActivityKind::Staking,
+            ActivityKind::Received => {
+                let mut received_coins = false;
+                for change in transaction_block.stored.balance_changes.iter().flatten() {
+                    let change = BalanceChange::read(change)?;
+                    if change.matches(Some(address), None) && change.is_credit() {
+                        received_coins = true;
+                        break;
+                    }
+                }
+
+                if received_coins {
+                    AddressActivityKind::ReceivedCoins
+                } else {
+                    AddressActivityKind::ReceivedObjects
+                }
+            }
+        };
+
+        Ok(Self {
+            kind,
+            transaction_block,
+        })
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::{big_int::BigInt, move_type::MoveType};
+use super::{big_int::BigInt, date_time::DateTime, move_type::MoveType};
 use async_graphql::*;
 
 #[derive(Clone, Debug, SimpleObject)]
@@ -13,3 +13,15 @@ pub(crate) struct Balance {
     /// Total balance across all coin objects of the coin type
     pub(crate) total_balance: Option<BigInt>,
 }
+
+/// An address's balance of a coin type at the end of a day (UTC).
+#[derive(Clone, Debug, SimpleObject)]
+pub(crate) struct DailyBalance {
+    /// The start of the day.
+    pub(crate) date: Option<DateTime>,
+    /// The last checkpoint of the day, as of which the balance is reported. For the current day,
+    /// this is the latest checkpoint.
+    pub(crate) checkpoint_sequence_number: u64,
+    /// Total balance across all coin objects of the coin type owned by the address.
+    pub(crate) total_balance: Option<BigInt>,
+}
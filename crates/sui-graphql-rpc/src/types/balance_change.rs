@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use async_graphql::*;
+use move_core_types::language_storage::TypeTag;
 use sui_json_rpc_types::BalanceChange as StoredBalanceChange;
 use sui_types::object::Owner as NativeOwner;
 
@@ -47,4 +48,94 @@ impl BalanceChange {
 
         Ok(Self { stored })
     }
+
+    /// Whether this change increases the balance.
+    pub(crate) fn is_credit(&self) -> bool {
+        self.stored.amount > 0
+    }
+
+    /// Whether this change is to the balance of `owner` (if provided), in coins of `coin_type` (if
+    /// provided).
+    pub(crate) fn matches(&self, owner: Option<SuiAddress>, coin_type: Option<&TypeTag>) -> bool {
+        use NativeOwner as O;
+
+        let owner_matches = match (owner, &self.stored.owner) {
+            (None, _) => true,
+            (Some(owner), O::AddressOwner(addr) | O::ObjectOwner(addr)) => {
+                owner == SuiAddress::from(*addr)
+            }
+            (Some(_), O::Shared { .. } | O::Immutable) => false,
+        };
+
+        owner_matches && coin_type.map_or(true, |tag| tag == &self.stored.coin_type)
+    }
+}
+
+impl From<StoredBalanceChange> for BalanceChange {
+    fn from(stored: StoredBalanceChange) -> Self {
+        Self { stored }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use sui_types::{
+        base_types::{SequenceNumber, SuiAddress as NativeSuiAddress},
+        gas_coin::GAS,
+    };
+
+    fn change(owner: NativeOwner, coin_type: TypeTag) -> BalanceChange {
+        BalanceChange::from(StoredBalanceChange {
+            owner,
+            coin_type,
+            amount: -100,
+        })
+    }
+
+    #[test]
+    fn test_matches_owner() {
+        let addr = NativeSuiAddress::from_str("0x1").unwrap();
+        let other = SuiAddress::from_str("0x2").unwrap();
+        let owner = Some(SuiAddress::from(addr));
+
+        let by_address = change(NativeOwner::AddressOwner(addr), GAS::type_tag());
+        assert!(by_address.matches(None, None));
+        assert!(by_address.matches(owner, None));
+        assert!(!by_address.matches(Some(other), None));
+
+        // Objects can own balances too.
+        let by_object = change(NativeOwner::ObjectOwner(addr), GAS::type_tag());
+        assert!(by_object.matches(owner, None));
+        assert!(!by_object.matches(Some(other), None));
+
+        // Shared and immutable balances have no owner to match.
+        let shared = change(
+            NativeOwner::Shared {
+                initial_shared_version: SequenceNumber::from_u64(1),
+            },
+            GAS::type_tag(),
+        );
+        assert!(shared.matches(None, None));
+        assert!(!shared.matches(owner, None));
+        assert!(!change(NativeOwner::Immutable, GAS::type_tag()).matches(owner, None));
+    }
+
+    #[test]
+    fn test_matches_coin_type() {
+        let addr = NativeSuiAddress::from_str("0x1").unwrap();
+        let owner = Some(SuiAddress::from(addr));
+        let sui = GAS::type_tag();
+        let other = TypeTag::from_str("0x42::coin::COIN").unwrap();
+
+        let sui_change = change(NativeOwner::AddressOwner(addr), sui.clone());
+        assert!(sui_change.matches(None, Some(&sui)));
+        assert!(!sui_change.matches(None, Some(&other)));
+
+        // Both filters must match.
+        assert!(sui_change.matches(owner, Some(&sui)));
+        assert!(!sui_change.matches(owner, Some(&other)));
+        assert!(!sui_change.matches(Some(SuiAddress::from_str("0x2").unwrap()), Some(&sui)));
+    }
 }
@@ -34,8 +34,15 @@ pub(crate) struct Checkpoint {
     pub timestamp: Option<DateTime>,
     /// This is an aggregation of signatures from a quorum of validators for the checkpoint proposal.
     pub validator_signature: Option<Base64>,
+    /// The positions in the epoch's committee of the validators whose signatures are aggregated
+    /// in `validatorSignature`. `null` for checkpoints indexed before signers were recorded.
+    pub validator_signers: Option<Vec<u32>>,
     /// The digest of the checkpoint at the previous sequence number.
     pub previous_checkpoint_digest: Option<String>,
+    /// A 32-byte hash of the checkpoint's contents (its transactions, their effects and their
+    /// signatures), encoded in Base58. The checkpoint's `digest` commits to this hash. `null` for
+    /// checkpoints indexed before contents digests were recorded.
+    pub contents_digest: Option<String>,
     /// This is a commitment by the committee at the end of epoch
     /// on the contents of the live object set at that time.
     /// This can be used to verify state snapshots.
@@ -65,6 +72,19 @@ impl Checkpoint {
         Ok(Some(epoch))
     }
 
+    /// The checkpoint at the previous sequence number, whose digest is
+    /// `previousCheckpointDigest`, or `null` for the genesis checkpoint.
+    async fn previous_checkpoint(&self, ctx: &Context<'_>) -> Result<Option<Checkpoint>> {
+        let Some(sequence_number) = self.sequence_number.checked_sub(1) else {
+            return Ok(None);
+        };
+
+        ctx.data_unchecked::<PgManager>()
+            .fetch_checkpoint(None, Some(sequence_number))
+            .await
+            .extend()
+    }
+
     async fn transaction_block_connection(
         &self,
         ctx: &Context<'_>,
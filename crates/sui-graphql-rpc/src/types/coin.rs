@@ -16,6 +16,21 @@ pub(crate) struct Coin {
     pub native: NativeCoin,
 }
 
+/// The order in which coins are paginated.
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub(crate) enum CoinOrder {
+    /// In ascending order of object ID.
+    #[default]
+    ObjectId,
+    /// Largest balance first, with coins of the same balance in ascending order of object ID.
+    /// Useful for coin selection, where the fewest coins that cover an amount are sought.
+    ///
+    /// Pages are not pinned to a checkpoint: cursors mark a position by (balance, object ID), so a
+    /// coin whose balance changes between requests for consecutive pages may be skipped or
+    /// returned twice.
+    BalanceDesc,
+}
+
 pub(crate) enum CoinDowncastError {
     NotACoin,
     Bcs(bcs::Error),
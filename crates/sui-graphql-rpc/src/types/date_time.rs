@@ -22,6 +22,11 @@ impl DateTime {
             .single()
             .map(Self)
     }
+
+    /// Milliseconds since the Unix epoch, the representation used for timestamps in the database.
+    pub fn timestamp_ms(&self) -> i64 {
+        self.0.timestamp_millis()
+    }
 }
 
 #[Scalar(use_type_description = true)]
@@ -0,0 +1,89 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::*;
+
+use super::date_time::DateTime;
+use crate::context_data::{db_data_provider::PgManager, db_query_stats::QueryTiming};
+
+/// Default number of queries returned by `DbDiagnostics.slowestQueries`.
+const DEFAULT_SLOWEST_QUERIES: u64 = 10;
+
+/// The state of the service's database connection pools, and the queries it has recently run
+/// against them, for diagnosing latency.
+pub(crate) struct DbDiagnostics;
+
+/// A snapshot of the connections in one of the service's database connection pools.
+#[derive(SimpleObject, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DbPool {
+    /// `primary` for the pool connected to the primary database, or `replica-N` for the pool
+    /// connected to its Nth read replica.
+    name: String,
+    /// The maximum number of connections the pool will open.
+    max_size: u64,
+    /// The number of connections currently open.
+    connections: u64,
+    /// The number of open connections that are not currently in use.
+    idle_connections: u64,
+}
+
+/// How long a recent database query took.
+#[derive(SimpleObject, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DbQuery {
+    /// Name of the function in the service that issued the query.
+    tag: String,
+    /// Time spent waiting for a connection, in milliseconds.
+    wait_ms: u64,
+    /// Time spent running the query once it had a connection, in milliseconds.
+    duration_ms: u64,
+    /// When the query finished running.
+    finished_at: Option<DateTime>,
+}
+
+#[Object]
+impl DbDiagnostics {
+    /// The service's connection pools: the primary database's first, followed by its replicas'.
+    async fn pools(&self, ctx: &Context<'_>) -> Vec<DbPool> {
+        ctx.data_unchecked::<PgManager>()
+            .pools()
+            .into_iter()
+            .map(|(name, reader)| {
+                let state = reader.pool_state();
+                DbPool {
+                    name,
+                    max_size: reader.pool_max_size() as u64,
+                    connections: state.connections as u64,
+                    idle_connections: state.idle_connections as u64,
+                }
+            })
+            .collect()
+    }
+
+    /// Number of queries currently waiting for, or holding, a connection.
+    async fn queries_in_flight(&self, ctx: &Context<'_>) -> u64 {
+        ctx.data_unchecked::<PgManager>().stats.in_flight() as u64
+    }
+
+    /// The `limit` (default 10) slowest queries out of those recently run by the service, slowest
+    /// first.
+    async fn slowest_queries(&self, ctx: &Context<'_>, limit: Option<u64>) -> Vec<DbQuery> {
+        let limit = limit.unwrap_or(DEFAULT_SLOWEST_QUERIES) as usize;
+        ctx.data_unchecked::<PgManager>()
+            .stats
+            .slowest(limit)
+            .into_iter()
+            .map(DbQuery::from)
+            .collect()
+    }
+}
+
+impl From<QueryTiming> for DbQuery {
+    fn from(timing: QueryTiming) -> Self {
+        DbQuery {
+            tag: timing.tag.to_string(),
+            wait_ms: timing.wait.as_millis() as u64,
+            duration_ms: timing.duration.as_millis() as u64,
+            finished_at: DateTime::from_ms(timing.finished_at_ms),
+        }
+    }
+}
@@ -53,10 +53,9 @@ impl std::str::FromStr for Digest {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut result = [0u8; BASE58_DIGEST_LENGTH];
-        result
-            .copy_from_slice(&Base58::decode(s).map_err(|r| Error::InvalidBase58(format!("{r}")))?);
-        Ok(Digest(result))
+        Base58::decode(s)
+            .map_err(|r| Error::InvalidBase58(format!("{r}")))?
+            .try_into()
     }
 }
 
@@ -0,0 +1,128 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::*;
+use either::Either;
+use sui_json_rpc_types::BalanceChange as NativeBalanceChange;
+use sui_types::{
+    effects::{TransactionEffects as NativeTransactionEffects, TransactionEffectsAPI},
+    execution_status::ExecutionStatus as NativeExecutionStatus,
+    transaction::TransactionData as NativeTransactionData,
+};
+
+use super::{
+    balance_change::BalanceChange,
+    gas::GasCostSummary,
+    transaction_block_effects::{ExecutionStatus, TransactionBlockEffects},
+};
+
+/// The results of executing a transaction without committing its effects on chain.
+#[derive(SimpleObject)]
+pub(crate) struct DryRunResult {
+    /// Whether the transaction would execute successfully or not.
+    pub status: ExecutionStatus,
+
+    /// The reason the transaction would fail, if it would fail.
+    pub errors: Option<String>,
+
+    /// The index (starting from 0) of the command in the transaction's programmable transaction
+    /// block that would fail, if the failure is attributable to a single command.
+    pub failed_command: Option<u64>,
+
+    /// The gas the transaction would cost to execute.
+    pub gas_summary: GasCostSummary,
+
+    /// The effect the transaction would have on the balances (sum of coin values per coin type)
+    /// of addresses and objects.
+    pub balance_changes: Vec<BalanceChange>,
+
+    /// The effects the transaction would have, including the changes it would make to objects.
+    pub effects: TransactionBlockEffects,
+}
+
+impl DryRunResult {
+    /// The result of dry running `tx_data`, which would have `effects`, and make
+    /// `balance_changes`.
+    pub(crate) fn new(
+        tx_data: NativeTransactionData,
+        effects: NativeTransactionEffects,
+        balance_changes: Vec<NativeBalanceChange>,
+    ) -> Self {
+        let (status, errors, failed_command) = match effects.status() {
+            NativeExecutionStatus::Success => (ExecutionStatus::Success, None, None),
+            NativeExecutionStatus::Failure { error, command } => (
+                ExecutionStatus::Failure,
+                Some(error.to_string()),
+                command.map(|c| c as u64),
+            ),
+        };
+
+        Self {
+            status,
+            errors,
+            failed_command,
+            gas_summary: GasCostSummary::from(effects.gas_cost_summary()),
+            balance_changes: balance_changes
+                .into_iter()
+                .map(BalanceChange::from)
+                .collect(),
+            effects: TransactionBlockEffects {
+                tx_data: Either::Right(tx_data),
+                native: effects,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_types::{
+        base_types::{random_object_ref, SuiAddress},
+        execution_status::ExecutionFailureStatus,
+        transaction::ProgrammableTransaction,
+    };
+
+    use super::*;
+
+    fn tx_data() -> NativeTransactionData {
+        let pt = ProgrammableTransaction {
+            inputs: vec![],
+            commands: vec![],
+        };
+
+        NativeTransactionData::new_programmable(
+            SuiAddress::random_for_testing_only(),
+            vec![random_object_ref()],
+            pt,
+            1_000_000,
+            1_000,
+        )
+    }
+
+    #[test]
+    fn test_dry_run_success() {
+        let result = DryRunResult::new(tx_data(), NativeTransactionEffects::default(), vec![]);
+
+        assert!(result.status == ExecutionStatus::Success);
+        assert_eq!(result.errors, None);
+        assert_eq!(result.failed_command, None);
+    }
+
+    #[test]
+    fn test_dry_run_failed_command() {
+        let mut effects = NativeTransactionEffects::default();
+        *effects.status_mut_for_testing() = NativeExecutionStatus::Failure {
+            error: ExecutionFailureStatus::InsufficientGas,
+            command: Some(2),
+        };
+
+        let result = DryRunResult::new(tx_data(), effects, vec![]);
+
+        assert!(result.status == ExecutionStatus::Failure);
+        assert_eq!(
+            result.errors,
+            Some(ExecutionFailureStatus::InsufficientGas.to_string()),
+        );
+        assert_eq!(result.failed_command, Some(2));
+    }
+}
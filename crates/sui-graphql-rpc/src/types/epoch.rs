@@ -7,6 +7,7 @@ use crate::error::Error;
 use super::big_int::BigInt;
 use super::checkpoint::Checkpoint;
 use super::date_time::DateTime;
+use super::epoch_aggregates::EpochAggregates;
 use super::protocol_config::ProtocolConfigs;
 use super::transaction_block::{TransactionBlock, TransactionBlockFilter};
 use super::validator_set::ValidatorSet;
@@ -88,6 +89,16 @@ impl Epoch {
             .map(BigInt::from)
     }
 
+    /// Totals over the epoch's checkpoints and transactions, including its transaction count
+    async fn aggregates(&self, ctx: &Context<'_>) -> Result<Option<EpochAggregates>> {
+        Ok(Some(
+            ctx.data_unchecked::<PgManager>()
+                .fetch_epoch_aggregates(&self.stored)
+                .await
+                .extend()?,
+        ))
+    }
+
     /// The amount added to total gas fees to make up the total stake rewards.
     async fn total_stake_subsidies(&self) -> Option<BigInt> {
         self.stored.stake_subsidy_amount.map(BigInt::from)
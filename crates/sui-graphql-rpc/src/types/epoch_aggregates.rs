@@ -0,0 +1,21 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::big_int::BigInt;
+use async_graphql::*;
+
+/// Totals over the checkpoints and transactions in an epoch. While the epoch is in progress, they
+/// cover the checkpoints that have been indexed so far.
+#[derive(Clone, Debug, SimpleObject)]
+pub(crate) struct EpochAggregates {
+    /// The number of transactions executed in the epoch
+    pub(crate) transaction_count: Option<BigInt>,
+    /// The gas fees (in MIST) paid in the epoch, net of storage rebates
+    pub(crate) total_gas_fees: Option<BigInt>,
+    /// The MIST rewarded as stake at the end of the epoch, only available once the epoch has ended
+    pub(crate) total_stake_rewards: Option<BigInt>,
+    /// The sequence number of the epoch's first checkpoint
+    pub(crate) first_checkpoint: Option<u64>,
+    /// The sequence number of the epoch's last checkpoint
+    pub(crate) last_checkpoint: Option<u64>,
+}
@@ -11,14 +11,14 @@ use crate::context_data::db_data_provider::PgManager;
 
 use super::{
     address::Address, base64::Base64, date_time::DateTime, move_module::MoveModule,
-    move_value::MoveValue, sui_address::SuiAddress,
+    move_value::MoveValue, sui_address::SuiAddress, type_filter::TypeMatch,
 };
 
 pub(crate) struct Event {
     pub stored: StoredEvent,
 }
 
-#[derive(InputObject, Clone)]
+#[derive(InputObject, Default, Clone)]
 pub(crate) struct EventFilter {
     pub sender: Option<SuiAddress>,
     pub transaction_digest: Option<String>,
@@ -32,6 +32,12 @@ pub(crate) struct EventFilter {
     /// Modules can be filtered by their package, or package::module.
     pub emitting_module: Option<String>,
 
+    /// Events emitted by modules in any version of a package, identified by
+    /// the package's original ID (the ID of its first version). Unlike
+    /// `emittingModule`, this also matches events emitted by upgrades of the
+    /// package, which are published at different addresses.
+    pub emitting_package_lineage: Option<SuiAddress>,
+
     /// This field is used to specify the type of event emitted.
     ///
     /// Events can be filtered by their type's package, package::module,
@@ -40,7 +46,12 @@ pub(crate) struct EventFilter {
     /// Generic types can be queried by either the generic type name, e.g.
     /// `0x2::coin::Coin`, or by the full type name, such as
     /// `0x2::coin::Coin<0x2::sui::SUI>`.
-    pub event_type: Option<String>,
+    ///
+    /// A (non-empty) list of at most 10 types matches events of any of those types.
+    pub event_type: Option<Vec<String>>,
+
+    /// How `eventType` is matched against events' types. Defaults to `PREFIX`.
+    pub event_type_match: Option<TypeMatch>,
     // Enhancement (post-MVP)
     // pub start_time
     // pub end_time
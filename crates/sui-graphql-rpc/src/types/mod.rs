@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod address;
+pub(crate) mod address_activity;
 pub(crate) mod available_range;
 pub(crate) mod balance;
 pub(crate) mod balance_change;
@@ -11,12 +12,16 @@ pub(crate) mod checkpoint;
 pub(crate) mod coin;
 pub(crate) mod coin_metadata;
 pub(crate) mod committee_member;
+pub(crate) mod cursor;
 pub(crate) mod date_time;
+pub(crate) mod db_diagnostics;
 pub(crate) mod digest;
 pub(crate) mod display;
+pub(crate) mod dry_run_result;
 pub(crate) mod dynamic_field;
 pub(crate) mod end_of_epoch_data;
 pub(crate) mod epoch;
+pub(crate) mod epoch_aggregates;
 pub(crate) mod event;
 pub(crate) mod execution_result;
 pub(crate) mod gas;
@@ -29,6 +34,7 @@ pub(crate) mod move_struct;
 pub(crate) mod move_type;
 pub(crate) mod move_value;
 pub(crate) mod name_service;
+pub(crate) mod network_totals;
 pub(crate) mod object;
 pub(crate) mod object_change;
 pub(crate) mod object_read;
@@ -37,6 +43,7 @@ pub(crate) mod owner;
 pub(crate) mod protocol_config;
 pub(crate) mod query;
 pub(crate) mod safe_mode;
+pub(crate) mod service_status;
 pub(crate) mod stake;
 pub(crate) mod stake_subsidy;
 pub(crate) mod storage_fund;
@@ -46,6 +53,7 @@ pub(crate) mod system_parameters;
 pub(crate) mod transaction_block;
 pub(crate) mod transaction_block_effects;
 pub(crate) mod transaction_block_kind;
+pub(crate) mod type_filter;
 pub(crate) mod unchanged_shared_object;
 pub(crate) mod validator;
 pub(crate) mod validator_credentials;
@@ -10,6 +10,7 @@ use move_ir_types::location::Loc;
 
 use crate::config::ServiceConfig;
 use crate::context_data::db_data_provider::{validate_cursor_pagination, PgManager};
+use crate::context_data::disassembly_cache::DisassemblyCache;
 use crate::error::Error;
 use sui_package_resolver::Module as ParsedMoveModule;
 
@@ -20,6 +21,8 @@ use super::{base64::Base64, move_package::MovePackage, sui_address::SuiAddress};
 #[derive(Clone)]
 pub(crate) struct MoveModule {
     pub storage_id: SuiAddress,
+    /// Version of the package this module was read from.
+    pub package_version: u64,
     pub native: Vec<u8>,
     pub parsed: ParsedMoveModule,
 }
@@ -314,16 +317,29 @@ impl MoveModule {
     }
 
     /// Textual representation of the module's bytecode.
-    async fn disassembly(&self) -> Result<Option<String>> {
-        let view = BinaryIndexedView::Module(self.parsed.bytecode());
-        Ok(Some(
+    async fn disassembly(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let disassemble = || {
+            let view = BinaryIndexedView::Module(self.parsed.bytecode());
             Disassembler::from_view(view, Loc::invalid())
-                .map_err(|e| Error::Internal(format!("Error creating disassembler: {e}")))
-                .extend()?
+                .map_err(|e| Error::Internal(format!("Error creating disassembler: {e}")))?
                 .disassemble()
                 .map_err(|e| Error::Internal(format!("Error creating disassembly: {e}")))
-                .extend()?,
-        ))
+        };
+
+        let Some(cache) = ctx.data_opt::<DisassemblyCache>() else {
+            return Ok(Some(disassemble().extend()?));
+        };
+
+        let disassembly = cache
+            .get_or_disassemble(
+                self.storage_id,
+                self.package_version,
+                self.parsed.name(),
+                disassemble,
+            )
+            .extend()?;
+
+        Ok(Some(disassembly.to_string()))
     }
 }
 
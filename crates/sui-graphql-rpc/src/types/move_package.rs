@@ -109,6 +109,7 @@ impl MovePackage {
                 name.clone(),
                 MoveModule {
                     storage_id: self.super_.address,
+                    package_version: self.native.version().value(),
                     native: native.clone(),
                     parsed: parsed.clone(),
                 },
@@ -203,6 +204,7 @@ impl MovePackage {
         ) {
             (Some(native), Ok(parsed)) => Ok(Some(MoveModule {
                 storage_id: self.super_.address,
+                package_version: self.native.version().value(),
                 native: native.clone(),
                 parsed: parsed.clone(),
             })),
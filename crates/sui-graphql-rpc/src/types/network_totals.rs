@@ -0,0 +1,51 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::*;
+
+use super::big_int::BigInt;
+use crate::context_data::db_data_provider::PgManager;
+
+/// Default number of checkpoints that `NetworkTotals.tps` is measured over.
+const DEFAULT_TPS_WINDOW: u64 = 100;
+
+/// Network-wide totals, as of the latest checkpoint the service has indexed. These are read from
+/// running totals recorded on checkpoints, rather than by counting rows, so they are cheap to
+/// query.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct NetworkTotals;
+
+#[Object]
+impl NetworkTotals {
+    /// The number of transactions executed by the network since genesis.
+    async fn total_transactions(&self, ctx: &Context<'_>) -> Result<Option<BigInt>> {
+        let latest = ctx
+            .data_unchecked::<PgManager>()
+            .fetch_latest_checkpoint()
+            .await
+            .extend()?;
+
+        Ok(latest.network_total_transactions.map(BigInt::from))
+    }
+
+    /// The number of checkpoints produced by the network since genesis.
+    async fn total_checkpoints(&self, ctx: &Context<'_>) -> Result<Option<BigInt>> {
+        let latest = ctx
+            .data_unchecked::<PgManager>()
+            .fetch_latest_checkpoint()
+            .await
+            .extend()?;
+
+        Ok(Some(BigInt::from(latest.sequence_number + 1)))
+    }
+
+    /// Transactions per second, over the `windowCheckpoints` (default 100) most recent checkpoints
+    /// in the current epoch, or over all of the epoch's checkpoints if it has fewer than that. Null
+    /// if the epoch has only one checkpoint so far, so there is no interval to measure over.
+    async fn tps(&self, ctx: &Context<'_>, window_checkpoints: Option<u64>) -> Result<Option<f64>> {
+        ctx.data_unchecked::<PgManager>()
+            .fetch_tps(window_checkpoints.unwrap_or(DEFAULT_TPS_WINDOW))
+            .await
+            .extend()
+    }
+}
@@ -3,8 +3,7 @@
 
 use async_graphql::{connection::Connection, *};
 use fastcrypto::encoding::{Base58, Encoding};
-use sui_indexer::models_v2::objects::StoredObject;
-use sui_json_rpc::name_service::NameServiceConfig;
+use sui_indexer::models_v2::objects::{StoredHistoryObject, StoredObject};
 use sui_types::dynamic_field::DynamicFieldType;
 
 use super::big_int::BigInt;
@@ -12,12 +11,19 @@ use super::dynamic_field::{DynamicField, DynamicFieldName};
 use super::move_object::MoveObject;
 use super::move_package::MovePackage;
 use super::{
-    balance::Balance, coin::Coin, owner::Owner, stake::StakedSui, sui_address::SuiAddress,
+    balance::Balance,
+    coin::{Coin, CoinOrder},
+    owner::Owner,
+    stake::StakedSui,
+    sui_address::SuiAddress,
     transaction_block::TransactionBlock,
+    type_filter::TypeMatch,
 };
 use crate::context_data::db_data_provider::PgManager;
+use crate::context_data::name_service::NameServiceResolver;
 use crate::error::Error;
 use crate::types::base64::Base64;
+use sui_indexer::types_v2::OwnerType;
 use sui_types::object::{Object as NativeObject, Owner as NativeOwner};
 
 #[derive(Clone, Debug)]
@@ -49,11 +55,28 @@ pub(crate) struct ObjectFilter {
     ///
     /// Generic types can be queried by either the generic type name, e.g. `0x2::coin::Coin`, or by
     /// the full type name, such as `0x2::coin::Coin<0x2::sui::SUI>`.
-    pub type_: Option<String>,
+    ///
+    /// A (non-empty) list of at most 10 types matches objects of any of those types.
+    pub type_: Option<Vec<String>>,
+
+    /// How `type` is matched against objects' types. Defaults to `PREFIX`.
+    pub type_match: Option<TypeMatch>,
 
     /// Filter for live objects by their current owners.
     pub owner: Option<SuiAddress>,
 
+    /// Filter for live objects by how they are owned. Shared and immutable objects do not have
+    /// owners, so filtering for `SHARED` or `IMMUTABLE` objects cannot be combined with `owner`,
+    /// and must be combined with `type` or `objectIds` instead.
+    pub kind: Option<ObjectKind>,
+
+    /// When filtering by `owner`, also include objects owned by the owner's objects, and by their
+    /// objects in turn, up to the service's `maxOwnershipDepth` levels of nesting (e.g. the
+    /// contents of kiosks and bags). `kind` only applies to objects owned directly by `owner`.
+    /// At most 1000 objects are expanded, so owners with more objects than that (directly or
+    /// nested) may not have all of them included. Requires `owner`. Defaults to `false`.
+    pub include_nested: Option<bool>,
+
     /// Filter for live objects by their IDs.
     pub object_ids: Option<Vec<SuiAddress>>,
 
@@ -67,6 +90,16 @@ pub(crate) struct ObjectKey {
     version: u64,
 }
 
+/// Identifies a specific version of an object, along with the digest of its contents at that
+/// version.
+#[derive(InputObject, Clone)]
+pub(crate) struct ObjectRef {
+    pub address: SuiAddress,
+    pub version: u64,
+    /// 32-byte hash of the object's contents at `version`, encoded as a Base58 string.
+    pub digest: String,
+}
+
 #[Object]
 impl Object {
     async fn version(&self) -> u64 {
@@ -153,6 +186,23 @@ impl Object {
         MovePackage::try_from(self).ok()
     }
 
+    /// The versions of this object, in ascending order of version, for rendering its history.
+    /// Versions at which the object was wrapped or deleted are skipped, and the connection only
+    /// reaches back as far as the history retained by the service.
+    async fn version_connection(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+    ) -> Result<Option<Connection<String, Object>>> {
+        ctx.data_unchecked::<PgManager>()
+            .fetch_obj_versions(self.address, first, after, last, before)
+            .await
+            .extend()
+    }
+
     // =========== Owner interface methods =============
 
     /// The address of the object, named as such to avoid conflict with the address type.
@@ -189,6 +239,8 @@ impl Object {
     }
 
     /// The balances of all coin types owned by the object. Coins of the same type are grouped together into one Balance.
+    ///
+    /// If `atCheckpoint` is provided, the balances are as of the end of that checkpoint.
     pub async fn balance_connection(
         &self,
         ctx: &Context<'_>,
@@ -196,9 +248,10 @@ impl Object {
         after: Option<String>,
         last: Option<u64>,
         before: Option<String>,
+        at_checkpoint: Option<u64>,
     ) -> Result<Option<Connection<String, Balance>>> {
         ctx.data_unchecked::<PgManager>()
-            .fetch_balances(self.address, first, after, last, before)
+            .fetch_balances(self.address, first, after, last, before, at_checkpoint)
             .await
             .extend()
     }
@@ -206,7 +259,8 @@ impl Object {
     /// The coin objects for the given address.
     ///
     /// The type field is a string of the inner type of the coin by which to filter
-    /// (e.g. `0x2::sui::SUI`). If no type is provided, it will default to `0x2::sui::SUI`.
+    /// (e.g. `0x2::sui::SUI`). If no type is provided, it will default to `0x2::sui::SUI`. Coins are
+    /// paginated by object ID, unless another `order` is requested.
     pub async fn coin_connection(
         &self,
         ctx: &Context<'_>,
@@ -215,9 +269,10 @@ impl Object {
         last: Option<u64>,
         before: Option<String>,
         type_: Option<String>,
+        order: Option<CoinOrder>,
     ) -> Result<Option<Connection<String, Coin>>> {
         ctx.data_unchecked::<PgManager>()
-            .fetch_coins(Some(self.address), type_, first, after, last, before)
+            .fetch_coins(Some(self.address), type_, order, first, after, last, before)
             .await
             .extend()
     }
@@ -239,8 +294,8 @@ impl Object {
 
     /// The domain that a user address has explicitly configured as their default domain
     pub async fn default_name_service_name(&self, ctx: &Context<'_>) -> Result<Option<String>> {
-        ctx.data_unchecked::<PgManager>()
-            .default_name_service_name(ctx.data_unchecked::<NameServiceConfig>(), self.address)
+        ctx.data_unchecked::<NameServiceResolver>()
+            .default_name(ctx.data_unchecked::<PgManager>(), self.address)
             .await
             .extend()
     }
@@ -316,6 +371,19 @@ impl Object {
     }
 }
 
+impl From<ObjectKind> for OwnerType {
+    fn from(kind: ObjectKind) -> Self {
+        use ObjectKind as K;
+        use OwnerType as O;
+        match kind {
+            K::Owned => O::Address,
+            K::Child => O::Object,
+            K::Shared => O::Shared,
+            K::Immutable => O::Immutable,
+        }
+    }
+}
+
 impl TryFrom<StoredObject> for Object {
     type Error = Error;
 
@@ -332,6 +400,29 @@ impl TryFrom<StoredObject> for Object {
     }
 }
 
+impl TryFrom<StoredHistoryObject> for Object {
+    type Error = Error;
+
+    fn try_from(history_object: StoredHistoryObject) -> Result<Self, Error> {
+        let address = addr(&history_object.object_id)?;
+        let Some(serialized_object) = &history_object.serialized_object else {
+            return Err(Error::Internal(format!(
+                "Object {address} has no contents at version {}",
+                history_object.object_version,
+            )));
+        };
+
+        let native_object = bcs::from_bytes(serialized_object)
+            .map_err(|_| Error::Internal(format!("Failed to deserialize object {address}")))?;
+
+        Ok(Self {
+            address,
+            stored: None,
+            native: native_object,
+        })
+    }
+}
+
 /// Parse a `SuiAddress` from its stored representation.  Failure is an internal error: the
 /// database should never contain a malformed address (containing the wrong number of bytes).
 fn addr(bytes: impl AsRef<[u8]>) -> Result<SuiAddress, Error> {
@@ -6,6 +6,7 @@ use super::dynamic_field::DynamicField;
 use super::dynamic_field::DynamicFieldName;
 use super::stake::StakedSui;
 use crate::context_data::db_data_provider::PgManager;
+use crate::context_data::name_service::NameServiceResolver;
 use crate::types::balance::*;
 use crate::types::coin::*;
 use crate::types::object::*;
@@ -13,7 +14,6 @@ use crate::types::sui_address::SuiAddress;
 
 use async_graphql::connection::Connection;
 use async_graphql::*;
-use sui_json_rpc::name_service::NameServiceConfig;
 use sui_types::dynamic_field::DynamicFieldType;
 
 #[derive(Interface)]
@@ -39,7 +39,8 @@ use sui_types::dynamic_field::DynamicFieldType;
         arg(name = "first", ty = "Option<u64>"),
         arg(name = "after", ty = "Option<String>"),
         arg(name = "last", ty = "Option<u64>"),
-        arg(name = "before", ty = "Option<String>")
+        arg(name = "before", ty = "Option<String>"),
+        arg(name = "at_checkpoint", ty = "Option<u64>")
     ),
     field(
         name = "coin_connection",
@@ -48,7 +49,8 @@ use sui_types::dynamic_field::DynamicFieldType;
         arg(name = "after", ty = "Option<String>"),
         arg(name = "last", ty = "Option<u64>"),
         arg(name = "before", ty = "Option<String>"),
-        arg(name = "type", ty = "Option<String>")
+        arg(name = "type", ty = "Option<String>"),
+        arg(name = "order", ty = "Option<CoinOrder>")
     ),
     field(
         name = "staked_sui_connection",
@@ -146,6 +148,10 @@ impl Owner {
             .extend()
     }
 
+    /// The balances of all coin types owned by the address or object. Coins of the same type are grouped
+    /// together into one Balance.
+    ///
+    /// If `atCheckpoint` is provided, the balances are as of the end of that checkpoint.
     pub async fn balance_connection(
         &self,
         ctx: &Context<'_>,
@@ -153,9 +159,10 @@ impl Owner {
         after: Option<String>,
         last: Option<u64>,
         before: Option<String>,
+        at_checkpoint: Option<u64>,
     ) -> Result<Option<Connection<String, Balance>>> {
         ctx.data_unchecked::<PgManager>()
-            .fetch_balances(self.address, first, after, last, before)
+            .fetch_balances(self.address, first, after, last, before, at_checkpoint)
             .await
             .extend()
     }
@@ -163,7 +170,8 @@ impl Owner {
     /// The coin objects for the given address or object.
     ///
     /// The type field is a string of the inner type of the coin by which to filter
-    /// (e.g. `0x2::sui::SUI`). If no type is provided, it will default to `0x2::sui::SUI`.
+    /// (e.g. `0x2::sui::SUI`). If no type is provided, it will default to `0x2::sui::SUI`. Coins are
+    /// paginated by object ID, unless another `order` is requested.
     pub async fn coin_connection(
         &self,
         ctx: &Context<'_>,
@@ -172,9 +180,10 @@ impl Owner {
         last: Option<u64>,
         before: Option<String>,
         type_: Option<String>,
+        order: Option<CoinOrder>,
     ) -> Result<Option<Connection<String, Coin>>> {
         ctx.data_unchecked::<PgManager>()
-            .fetch_coins(Some(self.address), type_, first, after, last, before)
+            .fetch_coins(Some(self.address), type_, order, first, after, last, before)
             .await
             .extend()
     }
@@ -195,8 +204,8 @@ impl Owner {
     }
 
     pub async fn default_name_service_name(&self, ctx: &Context<'_>) -> Result<Option<String>> {
-        ctx.data_unchecked::<PgManager>()
-            .default_name_service_name(ctx.data_unchecked::<NameServiceConfig>(), self.address)
+        ctx.data_unchecked::<NameServiceResolver>()
+            .default_name(ctx.data_unchecked::<PgManager>(), self.address)
             .await
             .extend()
     }
@@ -4,27 +4,35 @@
 use std::str::FromStr;
 
 use async_graphql::{connection::Connection, *};
-use sui_json_rpc::name_service::NameServiceConfig;
 use sui_types::TypeTag;
 
 use super::{
     address::Address,
     available_range::AvailableRange,
     checkpoint::{Checkpoint, CheckpointId},
-    coin::Coin,
+    coin::{Coin, CoinOrder},
     coin_metadata::CoinMetadata,
+    db_diagnostics::DbDiagnostics,
     epoch::Epoch,
     event::{Event, EventFilter},
     move_type::MoveType,
-    object::{Object, ObjectFilter},
+    network_totals::NetworkTotals,
+    object::{Object, ObjectFilter, ObjectRef},
     owner::{ObjectOwner, Owner},
     protocol_config::ProtocolConfigs,
+    service_status::ServiceStatus,
     sui_address::SuiAddress,
     sui_system_state_summary::SuiSystemStateSummary,
     transaction_block::{TransactionBlock, TransactionBlockFilter},
 };
 use crate::{
-    config::ServiceConfig, context_data::db_data_provider::PgManager, error::Error,
+    config::ServiceConfig,
+    context_data::{
+        db_data_provider::{DbValidationError, PgManager},
+        name_service::NameServiceResolver,
+    },
+    error::Error,
+    extensions::rate_limiter::ApiKey,
     mutation::Mutation,
 };
 
@@ -56,6 +64,36 @@ impl Query {
             .extend()
     }
 
+    /// The health of this RPC service, and the freshness of the data it serves.
+    async fn service_status(&self, ctx: &Context<'_>) -> ServiceStatus {
+        let health = ctx
+            .data_opt::<ServiceConfig>()
+            .map(|config| config.health)
+            .unwrap_or_default();
+
+        ServiceStatus::check(ctx.data_unchecked::<PgManager>(), &health).await
+    }
+
+    /// Network-wide totals, as of the latest checkpoint.
+    async fn network_totals(&self) -> NetworkTotals {
+        NetworkTotals
+    }
+
+    /// Diagnostics on the service's database connection pools and recent queries. Only available
+    /// to requests that supply one of the service's admin API keys.
+    async fn db_diagnostics(&self, ctx: &Context<'_>) -> Result<DbDiagnostics> {
+        let is_admin = match (ctx.data_opt::<ServiceConfig>(), ctx.data_opt::<ApiKey>()) {
+            (Some(config), Some(ApiKey(key))) => config.admin_api_keys.contains(key),
+            _ => false,
+        };
+
+        if !is_admin {
+            return Err(Error::AdminOnly.extend());
+        }
+
+        Ok(DbDiagnostics)
+    }
+
     // availableRange - pending impl. on IndexerV2
     // dryRunTransactionBlock
     // coinMetadata
@@ -76,6 +114,26 @@ impl Query {
             .extend()
     }
 
+    /// Fetch several objects in one request, either at their latest versions, by their IDs, or at
+    /// specific versions, by their object refs (but not both). Each entry in the result
+    /// corresponds to the ID or ref at the same position in the input, and is null if that object
+    /// does not exist (at that version with that digest, or currently, when fetched by ID). At most
+    /// `maxMultiGetSize` objects can be fetched at once.
+    async fn multi_get_objects(
+        &self,
+        ctx: &Context<'_>,
+        ids: Option<Vec<SuiAddress>>,
+        refs: Option<Vec<ObjectRef>>,
+    ) -> Result<Vec<Option<Object>>> {
+        let pg_manager = ctx.data_unchecked::<PgManager>();
+        match (ids, refs) {
+            (Some(ids), None) => pg_manager.fetch_objs_by_ids(ids).await,
+            (None, Some(refs)) => pg_manager.fetch_objs_by_refs(refs).await,
+            _ => Err(DbValidationError::MultiGetObjectKeys.into()),
+        }
+        .extend()
+    }
+
     async fn address(&self, address: SuiAddress) -> Option<Address> {
         Some(Address { address })
     }
@@ -145,10 +203,26 @@ impl Query {
             .extend()
     }
 
+    /// Fetch several transaction blocks by their digests, in one request. Each entry in the result
+    /// corresponds to the digest at the same position in `digests`, and is null if no transaction
+    /// block with that digest exists. At most `maxMultiGetSize` transaction blocks can be fetched
+    /// at once.
+    async fn multi_get_transaction_blocks(
+        &self,
+        ctx: &Context<'_>,
+        digests: Vec<String>,
+    ) -> Result<Vec<Option<TransactionBlock>>> {
+        ctx.data_unchecked::<PgManager>()
+            .fetch_txs_by_digests(digests)
+            .await
+            .extend()
+    }
+
     /// The coin objects that exist in the network.
     ///
     /// The type field is a string of the inner type of the coin by which to filter
-    /// (e.g. `0x2::sui::SUI`). If no type is provided, it will default to `0x2::sui::SUI`.
+    /// (e.g. `0x2::sui::SUI`). If no type is provided, it will default to `0x2::sui::SUI`. Coins are
+    /// paginated by object ID, unless another `order` is requested.
     async fn coin_connection(
         &self,
         ctx: &Context<'_>,
@@ -157,9 +231,10 @@ impl Query {
         last: Option<u64>,
         before: Option<String>,
         type_: Option<String>,
+        order: Option<CoinOrder>,
     ) -> Result<Option<Connection<String, Coin>>> {
         ctx.data_unchecked::<PgManager>()
-            .fetch_coins(None, type_, first, after, last, before)
+            .fetch_coins(None, type_, order, first, after, last, before)
             .await
             .extend()
     }
@@ -242,8 +317,8 @@ impl Query {
         ctx: &Context<'_>,
         name: String,
     ) -> Result<Option<Address>> {
-        ctx.data_unchecked::<PgManager>()
-            .resolve_name_service_address(ctx.data_unchecked::<NameServiceConfig>(), name)
+        ctx.data_unchecked::<NameServiceResolver>()
+            .resolve_address(ctx.data_unchecked::<PgManager>(), name)
             .await
             .extend()
     }
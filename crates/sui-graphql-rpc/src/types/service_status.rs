@@ -0,0 +1,107 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_graphql::*;
+
+use super::checkpoint::Checkpoint;
+use crate::{
+    config::Health,
+    context_data::db_data_provider::PgManager,
+    server::version::{RPC_VERSION_FULL, RPC_VERSION_MONTH, RPC_VERSION_YEAR},
+};
+
+/// The health of the service, and the freshness of the data it serves. The service's `/health`
+/// route reports on the same signals.
+#[derive(SimpleObject, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ServiceStatus {
+    /// Whether the service is healthy: It can read from its database, and the latest checkpoint it
+    /// has indexed is not too far behind the wall clock.
+    pub healthy: bool,
+
+    /// The latest checkpoint the service has indexed.
+    pub latest_checkpoint: Option<Checkpoint>,
+
+    /// How far (in milliseconds) the timestamp of the latest indexed checkpoint is behind the
+    /// service's wall clock.
+    pub checkpoint_lag_ms: Option<u64>,
+
+    /// Whether the service was able to read from its database.
+    pub db_up: bool,
+
+    /// The number of connections the service's database connection pools can hand out without
+    /// waiting: connections that are idle, plus connections that have yet to be opened.
+    pub db_available_connections: u64,
+
+    /// The precise version of the RPC software (including the patch version).
+    pub rpc_version: String,
+
+    /// The version of the schema that the service serves, as `<YEAR>.<MONTH>`. Requests can pin
+    /// this version with the `x-sui-rpc-version` header.
+    pub schema_version: String,
+}
+
+impl ServiceStatus {
+    /// Gather the status of the service from its database, judging its health by the thresholds
+    /// in `health`.
+    pub(crate) async fn check(pg: &PgManager, health: &Health) -> Self {
+        let latest_checkpoint = pg.fetch_checkpoint(None, None).await;
+        let db_up = latest_checkpoint.is_ok();
+        let latest_checkpoint = latest_checkpoint.ok().flatten();
+
+        let checkpoint_lag_ms = latest_checkpoint
+            .as_ref()
+            .and_then(|checkpoint| checkpoint.timestamp.as_ref())
+            .map(|timestamp| {
+                let now_ms = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |now| now.as_millis() as i64);
+                now_ms.saturating_sub(timestamp.timestamp_ms()).max(0) as u64
+            });
+
+        let db_available_connections = pg
+            .pools()
+            .into_iter()
+            .map(|(_, reader)| {
+                let state = reader.pool_state();
+                let unopened = reader.pool_max_size().saturating_sub(state.connections);
+                (state.idle_connections + unopened) as u64
+            })
+            .sum();
+
+        Self {
+            healthy: is_healthy(db_up, checkpoint_lag_ms, health),
+            latest_checkpoint,
+            checkpoint_lag_ms,
+            db_up,
+            db_available_connections,
+            rpc_version: RPC_VERSION_FULL.to_string(),
+            schema_version: format!("{RPC_VERSION_YEAR}.{RPC_VERSION_MONTH}"),
+        }
+    }
+}
+
+/// A service is healthy if it can read from its database, and has indexed a checkpoint recently
+/// enough. A service that has not indexed any checkpoints yet has no data to serve.
+fn is_healthy(db_up: bool, checkpoint_lag_ms: Option<u64>, health: &Health) -> bool {
+    db_up && checkpoint_lag_ms.is_some_and(|lag| lag <= health.max_checkpoint_lag_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_healthy() {
+        let health = Health {
+            max_checkpoint_lag_ms: 1_000,
+        };
+
+        assert!(is_healthy(true, Some(0), &health));
+        assert!(is_healthy(true, Some(1_000), &health));
+        assert!(!is_healthy(true, Some(1_001), &health));
+        assert!(!is_healthy(true, None, &health));
+        assert!(!is_healthy(false, Some(0), &health));
+    }
+}
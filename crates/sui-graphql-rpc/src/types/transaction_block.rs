@@ -13,8 +13,8 @@ use sui_types::{
 use crate::{context_data::db_data_provider::PgManager, error::Error};
 
 use super::{
-    address::Address, base64::Base64, epoch::Epoch, gas::GasInput, sui_address::SuiAddress,
-    transaction_block_effects::TransactionBlockEffects,
+    address::Address, base64::Base64, date_time::DateTime, epoch::Epoch, gas::GasInput,
+    sui_address::SuiAddress, transaction_block_effects::TransactionBlockEffects,
     transaction_block_kind::TransactionBlockKind,
 };
 
@@ -44,6 +44,8 @@ pub(crate) struct TransactionBlockFilter {
     pub after_checkpoint: Option<u64>,
     pub at_checkpoint: Option<u64>,
     pub before_checkpoint: Option<u64>,
+    pub after_time: Option<DateTime>,
+    pub before_time: Option<DateTime>,
 
     pub sign_address: Option<SuiAddress>,
     pub sent_address: Option<SuiAddress>,
@@ -5,6 +5,7 @@ use crate::{context_data::db_data_provider::PgManager, error::Error};
 use async_graphql::*;
 use either::Either;
 use sui_indexer::models_v2::transactions::StoredTransaction;
+use sui_json_rpc::coin_api::parse_to_type_tag;
 use sui_types::{
     effects::{TransactionEffects as NativeTransactionEffects, TransactionEffectsAPI},
     execution_status::ExecutionStatus as NativeExecutionStatus,
@@ -13,7 +14,7 @@ use sui_types::{
 
 use super::{
     balance_change::BalanceChange, base64::Base64, checkpoint::Checkpoint, date_time::DateTime,
-    epoch::Epoch, gas::GasEffects, object_change::ObjectChange,
+    epoch::Epoch, gas::GasEffects, object_change::ObjectChange, sui_address::SuiAddress,
     transaction_block::TransactionBlock, unchanged_shared_object::UnchangedSharedObject,
 };
 
@@ -122,15 +123,29 @@ impl TransactionBlockEffects {
     }
 
     /// The effect this transaction had on the balances (sum of coin values per coin type) of
-    /// addresses and objects.
-    async fn balance_changes(&self) -> Result<Option<Vec<BalanceChange>>> {
+    /// addresses and objects, optionally limited to the balances of `owner`, and to coins of
+    /// `coinType` (e.g. `0x2::sui::SUI`).
+    async fn balance_changes(
+        &self,
+        owner: Option<SuiAddress>,
+        coin_type: Option<String>,
+    ) -> Result<Option<Vec<BalanceChange>>> {
         let Some(stored_tx) = self.tx_data.as_ref().left() else {
             return Ok(None);
         };
 
+        let coin_type = coin_type
+            .map(|c| parse_to_type_tag(Some(c)))
+            .transpose()
+            .map_err(|e| Error::InvalidCoinType(e.to_string()))
+            .extend()?;
+
         let mut changes = Vec::with_capacity(stored_tx.balance_changes.len());
         for change in stored_tx.balance_changes.iter().flatten() {
-            changes.push(BalanceChange::read(change).extend()?);
+            let change = BalanceChange::read(change).extend()?;
+            if change.matches(owner, coin_type.as_ref()) {
+                changes.push(change);
+            }
         }
 
         Ok(Some(changes))
@@ -5,6 +5,8 @@ use async_graphql::{
     connection::{Connection, Edge},
     *,
 };
+use move_core_types::language_storage::TypeTag;
+use sui_package_resolver::Resolver;
 use sui_types::transaction::{
     Argument as NativeArgument, CallArg as NativeCallArg, Command as NativeProgrammableTransaction,
     ObjectArg as NativeObjectArg, ProgrammableMoveCall as NativeMoveCallTransaction,
@@ -12,11 +14,15 @@ use sui_types::transaction::{
 };
 
 use crate::{
-    context_data::db_data_provider::{validate_cursor_pagination, PgManager},
-    error::Error,
+    context_data::{
+        db_data_provider::{validate_cursor_pagination, PgManager},
+        input_resolver::InputResolver,
+        package_cache::PackageCache,
+    },
+    error::{code, Error},
     types::{
-        base64::Base64, move_function::MoveFunction, move_type::MoveType, object_read::ObjectRead,
-        sui_address::SuiAddress,
+        base64::Base64, move_function::MoveFunction, move_type::MoveType, move_value::MoveValue,
+        object_read::ObjectRead, sui_address::SuiAddress,
     },
 };
 
@@ -59,11 +65,12 @@ struct Receiving {
     read: ObjectRead,
 }
 
-/// BCS encoded primitive value (not an object or Move struct).
-#[derive(SimpleObject, Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq)]
 struct Pure {
-    /// BCS serialized and Base64 encoded primitive value.
     bytes: Base64,
+    /// The type of the value, if it could be inferred from how the input is used, or the reason
+    /// inferring it failed.
+    type_: Result<Option<TypeTag>, String>,
 }
 
 /// A single transaction, or command, in the programmable transaction block.
@@ -193,6 +200,7 @@ impl ProgrammableTransactionBlock {
     /// Input objects or primitive values.
     async fn input_connection(
         &self,
+        ctx: &Context<'_>,
         first: Option<u64>,
         after: Option<String>,
         last: Option<u64>,
@@ -247,8 +255,26 @@ impl ProgrammableTransactionBlock {
         connection.has_previous_page = 0 < lo;
         connection.has_next_page = hi < total;
 
-        for (idx, input) in self.0.inputs.iter().enumerate().skip(lo).take(hi - lo) {
-            let input = TransactionInput::from(input.clone());
+        let resolver: &Resolver<PackageCache> = ctx
+            .data()
+            .map_err(|_| Error::Internal("Unable to fetch Package Cache.".to_string()))
+            .extend()?;
+
+        let types = ctx
+            .data_unchecked::<InputResolver>()
+            .pure_input_types(resolver, &self.0)
+            .await;
+
+        for (idx, (input, type_)) in self
+            .0
+            .inputs
+            .iter()
+            .zip(types)
+            .enumerate()
+            .skip(lo)
+            .take(hi - lo)
+        {
+            let input = TransactionInput::new(input.clone(), type_);
             connection.edges.push(Edge::new(idx.to_string(), input));
         }
 
@@ -370,8 +396,33 @@ impl MoveCallTransaction {
     }
 }
 
-impl From<NativeCallArg> for TransactionInput {
-    fn from(argument: NativeCallArg) -> Self {
+/// BCS encoded primitive value (not an object or Move struct).
+#[Object]
+impl Pure {
+    /// BCS serialized and Base64 encoded primitive value.
+    async fn bytes(&self) -> &Base64 {
+        &self.bytes
+    }
+
+    /// The value, decoded according to the type it is used at in the transaction's commands: As
+    /// an argument to a Move function, a `SplitCoins` amount, a `TransferObjects` recipient, or an
+    /// element of a `MakeMoveVec` with an explicit type. `null` if the input is unused, or only
+    /// used in positions that do not determine its type, or if the signature of the function it
+    /// is passed to could not be read (in which case the field also reports an error).
+    async fn value(&self) -> Result<Option<MoveValue>> {
+        match &self.type_ {
+            Ok(type_) => Ok(type_
+                .clone()
+                .map(|type_| MoveValue::new(type_, self.bytes.clone()))),
+            Err(message) => Err(async_graphql::Error::new(message.clone())
+                .extend_with(|_, e| e.set("code", code::INTERNAL_SERVER_ERROR))),
+        }
+    }
+}
+
+impl TransactionInput {
+    /// Convert a native input, using `type_` (if available) to decode pure values.
+    fn new(argument: NativeCallArg, type_: Result<Option<TypeTag>, String>) -> Self {
         use NativeCallArg as N;
         use NativeObjectArg as O;
         use TransactionInput as I;
@@ -379,6 +430,7 @@ impl From<NativeCallArg> for TransactionInput {
         match argument {
             N::Pure(bytes) => I::Pure(Pure {
                 bytes: Base64::from(bytes),
+                type_,
             }),
 
             N::Object(O::ImmOrOwnedObject(oref)) => I::OwnedOrImmutable(OwnedOrImmutable {
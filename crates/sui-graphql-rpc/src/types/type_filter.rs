@@ -0,0 +1,270 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use async_graphql::*;
+use move_core_types::language_storage::StructTag;
+use sui_types::parse_sui_struct_tag;
+
+use super::sui_address::SuiAddress;
+use crate::context_data::db_data_provider::DbValidationError;
+
+/// The maximum number of types that a single filter can match against.
+pub(crate) const MAX_TYPE_FILTERS: usize = 10;
+
+const TYPE_FORMAT: &str = "package[::module[::type[<type_params>]]]";
+const MODULE_FORMAT: &str = "package[::module]";
+
+/// How a type filter is matched against types.
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub(crate) enum TypeMatch {
+    /// A package or module filter matches every type defined in that package or module, and a
+    /// type filter without type parameters matches every instantiation of that type.
+    #[default]
+    Prefix,
+    /// The filter must be a fully qualified type, and only matches that exact type. A generic
+    /// type without type parameters (e.g. `0x2::coin::Coin`) does not match its instantiations.
+    Exact,
+}
+
+/// A filter on Move types, parsed from its string representation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum TypeFilter {
+    /// A primitive type, identified by its name (e.g. `u64`).
+    Primitive(String),
+    Package(SuiAddress),
+    Module(SuiAddress, String),
+    Type(StructTag),
+}
+
+/// A filter on Move modules, parsed from its string representation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum ModuleFilter {
+    Package(SuiAddress),
+    Module(SuiAddress, String),
+}
+
+/// The pattern a type's canonical string representation must match to pass a `TypeFilter`.
+/// Backends are responsible for translating these into predicates over their representation of
+/// types.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum TypePattern {
+    /// The type's representation is exactly this string.
+    Exact(String),
+    /// The type's representation starts with this string.
+    Prefix(String),
+    /// The type's representation is this string, or it is an instantiation of this (generic) type.
+    Generic(String),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TypeFilterError {
+    #[error("Invalid format in '{0}' - if '::' is present, there must be a non-empty string on both sides. Expected format like '{1}'")]
+    MissingComponents(String, &'static str),
+    #[error("Invalid format in '{0}' - value must have {1} or fewer components. Expected format like '{2}'")]
+    TooManyComponents(String, u64, &'static str),
+    #[error("Invalid filter '{0}' - exact matches require a fully qualified type")]
+    ExactRequiresType(String),
+}
+
+impl TypeFilter {
+    /// Parse a filter of the form `package[::module[::type[<type_params>]]]`. A single component
+    /// that is not an address (does not start with `0x`) is treated as a primitive type.
+    pub(crate) fn parse(filter: &str) -> Result<Self, DbValidationError> {
+        let parts: Vec<_> = filter.splitn(3, "::").collect();
+        if parts.iter().any(|part| part.is_empty()) {
+            return Err(invalid(TypeFilterError::MissingComponents(
+                filter.to_string(),
+                TYPE_FORMAT,
+            )));
+        }
+
+        Ok(match parts[..] {
+            [primitive] if !primitive.starts_with("0x") => Self::Primitive(primitive.to_string()),
+            [package] => Self::Package(parse_package(package)?),
+            [package, module] => Self::Module(parse_package(package)?, module.to_string()),
+            _ => Self::Type(
+                parse_sui_struct_tag(filter)
+                    .map_err(|e| DbValidationError::InvalidType(e.to_string()))?,
+            ),
+        })
+    }
+
+    /// Parse a list of filters, of which a type needs to pass at least one. Lists of more than
+    /// `MAX_TYPE_FILTERS` filters are rejected, to bound the size of the resulting query, as are
+    /// empty lists, which no type could pass.
+    pub(crate) fn parse_any(filters: &[String]) -> Result<Vec<Self>, DbValidationError> {
+        if filters.is_empty() {
+            return Err(DbValidationError::NoTypes);
+        }
+
+        if filters.len() > MAX_TYPE_FILTERS {
+            return Err(DbValidationError::TooManyTypes(
+                filters.len(),
+                MAX_TYPE_FILTERS,
+            ));
+        }
+
+        filters.iter().map(|filter| Self::parse(filter)).collect()
+    }
+
+    /// The pattern that types need to match to pass this filter, under `type_match`.
+    pub(crate) fn pattern(&self, type_match: TypeMatch) -> Result<TypePattern, DbValidationError> {
+        use TypeFilter as F;
+        use TypeMatch as M;
+        use TypePattern as P;
+
+        Ok(match (self, type_match) {
+            (F::Primitive(primitive), _) => P::Exact(primitive.clone()),
+
+            (F::Package(_) | F::Module(_, _), M::Exact) => {
+                return Err(invalid(TypeFilterError::ExactRequiresType(
+                    self.to_string(),
+                )));
+            }
+
+            (F::Package(package), M::Prefix) => P::Prefix(format!("{package}::")),
+            (F::Module(package, module), M::Prefix) => P::Prefix(format!("{package}::{module}::")),
+
+            (F::Type(tag), M::Prefix) if tag.type_params.is_empty() => {
+                P::Generic(tag.to_canonical_string(/* with_prefix */ true))
+            }
+
+            (F::Type(tag), _) => P::Exact(tag.to_canonical_string(/* with_prefix */ true)),
+        })
+    }
+}
+
+impl ModuleFilter {
+    /// Parse a filter of the form `package[::module]`.
+    pub(crate) fn parse(filter: &str) -> Result<Self, DbValidationError> {
+        let parts: Vec<_> = filter.splitn(3, "::").collect();
+        if parts.iter().any(|part| part.is_empty()) {
+            return Err(invalid(TypeFilterError::MissingComponents(
+                filter.to_string(),
+                MODULE_FORMAT,
+            )));
+        }
+
+        Ok(match parts[..] {
+            [package] => Self::Package(parse_package(package)?),
+            [package, module] => Self::Module(parse_package(package)?, module.to_string()),
+            _ => {
+                return Err(invalid(TypeFilterError::TooManyComponents(
+                    filter.to_string(),
+                    2,
+                    MODULE_FORMAT,
+                )))
+            }
+        })
+    }
+}
+
+impl std::fmt::Display for TypeFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeFilter::Primitive(primitive) => write!(f, "{primitive}"),
+            TypeFilter::Package(package) => write!(f, "{package}"),
+            TypeFilter::Module(package, module) => write!(f, "{package}::{module}"),
+            TypeFilter::Type(tag) => {
+                write!(f, "{}", tag.to_canonical_display(/* with_prefix */ true))
+            }
+        }
+    }
+}
+
+fn parse_package(package: &str) -> Result<SuiAddress, DbValidationError> {
+    SuiAddress::from_str(package).map_err(|e| DbValidationError::InvalidType(e.to_string()))
+}
+
+fn invalid(error: TypeFilterError) -> DbValidationError {
+    DbValidationError::InvalidType(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUI: &str = "0x0000000000000000000000000000000000000000000000000000000000000002";
+
+    #[test]
+    fn parse_and_match_prefix() {
+        use TypePattern as P;
+
+        let pattern = |filter| {
+            TypeFilter::parse(filter)
+                .unwrap()
+                .pattern(TypeMatch::Prefix)
+                .unwrap()
+        };
+
+        assert_eq!(pattern("u64"), P::Exact("u64".to_string()));
+        assert_eq!(pattern("0x2"), P::Prefix(format!("{SUI}::")));
+        assert_eq!(pattern("0x2::coin"), P::Prefix(format!("{SUI}::coin::")));
+        assert_eq!(
+            pattern("0x2::coin::Coin"),
+            P::Generic(format!("{SUI}::coin::Coin")),
+        );
+        assert_eq!(
+            pattern("0x2::coin::Coin<0x2::sui::SUI>"),
+            P::Exact(format!("{SUI}::coin::Coin<{SUI}::sui::SUI>")),
+        );
+    }
+
+    #[test]
+    fn exact_match_requires_type() {
+        let exact = |filter| TypeFilter::parse(filter).unwrap().pattern(TypeMatch::Exact);
+
+        assert_eq!(
+            exact("0x2::coin::Coin").unwrap(),
+            TypePattern::Exact(format!("{SUI}::coin::Coin")),
+        );
+        assert!(exact("0x2").is_err());
+        assert!(exact("0x2::coin").is_err());
+    }
+
+    #[test]
+    fn parse_any_bounds_filters() {
+        let filters = |n| vec!["0x2::coin::Coin".to_string(); n];
+
+        assert!(matches!(
+            TypeFilter::parse_any(&filters(0)),
+            Err(DbValidationError::NoTypes),
+        ));
+        assert_eq!(TypeFilter::parse_any(&filters(1)).unwrap().len(), 1);
+        assert_eq!(
+            TypeFilter::parse_any(&filters(MAX_TYPE_FILTERS))
+                .unwrap()
+                .len(),
+            MAX_TYPE_FILTERS,
+        );
+        assert!(matches!(
+            TypeFilter::parse_any(&filters(MAX_TYPE_FILTERS + 1)),
+            Err(DbValidationError::TooManyTypes(_, MAX_TYPE_FILTERS)),
+        ));
+    }
+
+    #[test]
+    fn parse_invalid_type_filters() {
+        assert!(TypeFilter::parse("0x2::").is_err());
+        assert!(TypeFilter::parse("::coin").is_err());
+        assert!(TypeFilter::parse("0x2::coin::").is_err());
+        assert!(TypeFilter::parse("0x2::coin::Coin::extra").is_err());
+    }
+
+    #[test]
+    fn parse_module_filters() {
+        let sui = SuiAddress::from_str(SUI).unwrap();
+        assert_eq!(
+            ModuleFilter::parse("0x2").unwrap(),
+            ModuleFilter::Package(sui)
+        );
+        assert_eq!(
+            ModuleFilter::parse("0x2::coin").unwrap(),
+            ModuleFilter::Module(sui, "coin".to_string()),
+        );
+        assert!(ModuleFilter::parse("0x2::coin::Coin").is_err());
+        assert!(ModuleFilter::parse("0x2::").is_err());
+    }
+}
@@ -10,11 +10,14 @@ mod tests {
     use simulacrum::Simulacrum;
     use std::sync::Arc;
     use std::time::Duration;
+    use sui_graphql_rpc::client::response::GraphqlResponse;
     use sui_graphql_rpc::client::simple_client::GraphqlQueryVariable;
     use sui_graphql_rpc::client::ClientError;
     use sui_graphql_rpc::config::ConnectionConfig;
     use sui_graphql_rpc::test_infra::cluster::DEFAULT_INTERNAL_DATA_SOURCE_PORT;
-    use sui_types::digests::ChainIdentifier;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+    use sui_types::digests::{ChainIdentifier, TransactionDigest};
+    use sui_types::effects::TransactionEffectsAPI;
     use sui_types::DEEPBOOK_ADDRESS;
     use sui_types::SUI_FRAMEWORK_ADDRESS;
     use tokio::time::sleep;
@@ -293,6 +296,1356 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_balance_history() {
+        const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+        let rng = StdRng::from_seed([12; 32]);
+        let mut sim = Simulacrum::new_with_rng(rng);
+        let address = SuiAddress::from(ObjectID::from_single_byte(0x42));
+
+        // Day 0 (which genesis is also on): The address is sent its first coin.
+        sim.request_gas(address, 1_000).unwrap();
+        sim.create_checkpoint();
+
+        // Day 1: The address is sent another coin.
+        sim.advance_clock(DAY);
+        sim.request_gas(address, 2_000).unwrap();
+        sim.create_checkpoint();
+
+        // Day 2: The address's balance is unchanged.
+        sim.advance_clock(DAY);
+        sim.create_checkpoint();
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster = sui_graphql_rpc::test_infra::cluster::serve_executor(
+            connection_config,
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+        )
+        .await;
+
+        cluster
+            .wait_for_checkpoint_catchup(3, Duration::from_secs(10))
+            .await;
+
+        let query = r#"
+            {
+                address(address: $addr) {
+                    balanceHistory(first: 2, after: $after) {
+                        pageInfo { hasNextPage endCursor }
+                        nodes { checkpointSequenceNumber totalBalance }
+                    }
+                }
+            }
+        "#;
+
+        let page = |after: Option<String>| {
+            let variables = vec![
+                GraphqlQueryVariable {
+                    name: "addr".to_string(),
+                    ty: "SuiAddress!".to_string(),
+                    value: json!(address.to_string()),
+                },
+                GraphqlQueryVariable {
+                    name: "after".to_string(),
+                    ty: "String".to_string(),
+                    value: json!(after),
+                },
+            ];
+
+            let client = &cluster.graphql_client;
+            async move {
+                let res = client
+                    .execute_to_graphql(query.to_string(), true, variables, vec![])
+                    .await
+                    .unwrap();
+
+                assert!(res.errors().is_empty(), "{:?}", res.errors());
+                let data = res.response_body().data.clone().into_json().unwrap();
+                data["address"]["balanceHistory"].clone()
+            }
+        };
+
+        let first = page(None).await;
+        assert_eq!(
+            first["nodes"],
+            json!([
+                { "checkpointSequenceNumber": 1, "totalBalance": "1000" },
+                { "checkpointSequenceNumber": 2, "totalBalance": "3000" },
+            ]),
+        );
+        assert_eq!(first["pageInfo"]["hasNextPage"], json!(true));
+
+        let cursor = first["pageInfo"]["endCursor"].as_str().unwrap().to_string();
+        let second = page(Some(cursor)).await;
+        assert_eq!(
+            second["nodes"],
+            json!([{ "checkpointSequenceNumber": 3, "totalBalance": "3000" }]),
+        );
+        assert_eq!(second["pageInfo"]["hasNextPage"], json!(false));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_balances_at_checkpoint() {
+        let rng = StdRng::from_seed([12; 32]);
+        let mut sim = Simulacrum::new_with_rng(rng);
+        let address = SuiAddress::from(ObjectID::from_single_byte(0x42));
+
+        // Checkpoint 1: The address is sent its first coin.
+        sim.request_gas(address, 1_000).unwrap();
+        sim.create_checkpoint();
+
+        // Checkpoint 2: The address is sent another coin.
+        sim.request_gas(address, 2_000).unwrap();
+        sim.create_checkpoint();
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster = sui_graphql_rpc::test_infra::cluster::serve_executor(
+            connection_config,
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+        )
+        .await;
+
+        cluster
+            .wait_for_checkpoint_catchup(2, Duration::from_secs(10))
+            .await;
+
+        let query = r#"
+            {
+                address(address: $addr) {
+                    balanceConnection(atCheckpoint: $cp) {
+                        nodes { coinObjectCount totalBalance }
+                    }
+                }
+            }
+        "#;
+
+        let balances = |checkpoint: Option<u64>| {
+            let variables = vec![
+                GraphqlQueryVariable {
+                    name: "addr".to_string(),
+                    ty: "SuiAddress!".to_string(),
+                    value: json!(address.to_string()),
+                },
+                GraphqlQueryVariable {
+                    name: "cp".to_string(),
+                    ty: "Int".to_string(),
+                    value: json!(checkpoint),
+                },
+            ];
+
+            let client = &cluster.graphql_client;
+            async move {
+                client
+                    .execute_to_graphql(query.to_string(), true, variables, vec![])
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let nodes = |res: GraphqlResponse| {
+            assert!(res.errors().is_empty(), "{:?}", res.errors());
+            let data = res.response_body().data.clone().into_json().unwrap();
+            data["address"]["balanceConnection"]["nodes"].clone()
+        };
+
+        // Before the address received any coins, it has no balances.
+        assert_eq!(nodes(balances(Some(0)).await), json!([]));
+
+        assert_eq!(
+            nodes(balances(Some(1)).await),
+            json!([{ "coinObjectCount": 1, "totalBalance": "1000" }]),
+        );
+
+        // The latest checkpoint agrees with the live balance.
+        let latest = json!([{ "coinObjectCount": 2, "totalBalance": "3000" }]);
+        assert_eq!(nodes(balances(Some(2)).await), latest);
+        assert_eq!(nodes(balances(None).await), latest);
+
+        // Checkpoints that have not been indexed yet are rejected.
+        let res = balances(Some(3)).await;
+        assert_eq!(res.errors().len(), 1, "{:?}", res.errors());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_epoch_aggregates() {
+        let rng = StdRng::from_seed([12; 32]);
+        let mut sim = Simulacrum::new_with_rng(rng);
+        let address = SuiAddress::from(ObjectID::from_single_byte(0x42));
+
+        // Epoch 0: Two transactions in checkpoint 1, before the epoch ends in checkpoint 2.
+        for amount in 1..=2 {
+            sim.request_gas(address, amount * 1_000).unwrap();
+        }
+        sim.create_checkpoint();
+        sim.advance_epoch(false);
+
+        // Epoch 1 (in progress): Three transactions in checkpoint 3.
+        for amount in 1..=3 {
+            sim.request_gas(address, amount * 1_000).unwrap();
+        }
+        sim.create_checkpoint();
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster = sui_graphql_rpc::test_infra::cluster::serve_executor(
+            connection_config,
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+        )
+        .await;
+
+        cluster
+            .wait_for_checkpoint_catchup(3, Duration::from_secs(10))
+            .await;
+
+        let query = r#"
+            {
+                ended: epoch(id: 0) {
+                    aggregates {
+                        transactionCount
+                        totalGasFees
+                        totalStakeRewards
+                        firstCheckpoint
+                        lastCheckpoint
+                    }
+                }
+                current: epoch(id: 1) {
+                    aggregates {
+                        transactionCount
+                        totalGasFees
+                        totalStakeRewards
+                        firstCheckpoint
+                        lastCheckpoint
+                    }
+                }
+                checkpoint(id: { sequenceNumber: 2 }) { networkTotalTransactions }
+            }
+        "#;
+
+        let res = cluster
+            .graphql_client
+            .execute_to_graphql(query.to_string(), true, vec![], vec![])
+            .await
+            .unwrap();
+
+        assert!(res.errors().is_empty(), "{:?}", res.errors());
+        let data = res.response_body().data.clone().into_json().unwrap();
+
+        // The ended epoch spans genesis to its end-of-epoch checkpoint, so it holds every
+        // transaction up to that point.
+        let ended = &data["ended"]["aggregates"];
+        assert_eq!(ended["firstCheckpoint"], json!(0));
+        assert_eq!(ended["lastCheckpoint"], json!(2));
+        let total = data["checkpoint"]["networkTotalTransactions"]
+            .as_u64()
+            .unwrap();
+        assert_eq!(ended["transactionCount"], json!(total.to_string()));
+        assert!(ended["totalGasFees"].is_string(), "{ended}");
+
+        // The current epoch only covers the checkpoints indexed so far, and has not distributed
+        // any stake rewards yet.
+        let current = &data["current"]["aggregates"];
+        assert_eq!(current["firstCheckpoint"], json!(3));
+        assert_eq!(current["lastCheckpoint"], json!(3));
+        assert_eq!(current["transactionCount"], json!("3"));
+        assert!(current["totalGasFees"].is_string(), "{current}");
+        assert_eq!(current["totalStakeRewards"], json!(null));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_address_activity_pagination() {
+        let rng = StdRng::from_seed([12; 32]);
+        let mut sim = Simulacrum::new_with_rng(rng);
+        let address = SuiAddress::from(ObjectID::from_single_byte(0x42));
+
+        // The address receives coins in five separate transactions.
+        for amount in 1..=5 {
+            sim.request_gas(address, amount * 1_000).unwrap();
+        }
+        sim.create_checkpoint();
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster = sui_graphql_rpc::test_infra::cluster::serve_executor(
+            connection_config,
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+        )
+        .await;
+
+        cluster
+            .wait_for_checkpoint_catchup(1, Duration::from_secs(10))
+            .await;
+
+        let query = r#"
+            {
+                address(address: $addr) {
+                    activityConnection(first: $first, after: $after, last: $last, before: $before) {
+                        pageInfo { hasPreviousPage hasNextPage startCursor endCursor }
+                        nodes { kind transactionBlock { digest } }
+                    }
+                }
+            }
+        "#;
+
+        let page = |first: Option<u64>,
+                    after: Option<String>,
+                    last: Option<u64>,
+                    before: Option<String>| {
+            let variables = vec![
+                GraphqlQueryVariable {
+                    name: "addr".to_string(),
+                    ty: "SuiAddress!".to_string(),
+                    value: json!(address.to_string()),
+                },
+                GraphqlQueryVariable {
+                    name: "first".to_string(),
+                    ty: "Int".to_string(),
+                    value: json!(first),
+                },
+                GraphqlQueryVariable {
+                    name: "after".to_string(),
+                    ty: "String".to_string(),
+                    value: json!(after),
+                },
+                GraphqlQueryVariable {
+                    name: "last".to_string(),
+                    ty: "Int".to_string(),
+                    value: json!(last),
+                },
+                GraphqlQueryVariable {
+                    name: "before".to_string(),
+                    ty: "String".to_string(),
+                    value: json!(before),
+                },
+            ];
+
+            let client = &cluster.graphql_client;
+            async move {
+                let res = client
+                    .execute_to_graphql(query.to_string(), true, variables, vec![])
+                    .await
+                    .unwrap();
+
+                assert!(res.errors().is_empty(), "{:?}", res.errors());
+                let data = res.response_body().data.clone().into_json().unwrap();
+                data["address"]["activityConnection"].clone()
+            }
+        };
+
+        let nodes = |page: &serde_json::Value| page["nodes"].as_array().unwrap().clone();
+        let cursor_of = |page: &serde_json::Value, field: &str| {
+            page["pageInfo"][field].as_str().map(String::from)
+        };
+
+        let all = nodes(&page(Some(50), None, None, None).await);
+        assert_eq!(all.len(), 5);
+        assert!(all.iter().all(|n| n["kind"] == json!("RECEIVED_COINS")));
+
+        // Paging forwards visits all the activity, in order.
+        let mut forwards = vec![];
+        let mut after = None;
+        loop {
+            let next = page(Some(2), after, None, None).await;
+            forwards.extend(nodes(&next));
+            if next["pageInfo"]["hasNextPage"] != json!(true) {
+                break;
+            }
+            after = cursor_of(&next, "endCursor");
+        }
+        assert_eq!(forwards, all);
+
+        // The last page without a cursor holds the most recent activity.
+        let tail = page(None, None, Some(2), None).await;
+        assert_eq!(nodes(&tail), all[3..].to_vec());
+        assert_eq!(tail["pageInfo"]["hasPreviousPage"], json!(true));
+
+        // Paging backwards from it visits the rest, in order.
+        let mut backwards = nodes(&tail);
+        let mut before = cursor_of(&tail, "startCursor");
+        while let Some(cursor) = before {
+            let prev = page(None, None, Some(2), Some(cursor)).await;
+            let mut prev_nodes = nodes(&prev);
+            prev_nodes.extend(backwards);
+            backwards = prev_nodes;
+            before = (prev["pageInfo"]["hasPreviousPage"] == json!(true))
+                .then(|| cursor_of(&prev, "startCursor"))
+                .flatten();
+        }
+        assert_eq!(backwards, all);
+
+        // Bounding a page on both sides only returns the activity in between.
+        let ends = page(Some(1), None, None, None).await;
+        let middle = page(
+            Some(50),
+            cursor_of(&ends, "endCursor"),
+            None,
+            cursor_of(&tail, "startCursor"),
+        )
+        .await;
+        assert_eq!(nodes(&middle), all[1..3].to_vec());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_coins_by_balance_pagination() {
+        let rng = StdRng::from_seed([12; 32]);
+        let mut sim = Simulacrum::new_with_rng(rng);
+        let address = SuiAddress::from(ObjectID::from_single_byte(0x42));
+
+        // Two of the coins share a balance, so their order is decided by object ID.
+        for amount in [3_000, 1_000, 5_000, 1_000, 4_000] {
+            sim.request_gas(address, amount).unwrap();
+        }
+        sim.create_checkpoint();
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster = sui_graphql_rpc::test_infra::cluster::serve_executor(
+            connection_config,
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+        )
+        .await;
+
+        cluster
+            .wait_for_checkpoint_catchup(1, Duration::from_secs(10))
+            .await;
+
+        let query = r#"
+            {
+                address(address: $addr) {
+                    coinConnection(
+                        first: $first,
+                        after: $after,
+                        last: $last,
+                        before: $before,
+                        type: "0x2::sui::SUI",
+                        order: BALANCE_DESC,
+                    ) {
+                        pageInfo { hasPreviousPage hasNextPage startCursor endCursor }
+                        nodes { balance asMoveObject { asObject { address } } }
+                    }
+                }
+            }
+        "#;
+
+        let page = |first: Option<u64>,
+                    after: Option<String>,
+                    last: Option<u64>,
+                    before: Option<String>| {
+            let variables = vec![
+                GraphqlQueryVariable {
+                    name: "addr".to_string(),
+                    ty: "SuiAddress!".to_string(),
+                    value: json!(address.to_string()),
+                },
+                GraphqlQueryVariable {
+                    name: "first".to_string(),
+                    ty: "Int".to_string(),
+                    value: json!(first),
+                },
+                GraphqlQueryVariable {
+                    name: "after".to_string(),
+                    ty: "String".to_string(),
+                    value: json!(after),
+                },
+                GraphqlQueryVariable {
+                    name: "last".to_string(),
+                    ty: "Int".to_string(),
+                    value: json!(last),
+                },
+                GraphqlQueryVariable {
+                    name: "before".to_string(),
+                    ty: "String".to_string(),
+                    value: json!(before),
+                },
+            ];
+
+            let client = &cluster.graphql_client;
+            async move {
+                let res = client
+                    .execute_to_graphql(query.to_string(), true, variables, vec![])
+                    .await
+                    .unwrap();
+
+                assert!(res.errors().is_empty(), "{:?}", res.errors());
+                let data = res.response_body().data.clone().into_json().unwrap();
+                data["address"]["coinConnection"].clone()
+            }
+        };
+
+        let nodes = |page: &serde_json::Value| page["nodes"].as_array().unwrap().clone();
+        let cursor_of = |page: &serde_json::Value, field: &str| {
+            page["pageInfo"][field].as_str().map(String::from)
+        };
+
+        let all = nodes(&page(Some(50), None, None, None).await);
+        let balances: Vec<_> = all.iter().map(|n| n["balance"].clone()).collect();
+        assert_eq!(
+            balances,
+            vec![
+                json!("5000"),
+                json!("4000"),
+                json!("3000"),
+                json!("1000"),
+                json!("1000"),
+            ],
+        );
+
+        let id_of = |node: &serde_json::Value| {
+            node["asMoveObject"]["asObject"]["address"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+        assert!(id_of(&all[3]) < id_of(&all[4]));
+
+        // Paging forwards visits every coin once, largest first.
+        let mut forwards = vec![];
+        let mut after = None;
+        loop {
+            let next = page(Some(2), after, None, None).await;
+            forwards.extend(nodes(&next));
+            if next["pageInfo"]["hasNextPage"] != json!(true) {
+                break;
+            }
+            after = cursor_of(&next, "endCursor");
+        }
+        assert_eq!(forwards, all);
+
+        // Paging backwards from the smallest coins visits them all, in the same order.
+        let mut backwards = vec![];
+        let mut before = None;
+        loop {
+            let prev = page(None, None, Some(2), before).await;
+            let mut prev_nodes = nodes(&prev);
+            prev_nodes.extend(backwards);
+            backwards = prev_nodes;
+            if prev["pageInfo"]["hasPreviousPage"] != json!(true) {
+                break;
+            }
+            before = cursor_of(&prev, "startCursor");
+        }
+        assert_eq!(backwards, all);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_transaction_blocks_by_time() {
+        let rng = StdRng::from_seed([12; 32]);
+        let mut sim = Simulacrum::new_with_rng(rng);
+        let address = SuiAddress::from(ObjectID::from_single_byte(0x42));
+
+        // Checkpoints 1 to 3 each hold transactions, and are a second apart.
+        for amount in 1..=3 {
+            sim.advance_clock(Duration::from_secs(1));
+            sim.request_gas(address, amount * 1_000).unwrap();
+            sim.create_checkpoint();
+        }
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster = sui_graphql_rpc::test_infra::cluster::serve_executor(
+            connection_config,
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+        )
+        .await;
+
+        cluster
+            .wait_for_checkpoint_catchup(3, Duration::from_secs(10))
+            .await;
+
+        let query = |query: &str, name: &str, ty: &str, value: serde_json::Value| {
+            let variables = vec![GraphqlQueryVariable {
+                name: name.to_string(),
+                ty: ty.to_string(),
+                value,
+            }];
+
+            let query = query.to_string();
+            let client = &cluster.graphql_client;
+            async move {
+                let res = client
+                    .execute_to_graphql(query, true, variables, vec![])
+                    .await
+                    .unwrap();
+
+                assert!(res.errors().is_empty(), "{:?}", res.errors());
+                res.response_body().data.clone().into_json().unwrap()
+            }
+        };
+
+        let timestamp = |seq: u64| {
+            let data = query(
+                "{ checkpoint(id: { sequenceNumber: $seq }) { timestamp } }",
+                "seq",
+                "Int",
+                json!(seq),
+            );
+            async move { data.await["checkpoint"]["timestamp"].clone() }
+        };
+
+        let digests = |filter: serde_json::Value| {
+            let data = query(
+                "{ transactionBlockConnection(first: 50, filter: $filter) { nodes { digest } } }",
+                "filter",
+                "TransactionBlockFilter!",
+                filter,
+            );
+            async move { data.await["transactionBlockConnection"]["nodes"].clone() }
+        };
+
+        let (t1, t2, t3) = (timestamp(1).await, timestamp(2).await, timestamp(3).await);
+        assert!(t1.is_string() && t2.is_string() && t3.is_string());
+
+        // Both time bounds are exclusive, so this only selects checkpoint 2's transactions.
+        let in_2 = digests(json!({ "atCheckpoint": 2 })).await;
+        assert!(!in_2.as_array().unwrap().is_empty());
+        assert_eq!(
+            digests(json!({ "afterTime": t1, "beforeTime": t3 })).await,
+            in_2,
+        );
+
+        assert_eq!(
+            digests(json!({ "afterTime": t2 })).await,
+            digests(json!({ "afterCheckpoint": 2 })).await,
+        );
+
+        assert_eq!(
+            digests(json!({ "beforeTime": t2 })).await,
+            digests(json!({ "beforeCheckpoint": 2 })).await,
+        );
+
+        // Nothing happens after the latest checkpoint (the connection may be empty or null).
+        let after_3 = digests(json!({ "afterTime": t3 })).await;
+        assert!(after_3.as_array().map_or(true, Vec::is_empty), "{after_3}");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_multi_get_objects_by_ref() {
+        let rng = StdRng::from_seed([12; 32]);
+        let mut sim = Simulacrum::new_with_rng(rng);
+        let recipient = SuiAddress::from(ObjectID::from_single_byte(0x42));
+
+        // Each transfer modifies the sender's gas coin, in a checkpoint of its own.
+        let mut gas_refs = vec![];
+        for _ in 0..2 {
+            let (transfer, _) = sim.transfer_txn(recipient);
+            let (effects, error) = sim.execute_transaction(transfer).unwrap();
+            assert!(error.is_none(), "{error:?}");
+            gas_refs.push(effects.gas_object().0);
+            sim.create_checkpoint();
+        }
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster = sui_graphql_rpc::test_infra::cluster::serve_executor(
+            connection_config,
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+        )
+        .await;
+
+        cluster
+            .wait_for_checkpoint_catchup(2, Duration::from_secs(10))
+            .await;
+
+        let object_ref = |(id, version, digest): (ObjectID, _, _)| {
+            json!({
+                "address": id.to_string(),
+                "version": version.value(),
+                "digest": digest.to_string(),
+            })
+        };
+
+        let (old, new) = (gas_refs[0], gas_refs[1]);
+        assert_eq!(old.0, new.0, "Transfers should pay with the same coin");
+        let wrong_digest = (old.0, old.1, new.2);
+        let missing = (ObjectID::from_single_byte(0x43), old.1, old.2);
+
+        let query = r#"
+            {
+                multiGetObjects(refs: $refs) { address version digest }
+            }
+        "#;
+        let variables = vec![GraphqlQueryVariable {
+            name: "refs".to_string(),
+            ty: "[ObjectRef!]".to_string(),
+            value: json!([
+                object_ref(old),
+                object_ref(new),
+                object_ref(wrong_digest),
+                object_ref(missing),
+            ]),
+        }];
+
+        let res = cluster
+            .graphql_client
+            .execute_to_graphql(query.to_string(), true, variables, vec![])
+            .await
+            .unwrap();
+
+        assert!(res.errors().is_empty(), "{:?}", res.errors());
+        let data = res.response_body().data.clone().into_json().unwrap();
+
+        // Objects are found at past and current versions, in the order they were asked for, and
+        // refs that do not match any version of an object are null.
+        assert_eq!(
+            data["multiGetObjects"],
+            json!([object_ref(old), object_ref(new), null, null]),
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_multi_get_by_id() {
+        let rng = StdRng::from_seed([12; 32]);
+        let mut sim = Simulacrum::new_with_rng(rng);
+        let recipient = SuiAddress::from(ObjectID::from_single_byte(0x42));
+
+        let (transfer, _) = sim.transfer_txn(recipient);
+        let (effects, error) = sim.execute_transaction(transfer).unwrap();
+        assert!(error.is_none(), "{error:?}");
+        sim.create_checkpoint();
+
+        let (gas_id, gas_version, _) = effects.gas_object().0;
+        let digest = *effects.transaction_digest();
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster = sui_graphql_rpc::test_infra::cluster::serve_executor(
+            connection_config,
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+        )
+        .await;
+
+        cluster
+            .wait_for_checkpoint_catchup(1, Duration::from_secs(10))
+            .await;
+
+        let missing_id = ObjectID::from_single_byte(0x43);
+        let missing_digest = TransactionDigest::new([0x43; 32]);
+
+        let query = r#"
+            {
+                multiGetObjects(ids: $ids) { address version }
+                multiGetTransactionBlocks(digests: $digests) { digest }
+            }
+        "#;
+        let variables = vec![
+            GraphqlQueryVariable {
+                name: "ids".to_string(),
+                ty: "[SuiAddress!]".to_string(),
+                value: json!([missing_id.to_string(), gas_id.to_string()]),
+            },
+            GraphqlQueryVariable {
+                name: "digests".to_string(),
+                ty: "[String!]!".to_string(),
+                value: json!([digest.to_string(), missing_digest.to_string()]),
+            },
+        ];
+
+        let res = cluster
+            .graphql_client
+            .execute_to_graphql(query.to_string(), true, variables, vec![])
+            .await
+            .unwrap();
+
+        assert!(res.errors().is_empty(), "{:?}", res.errors());
+        let data = res.response_body().data.clone().into_json().unwrap();
+
+        // Results are in the order they were asked for, with nulls for keys that were not found.
+        assert_eq!(
+            data["multiGetObjects"],
+            json!([
+                null,
+                { "address": gas_id.to_string(), "version": gas_version.value() },
+            ]),
+        );
+        assert_eq!(
+            data["multiGetTransactionBlocks"],
+            json!([{ "digest": digest.to_string() }, null]),
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_resolve_name_spellings() {
+        let rng = StdRng::from_seed([12; 32]);
+        let mut sim = Simulacrum::new_with_rng(rng);
+        sim.create_checkpoint();
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster = sui_graphql_rpc::test_infra::cluster::serve_executor(
+            connection_config,
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+        )
+        .await;
+
+        cluster
+            .wait_for_checkpoint_catchup(1, Duration::from_secs(10))
+            .await;
+
+        // No names are registered on this network, so every spelling of a name resolves to
+        // nothing, whether or not it was served from the cache.
+        let query = r#"
+            {
+                lower: resolveNameServiceAddress(name: "example.sui") { address }
+                mixed: resolveNameServiceAddress(name: "Example.SUI") { address }
+                again: resolveNameServiceAddress(name: "example.sui") { address }
+            }
+        "#;
+
+        let res = cluster
+            .graphql_client
+            .execute_to_graphql(query.to_string(), true, vec![], vec![])
+            .await
+            .unwrap();
+
+        assert!(res.errors().is_empty(), "{:?}", res.errors());
+        let data = res.response_body().data.clone().into_json().unwrap();
+        assert_eq!(data, json!({ "lower": null, "mixed": null, "again": null }),);
+
+        // Names that are not valid domains, however they are spelled, are rejected.
+        let res = cluster
+            .graphql_client
+            .execute_to_graphql(
+                r#"{ resolveNameServiceAddress(name: "ex_ample.sui") { address } }"#.to_string(),
+                true,
+                vec![],
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(res.errors().len(), 1);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_filter_balance_changes() {
+        let rng = StdRng::from_seed([12; 32]);
+        let mut sim = Simulacrum::new_with_rng(rng);
+        let recipient = SuiAddress::from(ObjectID::from_single_byte(0x42));
+
+        // Transfers are sent by the first account in the simulator's keystore.
+        let sender = *sim.keystore().accounts().next().unwrap().0;
+        let (transfer, amount) = sim.transfer_txn(recipient);
+        let (effects, error) = sim.execute_transaction(transfer).unwrap();
+        assert!(error.is_none(), "{error:?}");
+        sim.create_checkpoint();
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster = sui_graphql_rpc::test_infra::cluster::serve_executor(
+            connection_config,
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+        )
+        .await;
+
+        cluster
+            .wait_for_checkpoint_catchup(1, Duration::from_secs(10))
+            .await;
+
+        let query = r#"
+            {
+                transactionBlock(digest: $dig) {
+                    effects {
+                        all: balanceChanges { owner { address } amount }
+                        sender: balanceChanges(owner: $sender) { amount }
+                        recipient: balanceChanges(owner: $recipient) { amount }
+                        sui: balanceChanges(coinType: "0x2::sui::SUI") { amount }
+                        other: balanceChanges(coinType: "0x42::coin::COIN") { amount }
+                        both: balanceChanges(owner: $recipient, coinType: "0x2::sui::SUI") {
+                            amount
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = vec![
+            GraphqlQueryVariable {
+                name: "dig".to_string(),
+                ty: "String!".to_string(),
+                value: json!(effects.transaction_digest().to_string()),
+            },
+            GraphqlQueryVariable {
+                name: "sender".to_string(),
+                ty: "SuiAddress".to_string(),
+                value: json!(sender.to_string()),
+            },
+            GraphqlQueryVariable {
+                name: "recipient".to_string(),
+                ty: "SuiAddress".to_string(),
+                value: json!(recipient.to_string()),
+            },
+        ];
+
+        let res = cluster
+            .graphql_client
+            .execute_to_graphql(query.to_string(), true, variables, vec![])
+            .await
+            .unwrap();
+
+        assert!(res.errors().is_empty(), "{:?}", res.errors());
+        let data = res.response_body().data.clone().into_json().unwrap();
+        let changes = &data["transactionBlock"]["effects"];
+
+        // The transfer changes the SUI balances of its sender and recipient.
+        assert_eq!(changes["all"].as_array().unwrap().len(), 2);
+        assert_eq!(changes["sui"].as_array().unwrap().len(), 2);
+        assert_eq!(changes["other"], json!([]));
+
+        let received = json!([{ "amount": amount.to_string() }]);
+        assert_eq!(changes["recipient"], received);
+        assert_eq!(changes["both"], received);
+
+        // The sender pays for gas on top of the amount it sent.
+        let sent: i128 = changes["sender"][0]["amount"]
+            .as_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(sent < -(amount as i128), "{sent}");
+
+        // Coin types are validated.
+        let res = cluster
+            .graphql_client
+            .execute_to_graphql(
+                format!(
+                    r#"{{ transactionBlock(digest: "{}") {{
+                        effects {{ balanceChanges(coinType: "not a type") {{ amount }} }}
+                    }} }}"#,
+                    effects.transaction_digest(),
+                ),
+                true,
+                vec![],
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(res.errors().len(), 1, "{:?}", res.errors());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_object_version_pagination() {
+        let rng = StdRng::from_seed([12; 32]);
+        let mut sim = Simulacrum::new_with_rng(rng);
+        let recipient = SuiAddress::from(ObjectID::from_single_byte(0x42));
+
+        // Each transfer creates a new version of the sender's gas coin.
+        let mut gas_refs = vec![];
+        for _ in 0..3 {
+            let (transfer, _) = sim.transfer_txn(recipient);
+            let (effects, error) = sim.execute_transaction(transfer).unwrap();
+            assert!(error.is_none(), "{error:?}");
+            gas_refs.push(effects.gas_object().0);
+            sim.create_checkpoint();
+        }
+
+        let gas_id = gas_refs[0].0;
+        assert!(gas_refs.iter().all(|(id, _, _)| *id == gas_id));
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster = sui_graphql_rpc::test_infra::cluster::serve_executor(
+            connection_config,
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+        )
+        .await;
+
+        cluster
+            .wait_for_checkpoint_catchup(3, Duration::from_secs(10))
+            .await;
+
+        let query = r#"
+            {
+                object(address: $id) {
+                    versionConnection(first: $first, after: $after, last: $last, before: $before) {
+                        pageInfo { hasPreviousPage hasNextPage startCursor endCursor }
+                        nodes { version }
+                    }
+                }
+            }
+        "#;
+
+        let page = |first: Option<u64>,
+                    after: Option<String>,
+                    last: Option<u64>,
+                    before: Option<String>| {
+            let variables = vec![
+                GraphqlQueryVariable {
+                    name: "id".to_string(),
+                    ty: "SuiAddress!".to_string(),
+                    value: json!(gas_id.to_string()),
+                },
+                GraphqlQueryVariable {
+                    name: "first".to_string(),
+                    ty: "Int".to_string(),
+                    value: json!(first),
+                },
+                GraphqlQueryVariable {
+                    name: "after".to_string(),
+                    ty: "String".to_string(),
+                    value: json!(after),
+                },
+                GraphqlQueryVariable {
+                    name: "last".to_string(),
+                    ty: "Int".to_string(),
+                    value: json!(last),
+                },
+                GraphqlQueryVariable {
+                    name: "before".to_string(),
+                    ty: "String".to_string(),
+                    value: json!(before),
+                },
+            ];
+
+            let client = &cluster.graphql_client;
+            async move {
+                let res = client
+                    .execute_to_graphql(query.to_string(), true, variables, vec![])
+                    .await
+                    .unwrap();
+
+                assert!(res.errors().is_empty(), "{:?}", res.errors());
+                let data = res.response_body().data.clone().into_json().unwrap();
+                data["object"]["versionConnection"].clone()
+            }
+        };
+
+        let versions = |page: &serde_json::Value| {
+            page["nodes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|node| node["version"].as_u64().unwrap())
+                .collect::<Vec<_>>()
+        };
+        let cursor_of = |page: &serde_json::Value, field: &str| {
+            page["pageInfo"][field].as_str().map(String::from)
+        };
+
+        // The history ends with the versions written by the transfers, in ascending order.
+        let all = versions(&page(Some(50), None, None, None).await);
+        let transferred: Vec<_> = gas_refs.iter().map(|(_, v, _)| v.value()).collect();
+        assert!(all.ends_with(&transferred), "{all:?}");
+        assert!(all.windows(2).all(|w| w[0] < w[1]), "{all:?}");
+
+        // Paging forwards visits every version once.
+        let mut forwards = vec![];
+        let mut after = None;
+        loop {
+            let next = page(Some(1), after, None, None).await;
+            forwards.extend(versions(&next));
+            if next["pageInfo"]["hasNextPage"] != json!(true) {
+                break;
+            }
+            after = cursor_of(&next, "endCursor");
+        }
+        assert_eq!(forwards, all);
+
+        // Paging backwards from the latest version visits them all too, in the same order.
+        let mut backwards = vec![];
+        let mut before = None;
+        loop {
+            let prev = page(None, None, Some(1), before).await;
+            let mut prev_versions = versions(&prev);
+            prev_versions.extend(backwards);
+            backwards = prev_versions;
+            if prev["pageInfo"]["hasPreviousPage"] != json!(true) {
+                break;
+            }
+            before = cursor_of(&prev, "startCursor");
+        }
+        assert_eq!(backwards, all);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_network_tps() {
+        let rng = StdRng::from_seed([12; 32]);
+        let mut sim = Simulacrum::new_with_rng(rng);
+        let address = SuiAddress::from(ObjectID::from_single_byte(0x42));
+
+        // Checkpoints 1 to 4 are a second apart, and each holds two transactions: one to advance
+        // the clock, and one to send the address a coin.
+        for _ in 1..=4 {
+            sim.advance_clock(Duration::from_secs(1));
+            sim.request_gas(address, 1_000).unwrap();
+            sim.create_checkpoint();
+        }
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster = sui_graphql_rpc::test_infra::cluster::serve_executor(
+            connection_config,
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+        )
+        .await;
+
+        cluster
+            .wait_for_checkpoint_catchup(4, Duration::from_secs(10))
+            .await;
+
+        let query = r#"
+            {
+                networkTotals {
+                    recent: tps(windowCheckpoints: 2)
+                    none: tps(windowCheckpoints: 0)
+                    epoch: tps(windowCheckpoints: 4)
+                    default: tps
+                }
+            }
+        "#;
+
+        let res = cluster
+            .graphql_client
+            .execute_to_graphql(query.to_string(), true, vec![], vec![])
+            .await
+            .unwrap();
+
+        assert!(res.errors().is_empty(), "{:?}", res.errors());
+        let data = res.response_body().data.clone().into_json().unwrap();
+        let tps = &data["networkTotals"];
+
+        // Four transactions over the two seconds between checkpoints 2 and 4.
+        assert_eq!(tps["recent"], json!(2.0));
+
+        // There is no interval to measure over without any checkpoints.
+        assert_eq!(tps["none"], json!(null));
+
+        // The epoch has fewer checkpoints than the default window, so it is measured over all of
+        // them.
+        assert!(tps["epoch"].is_f64(), "{tps}");
+        assert_eq!(tps["default"], tps["epoch"]);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_checkpoint_header_chain() {
+        let rng = StdRng::from_seed([12; 32]);
+        let mut sim = Simulacrum::new_with_rng(rng);
+        let address = SuiAddress::from(ObjectID::from_single_byte(0x42));
+
+        let mut checkpoints = vec![];
+        for amount in 1..=2 {
+            sim.request_gas(address, amount * 1_000).unwrap();
+            checkpoints.push(sim.create_checkpoint());
+        }
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster = sui_graphql_rpc::test_infra::cluster::serve_executor(
+            connection_config,
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+        )
+        .await;
+
+        cluster
+            .wait_for_checkpoint_catchup(2, Duration::from_secs(10))
+            .await;
+
+        let query = r#"
+            {
+                checkpoint(id: { sequenceNumber: 2 }) {
+                    sequenceNumber
+                    digest
+                    contentsDigest
+                    previousCheckpointDigest
+                    validatorSigners
+                    previousCheckpoint {
+                        sequenceNumber
+                        digest
+                        contentsDigest
+                        previousCheckpointDigest
+                        previousCheckpoint {
+                            sequenceNumber
+                            previousCheckpoint { sequenceNumber }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let res = cluster
+            .graphql_client
+            .execute_to_graphql(query.to_string(), true, vec![], vec![])
+            .await
+            .unwrap();
+
+        assert!(res.errors().is_empty(), "{:?}", res.errors());
+        let data = res.response_body().data.clone().into_json().unwrap();
+
+        // Headers read through GraphQL match the checkpoints that were produced, and link back to
+        // genesis, which has no previous checkpoint.
+        let (one, two) = (&checkpoints[0], &checkpoints[1]);
+        let latest = &data["checkpoint"];
+        assert_eq!(latest["sequenceNumber"], json!(2));
+        assert_eq!(latest["digest"], json!(two.digest().to_string()));
+        assert_eq!(
+            latest["contentsDigest"],
+            json!(two.content_digest.to_string())
+        );
+        assert_eq!(
+            latest["previousCheckpointDigest"],
+            json!(one.digest().to_string())
+        );
+        assert!(!latest["validatorSigners"].as_array().unwrap().is_empty());
+
+        let previous = &latest["previousCheckpoint"];
+        assert_eq!(previous["sequenceNumber"], json!(1));
+        assert_eq!(previous["digest"], latest["previousCheckpointDigest"]);
+        assert_eq!(
+            previous["contentsDigest"],
+            json!(one.content_digest.to_string())
+        );
+        assert_eq!(
+            previous["previousCheckpoint"],
+            json!({ "sequenceNumber": 0, "previousCheckpoint": null }),
+        );
+
+        // Digests read from the chain of headers can be used to look the checkpoints up again.
+        let res = cluster
+            .graphql_client
+            .execute_to_graphql(
+                r#"{ checkpoint(id: { digest: $digest }) { sequenceNumber contentsDigest } }"#
+                    .to_string(),
+                true,
+                vec![GraphqlQueryVariable {
+                    name: "digest".to_string(),
+                    ty: "String".to_string(),
+                    value: previous["digest"].clone(),
+                }],
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        assert!(res.errors().is_empty(), "{:?}", res.errors());
+        let data = res.response_body().data.clone().into_json().unwrap();
+        assert_eq!(
+            data["checkpoint"],
+            json!({
+                "sequenceNumber": 1,
+                "contentsDigest": one.content_digest.to_string(),
+            }),
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_object_pages_pinned_to_checkpoint() {
+        let _guard = telemetry_subscribers::TelemetryConfig::new()
+            .with_env()
+            .init();
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster =
+            sui_graphql_rpc::test_infra::cluster::start_cluster(connection_config, None).await;
+
+        let builder = cluster
+            .validator_fullnode_handle
+            .test_transaction_builder()
+            .await;
+        let sender = builder.sender();
+        let recipient = cluster
+            .validator_fullnode_handle
+            .wallet
+            .get_addresses()
+            .into_iter()
+            .find(|address| *address != sender)
+            .unwrap();
+
+        // Wait for the genesis objects to be indexed.
+        sleep(Duration::from_secs(10)).await;
+
+        let query = r#"
+            {
+                address(address: $addr) {
+                    objectConnection(first: $first, after: $after) {
+                        pageInfo { hasNextPage endCursor }
+                        nodes { address version }
+                    }
+                }
+            }
+        "#;
+
+        let page = |first: u64, after: Option<String>| {
+            let variables = vec![
+                GraphqlQueryVariable {
+                    name: "addr".to_string(),
+                    ty: "SuiAddress!".to_string(),
+                    value: json!(sender.to_string()),
+                },
+                GraphqlQueryVariable {
+                    name: "first".to_string(),
+                    ty: "Int".to_string(),
+                    value: json!(first),
+                },
+                GraphqlQueryVariable {
+                    name: "after".to_string(),
+                    ty: "String".to_string(),
+                    value: json!(after),
+                },
+            ];
+
+            let client = &cluster.graphql_client;
+            async move {
+                let res = client
+                    .execute_to_graphql(query.to_string(), true, variables, vec![])
+                    .await
+                    .unwrap();
+
+                assert!(res.errors().is_empty(), "{:?}", res.errors());
+                let data = res.response_body().data.clone().into_json().unwrap();
+                data["address"]["objectConnection"].clone()
+            }
+        };
+
+        let nodes = |page: &serde_json::Value| page["nodes"].as_array().unwrap().clone();
+
+        let snapshot = nodes(&page(50, None).await);
+        assert!(snapshot.len() > 2, "Sender should own several gas coins");
+
+        let first = page(2, None).await;
+        let mut paged = nodes(&first);
+        assert_eq!(first["pageInfo"]["hasNextPage"], json!(true));
+
+        // Transfer one of the sender's coins away, and wait for the checkpoint it is in to be
+        // indexed, before reading the rest of the pages.
+        let tx = builder.transfer_sui(None, recipient).build();
+        cluster
+            .validator_fullnode_handle
+            .sign_and_execute_transaction(&tx)
+            .await;
+        sleep(Duration::from_secs(10)).await;
+
+        let mut cursor = first["pageInfo"]["endCursor"].as_str().map(String::from);
+        while let Some(after) = cursor {
+            let next = page(2, Some(after)).await;
+            paged.extend(nodes(&next));
+            cursor = (next["pageInfo"]["hasNextPage"] == json!(true))
+                .then(|| next["pageInfo"]["endCursor"].as_str().unwrap().to_string());
+        }
+
+        // Later pages are read as of the same checkpoint as the first.
+        assert_eq!(paged, snapshot);
+
+        // Fresh queries see the transfer.
+        let latest = nodes(&page(50, None).await);
+        assert_eq!(latest.len(), snapshot.len() - 1);
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_transaction_execution() {
@@ -388,8 +1741,72 @@ mod tests {
         assert_eq!(sender_read, sender.to_string());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_dry_run_transfer() {
+        let _guard = telemetry_subscribers::TelemetryConfig::new()
+            .with_env()
+            .init();
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+
+        let cluster =
+            sui_graphql_rpc::test_infra::cluster::start_cluster(connection_config, None).await;
+
+        // Wait for the gas coins created at genesis to be indexed
+        sleep(Duration::from_secs(10)).await;
+
+        let addresses = cluster.validator_fullnode_handle.wallet.get_addresses();
+
+        let recipient = addresses[1];
+        let tx = cluster
+            .validator_fullnode_handle
+            .test_transaction_builder()
+            .await
+            .transfer_sui(Some(1_000), recipient)
+            .build();
+        let signed_tx = cluster
+            .validator_fullnode_handle
+            .wallet
+            .sign_transaction(&tx);
+        let (tx_bytes, _) = signed_tx.to_tx_bytes_and_signatures();
+
+        let mutation = r#"{
+            dryRunTransactionBlock(txBytes: $tx) {
+                status
+                errors
+                gasSummary { computationCost }
+            }
+        }"#;
+
+        let variables = vec![GraphqlQueryVariable {
+            name: "tx".to_string(),
+            ty: "String!".to_string(),
+            value: json!(tx_bytes.encoded()),
+        }];
+        let res = cluster
+            .graphql_client
+            .execute_mutation_to_graphql(mutation.to_string(), variables)
+            .await
+            .unwrap();
+
+        assert!(res.errors().is_empty(), "{:#?}", res.errors());
+        let binding = res.response_body().data.clone().into_json().unwrap();
+        let res = binding.get("dryRunTransactionBlock").unwrap();
+
+        assert_eq!(res.get("status").unwrap().as_str().unwrap(), "SUCCESS");
+        assert!(res.get("errors").unwrap().is_null());
+        assert!(!res["gasSummary"]["computationCost"].is_null());
+    }
+
     use sui_graphql_rpc::server::builder::tests::*;
 
+    #[tokio::test]
+    #[serial]
+    async fn test_read_replica_fallback() {
+        test_read_replica_fallback_impl().await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_timeout() {
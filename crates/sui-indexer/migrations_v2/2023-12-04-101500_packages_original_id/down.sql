@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS packages_original_id;
+ALTER TABLE packages DROP COLUMN IF EXISTS original_id;
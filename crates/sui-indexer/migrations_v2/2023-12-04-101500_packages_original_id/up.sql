@@ -0,0 +1,86 @@
+-- ID of the first version of the package, shared by all its upgrades
+ALTER TABLE packages ADD COLUMN original_id bytea;
+
+-- Reads an unsigned LEB128 integer from `bytes`, starting at (0-based) offset `pos`, returning
+-- the integer, and the offset just past it.
+CREATE FUNCTION packages_original_id_uleb128(bytes bytea, pos INT, value OUT BIGINT, next OUT INT)
+AS $$
+DECLARE
+    shift INT := 0;
+    b INT;
+BEGIN
+    value := 0;
+    LOOP
+        b := get_byte(bytes, pos);
+        value := value | ((b & 127)::BIGINT << shift);
+        pos := pos + 1;
+        EXIT WHEN b < 128;
+        shift := shift + 7;
+    END LOOP;
+    next := pos;
+END;$$ LANGUAGE plpgsql IMMUTABLE STRICT;
+
+-- The original ID of a BCS serialized MovePackage: the self address of its first module (the same
+-- as `MovePackage::original_package_id`).
+CREATE FUNCTION packages_original_id(move_package bytea) RETURNS bytea
+AS $$
+DECLARE
+    pos INT;
+    len BIGINT;
+    module bytea;
+    table_count BIGINT;
+    kind INT;
+    table_offset BIGINT;
+    table_size BIGINT;
+    contents_start INT;
+    contents_size BIGINT := 0;
+    handles_offset BIGINT;
+    addresses_offset BIGINT;
+    self_handle BIGINT;
+    address BIGINT;
+BEGIN
+    -- Skip the package's ID (32 bytes) and version (8 bytes), and the number of modules in its
+    -- module map, to get to the name and then the bytes of its first module.
+    SELECT u.value, u.next INTO len, pos FROM packages_original_id_uleb128(move_package, 40) u;
+    SELECT u.value, u.next INTO len, pos FROM packages_original_id_uleb128(move_package, pos) u;
+    pos := pos + len;
+    SELECT u.value, u.next INTO len, pos FROM packages_original_id_uleb128(move_package, pos) u;
+    module := substring(move_package FROM pos + 1 FOR len);
+
+    -- Skip the module's magic (4 bytes) and version (4 bytes), to read its table headers.
+    SELECT u.value, u.next INTO table_count, pos FROM packages_original_id_uleb128(module, 8) u;
+    FOR i IN 1..table_count LOOP
+        kind := get_byte(module, pos);
+        SELECT u.value, u.next INTO table_offset, pos FROM packages_original_id_uleb128(module, pos + 1) u;
+        SELECT u.value, u.next INTO table_size, pos FROM packages_original_id_uleb128(module, pos) u;
+        contents_size := contents_size + table_size;
+
+        IF kind = 1 THEN -- MODULE_HANDLES
+            handles_offset := table_offset;
+        ELSIF kind = 8 THEN -- ADDRESS_IDENTIFIERS
+            addresses_offset := table_offset;
+        END IF;
+    END LOOP;
+
+    -- Table contents follow the headers, and the self module handle's index follows the contents.
+    contents_start := pos;
+    SELECT u.value INTO self_handle
+    FROM packages_original_id_uleb128(module, contents_start + contents_size::INT) u;
+
+    -- Module handles are pairs of (address index, name index).
+    pos := contents_start + handles_offset::INT;
+    FOR i IN 0..self_handle LOOP
+        SELECT u.value, u.next INTO address, pos FROM packages_original_id_uleb128(module, pos) u;
+        SELECT u.next INTO pos FROM packages_original_id_uleb128(module, pos) u;
+    END LOOP;
+
+    RETURN substring(module FROM contents_start + addresses_offset::INT + 32 * address::INT + 1 FOR 32);
+END;$$ LANGUAGE plpgsql IMMUTABLE STRICT;
+
+UPDATE packages SET original_id = packages_original_id(move_package);
+
+DROP FUNCTION packages_original_id(bytea);
+DROP FUNCTION packages_original_id_uleb128(bytea, INT);
+
+ALTER TABLE packages ALTER COLUMN original_id SET NOT NULL;
+CREATE INDEX packages_original_id ON packages (original_id);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE checkpoints DROP COLUMN IF EXISTS checkpoint_contents_digest;
+ALTER TABLE checkpoints DROP COLUMN IF EXISTS validator_signers;
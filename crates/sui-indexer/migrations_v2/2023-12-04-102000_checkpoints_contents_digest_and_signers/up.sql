@@ -0,0 +1,12 @@
+-- digest of the checkpoint's contents, committed to by the checkpoint summary
+ALTER TABLE checkpoints ADD COLUMN checkpoint_contents_digest bytea;
+-- bcs serialized Vec<u32> of the committee indices of validators that signed the checkpoint
+ALTER TABLE checkpoints ADD COLUMN validator_signers bytea;
+
+-- Neither can be derived from the rest of a checkpoint that has already been indexed, so these
+-- checkpoints are marked as not having them, with empty bytes.
+UPDATE checkpoints SET checkpoint_contents_digest = '', validator_signers = '';
+
+ALTER TABLE checkpoints
+    ALTER COLUMN checkpoint_contents_digest SET NOT NULL,
+    ALTER COLUMN validator_signers SET NOT NULL;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS objects_history_coin;
+DROP INDEX IF EXISTS checkpoints_timestamp_ms;
@@ -0,0 +1,4 @@
+-- Supports bucketing checkpoints into days by their timestamps.
+CREATE INDEX checkpoints_timestamp_ms ON checkpoints (timestamp_ms);
+-- Supports summing the historical balance of an address's coins as of a checkpoint.
+CREATE INDEX objects_history_coin ON objects_history (owner_id, coin_type, checkpoint_sequence_number) WHERE coin_type IS NOT NULL AND owner_type = 1;
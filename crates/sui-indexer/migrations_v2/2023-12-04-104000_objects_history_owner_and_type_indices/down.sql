@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS objects_history_type;
+DROP INDEX IF EXISTS objects_history_owner;
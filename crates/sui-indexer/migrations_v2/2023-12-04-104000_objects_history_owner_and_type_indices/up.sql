@@ -0,0 +1,5 @@
+-- Supports reading an owner's objects (or dynamic fields) as of a past checkpoint, in object ID
+-- order.
+CREATE INDEX objects_history_owner ON objects_history (owner_type, owner_id, object_id);
+-- Supports filtering objects as of a past checkpoint by their type (or a prefix of it).
+CREATE INDEX objects_history_type ON objects_history (object_type text_pattern_ops, object_id);
@@ -0,0 +1,2 @@
+-- Supports paginating an owner's coins of a given type, largest balance first.
+CREATE INDEX objects_owner_coin_balance ON objects (owner_id, coin_type, coin_balance DESC, object_id);
@@ -111,6 +111,16 @@ impl IndexerReader {
         })
     }
 
+    /// The maximum number of connections the reader's connection pool will open.
+    pub fn pool_max_size(&self) -> u32 {
+        self.pool.max_size()
+    }
+
+    /// A snapshot of the number of connections (total and idle) in the reader's connection pool.
+    pub fn pool_state(&self) -> diesel::r2d2::State {
+        self.pool.state()
+    }
+
     pub fn run_query<T, E, F>(&self, query: F) -> Result<T, IndexerError>
     where
         F: FnOnce(&mut PgConnection) -> Result<T, E>,
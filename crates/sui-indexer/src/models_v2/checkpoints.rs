@@ -31,6 +31,8 @@ pub struct StoredCheckpoint {
     pub checkpoint_commitments: Vec<u8>,
     pub validator_signature: Vec<u8>,
     pub end_of_epoch_data: Option<Vec<u8>>,
+    pub checkpoint_contents_digest: Vec<u8>,
+    pub validator_signers: Vec<u8>,
 }
 
 impl From<&IndexedCheckpoint> for StoredCheckpoint {
@@ -38,6 +40,7 @@ impl From<&IndexedCheckpoint> for StoredCheckpoint {
         Self {
             sequence_number: c.sequence_number as i64,
             checkpoint_digest: c.checkpoint_digest.into_inner().to_vec(),
+            checkpoint_contents_digest: c.checkpoint_contents_digest.into_inner().to_vec(),
             epoch: c.epoch as i64,
             tx_digests: c
                 .tx_digests
@@ -57,6 +60,7 @@ impl From<&IndexedCheckpoint> for StoredCheckpoint {
             non_refundable_storage_fee: c.non_refundable_storage_fee as i64,
             checkpoint_commitments: bcs::to_bytes(&c.checkpoint_commitments).unwrap(),
             validator_signature: bcs::to_bytes(&c.validator_signature).unwrap(),
+            validator_signers: bcs::to_bytes(&c.validator_signers).unwrap(),
             end_of_epoch_data: c
                 .end_of_epoch_data
                 .as_ref()
@@ -102,6 +102,49 @@ impl From<StoredObject> for StoredHistoryObject {
     }
 }
 
+/// The inverse of `From<StoredObject>`: a version of an object from its history, as it would
+/// appear in the table of live objects. Fails for versions at which the object was not live.
+impl TryFrom<StoredHistoryObject> for StoredObject {
+    type Error = IndexerError;
+
+    fn try_from(o: StoredHistoryObject) -> Result<Self, Self::Error> {
+        let missing = |field: &str| {
+            IndexerError::PersistentStorageDataCorruptionError(format!(
+                "Object {:?} at version {} has no {field}",
+                o.object_id, o.object_version
+            ))
+        };
+
+        if o.object_status != ObjectStatus::Active as i16 {
+            return Err(IndexerError::PersistentStorageDataCorruptionError(format!(
+                "Object {:?} is not live at version {}",
+                o.object_id, o.object_version
+            )));
+        }
+
+        let object_digest = o.object_digest.ok_or_else(|| missing("digest"))?;
+        let owner_type = o.owner_type.ok_or_else(|| missing("owner type"))?;
+        let serialized_object = o.serialized_object.ok_or_else(|| missing("contents"))?;
+
+        Ok(Self {
+            object_id: o.object_id,
+            object_version: o.object_version,
+            object_digest,
+            checkpoint_sequence_number: o.checkpoint_sequence_number,
+            owner_type,
+            owner_id: o.owner_id,
+            object_type: o.object_type,
+            serialized_object,
+            coin_type: o.coin_type,
+            coin_balance: o.coin_balance,
+            df_kind: o.df_kind,
+            df_name: o.df_name,
+            df_object_type: o.df_object_type,
+            df_object_id: o.df_object_id,
+        })
+    }
+}
+
 #[derive(Queryable, Insertable, Debug, Identifiable, Clone, QueryableByName)]
 #[diesel(table_name = objects, primary_key(object_id))]
 pub struct StoredDeletedObject {
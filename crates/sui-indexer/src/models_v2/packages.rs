@@ -11,6 +11,7 @@ use diesel::prelude::*;
 pub struct StoredPackage {
     pub package_id: Vec<u8>,
     pub move_package: Vec<u8>,
+    pub original_id: Vec<u8>,
 }
 
 impl From<IndexedPackage> for StoredPackage {
@@ -18,6 +19,7 @@ impl From<IndexedPackage> for StoredPackage {
         Self {
             package_id: p.package_id.to_vec(),
             move_package: bcs::to_bytes(&p.move_package).unwrap(),
+            original_id: p.move_package.original_package_id().to_vec(),
         }
     }
 }
@@ -51,6 +51,8 @@ diesel::table! {
         checkpoint_commitments -> Bytea,
         validator_signature -> Bytea,
         end_of_epoch_data -> Nullable<Bytea>,
+        checkpoint_contents_digest -> Bytea,
+        validator_signers -> Bytea,
     }
 }
 
@@ -216,6 +218,7 @@ diesel::table! {
     packages (package_id) {
         package_id -> Bytea,
         move_package -> Bytea,
+        original_id -> Bytea,
     }
 }
 
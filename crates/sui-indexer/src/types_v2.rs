@@ -14,7 +14,8 @@ use sui_types::dynamic_field::DynamicFieldInfo;
 use sui_types::effects::TransactionEffects;
 use sui_types::event::SystemEpochInfoEvent;
 use sui_types::messages_checkpoint::{
-    CertifiedCheckpointSummary, CheckpointCommitment, CheckpointDigest, EndOfEpochData,
+    CertifiedCheckpointSummary, CheckpointCommitment, CheckpointContentsDigest, CheckpointDigest,
+    EndOfEpochData,
 };
 use sui_types::move_package::MovePackage;
 use sui_types::object::{Object, Owner};
@@ -28,6 +29,7 @@ pub type IndexerResult<T> = Result<T, IndexerError>;
 pub struct IndexedCheckpoint {
     pub sequence_number: u64,
     pub checkpoint_digest: CheckpointDigest,
+    pub checkpoint_contents_digest: CheckpointContentsDigest,
     pub epoch: u64,
     pub tx_digests: Vec<TransactionDigest>,
     pub network_total_transactions: u64,
@@ -40,6 +42,7 @@ pub struct IndexedCheckpoint {
     pub non_refundable_storage_fee: u64,
     pub checkpoint_commitments: Vec<CheckpointCommitment>,
     pub validator_signature: AggregateAuthoritySignature,
+    pub validator_signers: Vec<u32>,
     pub successful_tx_num: usize,
     pub end_of_epoch_data: Option<EndOfEpochData>,
     pub end_of_epoch: bool,
@@ -55,10 +58,11 @@ impl IndexedCheckpoint {
             + checkpoint.epoch_rolling_gas_cost_summary.storage_cost as i64
             - checkpoint.epoch_rolling_gas_cost_summary.storage_rebate as i64;
         let tx_digests = contents.iter().map(|t| t.transaction).collect::<Vec<_>>();
-        let auth_sig = &checkpoint.auth_sig().signature;
+        let auth_sig = checkpoint.auth_sig();
         Self {
             sequence_number: checkpoint.sequence_number,
             checkpoint_digest: *checkpoint.digest(),
+            checkpoint_contents_digest: checkpoint.content_digest,
             epoch: checkpoint.epoch,
             tx_digests,
             previous_checkpoint_digest: checkpoint.previous_digest,
@@ -74,7 +78,8 @@ impl IndexedCheckpoint {
             successful_tx_num,
             network_total_transactions: checkpoint.network_total_transactions,
             timestamp_ms: checkpoint.timestamp_ms,
-            validator_signature: auth_sig.clone(),
+            validator_signature: auth_sig.signature.clone(),
+            validator_signers: auth_sig.signers_map.iter().collect(),
             checkpoint_commitments: checkpoint.checkpoint_commitments.clone(),
         }
     }
@@ -28,6 +28,7 @@ use sui_types::crypto::SuiSignature;
 use sui_types::digests::{ConsensusCommitDigest, ObjectDigest, TransactionEventsDigest};
 use sui_types::effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents};
 use sui_types::error::{ExecutionError, SuiError, SuiResult};
+use sui_types::execution_mode::CommandResult;
 use sui_types::execution_status::ExecutionStatus;
 use sui_types::gas::GasCostSummary;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
@@ -1016,9 +1017,15 @@ pub struct DevInspectResults {
     pub error: Option<String>,
 }
 
+#[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename = "SuiExecutionResult", rename_all = "camelCase")]
 pub struct SuiExecutionResult {
+    /// The gas used by the command, in gas units
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    #[serde(default)]
+    pub gas_used: u64,
     /// The value of any arguments that were mutably borrowed.
     /// Non-mut borrowed values are not included
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -1028,16 +1035,11 @@ pub struct SuiExecutionResult {
     pub return_values: Vec<(Vec<u8>, SuiTypeTag)>,
 }
 
-type ExecutionResult = (
-    /*  mutable_reference_outputs */ Vec<(Argument, Vec<u8>, TypeTag)>,
-    /*  return_values */ Vec<(Vec<u8>, TypeTag)>,
-);
-
 impl DevInspectResults {
     pub fn new(
         effects: TransactionEffects,
         events: TransactionEvents,
-        return_values: Result<Vec<ExecutionResult>, ExecutionError>,
+        return_values: Result<Vec<CommandResult>, ExecutionError>,
         resolver: &impl GetModule,
     ) -> SuiResult<Self> {
         let tx_digest = *effects.transaction_digest();
@@ -1049,7 +1051,11 @@ impl DevInspectResults {
                 results = Some(
                     srvs.into_iter()
                         .map(|srv| {
-                            let (mutable_reference_outputs, return_values) = srv;
+                            let CommandResult {
+                                gas_used,
+                                mutable_reference_outputs,
+                                return_values,
+                            } = srv;
                             let mutable_reference_outputs = mutable_reference_outputs
                                 .into_iter()
                                 .map(|(a, bytes, tag)| (a.into(), bytes, SuiTypeTag::from(tag)))
@@ -1059,6 +1065,7 @@ impl DevInspectResults {
                                 .map(|(bytes, tag)| (bytes, SuiTypeTag::from(tag)))
                                 .collect();
                             SuiExecutionResult {
+                                gas_used,
                                 mutable_reference_outputs,
                                 return_values,
                             }
@@ -30,7 +30,7 @@ use crate::routing_layer::RpcRouter;
 
 pub mod authority_state;
 pub mod axum_router;
-mod balance_changes;
+pub mod balance_changes;
 pub mod coin_api;
 pub mod error;
 pub mod governance_api;
@@ -44,11 +44,14 @@ pub trait ExecutionMode {
         _new_value: &Value,
     ) -> Result<(), ExecutionError>;
 
+    /// Called once a command has executed. `gas_used` is the computation gas (in gas units,
+    /// before the gas price is applied) charged while executing it.
     fn finish_command(
         resolver: &impl TypeTagResolver,
         acc: &mut Self::ExecutionResults,
         argument_updates: Self::ArgumentUpdates,
         command_result: &[Value],
+        gas_used: u64,
     ) -> Result<(), ExecutionError>;
 }
 
@@ -93,6 +96,7 @@ impl ExecutionMode for Normal {
         _acc: &mut Self::ExecutionResults,
         _argument_updates: Self::ArgumentUpdates,
         _command_result: &[Value],
+        _gas_used: u64,
     ) -> Result<(), ExecutionError> {
         Ok(())
     }
@@ -139,6 +143,7 @@ impl ExecutionMode for Genesis {
         _acc: &mut Self::ExecutionResults,
         _argument_updates: Self::ArgumentUpdates,
         _command_result: &[Value],
+        _gas_used: u64,
     ) -> Result<(), ExecutionError> {
         Ok(())
     }
@@ -191,6 +196,7 @@ impl ExecutionMode for System {
         _acc: &mut Self::ExecutionResults,
         _argument_updates: Self::ArgumentUpdates,
         _command_result: &[Value],
+        _gas_used: u64,
     ) -> Result<(), ExecutionError> {
         Ok(())
     }
@@ -201,14 +207,21 @@ impl ExecutionMode for System {
 /// BCS bytes!
 pub struct DevInspect;
 
-pub type ExecutionResult = (
-    /*  mutable_reference_outputs */ Vec<(Argument, Vec<u8>, TypeTag)>,
-    /*  return_values */ Vec<(Vec<u8>, TypeTag)>,
-);
+/// What `DevInspect` observed while executing a single command of a programmable transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandResult {
+    /// Computation gas charged while executing the command, in gas units (i.e. before the gas
+    /// price is applied).
+    pub gas_used: u64,
+    /// The values of any arguments that were mutably borrowed by the command, after it ran.
+    pub mutable_reference_outputs: Vec<(Argument, Vec<u8>, TypeTag)>,
+    /// The values returned by the command.
+    pub return_values: Vec<(Vec<u8>, TypeTag)>,
+}
 
 impl ExecutionMode for DevInspect {
     type ArgumentUpdates = Vec<(Argument, Vec<u8>, TypeTag)>;
-    type ExecutionResults = Vec<ExecutionResult>;
+    type ExecutionResults = Vec<CommandResult>;
 
     fn allow_arbitrary_function_calls() -> bool {
         true
@@ -250,12 +263,17 @@ impl ExecutionMode for DevInspect {
         acc: &mut Self::ExecutionResults,
         argument_updates: Self::ArgumentUpdates,
         command_result: &[Value],
+        gas_used: u64,
     ) -> Result<(), ExecutionError> {
-        let command_bytes = command_result
+        let return_values = command_result
             .iter()
             .map(|value| value_to_bytes_and_tag(resolver, value))
             .collect::<Result<_, _>>()?;
-        acc.push((argument_updates, command_bytes));
+        acc.push(CommandResult {
+            gas_used,
+            mutable_reference_outputs: argument_updates,
+            return_values,
+        });
         Ok(())
     }
 }
@@ -1,9 +1,12 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::time::Duration;
+
+use move_core_types::language_storage::ModuleId;
 use prometheus::{
-    register_histogram_with_registry, register_int_counter_vec_with_registry, Histogram,
-    IntCounterVec,
+    register_histogram_vec_with_registry, register_histogram_with_registry,
+    register_int_counter_vec_with_registry, Histogram, HistogramVec, IntCounterVec,
 };
 
 pub struct LimitsMetrics {
@@ -84,6 +87,12 @@ pub struct BytecodeVerifierMetrics {
     pub verifier_runtime_per_module_timeout_latency: Histogram,
     /// Bytecode verifier runtime latency for each programmable transaction block which timed out
     pub verifier_runtime_per_ptb_timeout_latency: Histogram,
+    /// Bytecode verifier runtime latency for each verifier pass, per module
+    pub verifier_runtime_per_pass_latency: HistogramVec,
+    /// Meter ticks charged by each verifier pass, per module
+    pub verifier_ticks_per_pass: HistogramVec,
+    /// Number of modules run through the metered verifier, by outcome
+    pub verifier_module_outcome: IntCounterVec,
 }
 
 impl BytecodeVerifierMetrics {
@@ -92,12 +101,16 @@ impl BytecodeVerifierMetrics {
     pub const OVERALL_TAG: &'static str = "overall";
     pub const SUCCESS_TAG: &'static str = "success";
     pub const TIMEOUT_TAG: &'static str = "failed";
+    pub const FAILURE_TAG: &'static str = "rejected";
     const LATENCY_SEC_BUCKETS: &[f64] = &[
         0.000_010, 0.000_025, 0.000_050, 0.000_100, /* sub 100 micros */
         0.000_250, 0.000_500, 0.001_000, 0.002_500, 0.005_000, 0.010_000, /* sub 10 ms: p99 */
         0.025_000, 0.050_000, 0.100_000, 0.250_000, 0.500_000, 1.000_000, /* sub 1 s */
         10.000_000, 20.000_000, 50.000_000, 100.0, /* We should almost never get here */
     ];
+    const TICKS_BUCKETS: &[f64] = &[
+        0.0, 1e3, 1e4, 1e5, 1e6, 2.5e6, 5e6, 1e7, 2.5e7, 5e7, 1e8, 2.5e8, 5e8, 1e9,
+    ];
     pub fn new(registry: &prometheus::Registry) -> Self {
         Self {
             verifier_timeout_metrics: register_int_counter_vec_with_registry!(
@@ -133,6 +146,78 @@ impl BytecodeVerifierMetrics {
                 Self::LATENCY_SEC_BUCKETS.to_vec(),
                 registry
             ).unwrap(),
+            verifier_runtime_per_pass_latency: register_histogram_vec_with_registry!(
+                "verifier_runtime_per_pass_latency",
+                "Time spent in each pass of the metered bytecode verifier, per module",
+                &["pass"],
+                Self::LATENCY_SEC_BUCKETS.to_vec(),
+                registry
+            )
+            .unwrap(),
+            verifier_ticks_per_pass: register_histogram_vec_with_registry!(
+                "verifier_ticks_per_pass",
+                "Meter ticks charged by each pass of the metered bytecode verifier, per module",
+                &["pass"],
+                Self::TICKS_BUCKETS.to_vec(),
+                registry
+            )
+            .unwrap(),
+            verifier_module_outcome: register_int_counter_vec_with_registry!(
+                "verifier_module_outcome",
+                "Number of modules run through the metered bytecode verifier, by outcome",
+                &["outcome"],
+                registry,
+            )
+            .unwrap(),
         }
     }
+
+    /// Record the time and ticks spent in one verifier pass over a module.
+    pub fn observe_pass(&self, pass: &VerifierPassReport) {
+        self.verifier_runtime_per_pass_latency
+            .with_label_values(&[pass.pass])
+            .observe(pass.duration.as_secs_f64());
+        self.verifier_ticks_per_pass
+            .with_label_values(&[pass.pass])
+            .observe(pass.ticks as f64);
+    }
+
+    /// Record how verifying a module ended.
+    pub fn observe_outcome(&self, outcome: VerifierOutcome) {
+        let tag = match outcome {
+            VerifierOutcome::Success => Self::SUCCESS_TAG,
+            VerifierOutcome::Timeout => Self::TIMEOUT_TAG,
+            VerifierOutcome::Failure => Self::FAILURE_TAG,
+        };
+        self.verifier_module_outcome.with_label_values(&[tag]).inc();
+    }
+}
+
+/// How the metered verifier finished with a module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifierOutcome {
+    Success,
+    /// The module exceeded its metering budget.
+    Timeout,
+    /// The module was rejected for a reason other than its metering budget.
+    Failure,
+}
+
+/// Time spent, and meter ticks charged, by a single verifier pass over a module.
+#[derive(Clone, Debug)]
+pub struct VerifierPassReport {
+    pub pass: &'static str,
+    pub duration: Duration,
+    pub ticks: u128,
+}
+
+/// Breakdown of the metered verifier's work on a single module, pass by pass, in the order the
+/// passes ran. Passes that did not run (because an earlier pass failed) are omitted.
+#[derive(Clone, Debug)]
+pub struct ModuleVerifierReport {
+    /// `None` if the module is too malformed to identify itself (its self handle is out of
+    /// bounds).
+    pub module: Option<ModuleId>,
+    pub passes: Vec<VerifierPassReport>,
+    pub outcome: VerifierOutcome,
 }
@@ -5,7 +5,10 @@ use crate::{
     error::{ExecutionError, SuiError},
     object::MoveObject,
 };
-use move_core_types::{annotated_value as A, language_storage::TypeTag};
+use move_core_types::{
+    annotated_value as A,
+    language_storage::{StructTag, TypeTag},
+};
 use move_vm_types::loaded_data::runtime_types::Type;
 
 pub trait LayoutResolver {
@@ -13,6 +16,13 @@ pub trait LayoutResolver {
         &mut self,
         object: &MoveObject,
     ) -> Result<A::MoveStructLayout, SuiError>;
+
+    /// The layout of values of type `struct_tag`, whose addresses are the IDs of the packages
+    /// that define each type (not the packages that their types were first introduced in).
+    fn get_annotated_struct_layout(
+        &mut self,
+        struct_tag: &StructTag,
+    ) -> Result<A::MoveStructLayout, SuiError>;
 }
 
 pub trait TypeTagResolver {
@@ -166,3 +166,94 @@ impl Meter for DummyMeter {
         Ok(())
     }
 }
+
+/// Forwards to another meter, keeping a tally of the ticks charged through it, so that they can
+/// be attributed to the verifier pass that charged them.
+pub struct TickCountingMeter<'m, M: Meter> {
+    inner: &'m mut M,
+    ticks: u128,
+}
+
+impl<'m, M: Meter> TickCountingMeter<'m, M> {
+    pub fn new(inner: &'m mut M) -> Self {
+        Self { inner, ticks: 0 }
+    }
+
+    /// Ticks charged through this meter so far (excluding any transfers between scopes).
+    pub fn ticks(&self) -> u128 {
+        self.ticks
+    }
+}
+
+impl<'m, M: Meter> Meter for TickCountingMeter<'m, M> {
+    fn enter_scope(&mut self, name: &str, scope: Scope) {
+        self.inner.enter_scope(name, scope)
+    }
+
+    fn transfer(&mut self, from: Scope, to: Scope, factor: f32) -> PartialVMResult<()> {
+        self.inner.transfer(from, to, factor)
+    }
+
+    fn add(&mut self, scope: Scope, ticks: u128) -> PartialVMResult<()> {
+        self.ticks = self.ticks.saturating_add(ticks);
+        self.inner.add(scope, ticks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bound_meter(max_per_fun_meter_units: u128) -> BoundMeter {
+        BoundMeter::new(&VerifierConfig {
+            max_per_fun_meter_units: Some(max_per_fun_meter_units),
+            max_per_mod_meter_units: None,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn tick_counting_meter_forwards_and_counts() {
+        let mut inner = bound_meter(1_000);
+        let mut meter = TickCountingMeter::new(&mut inner);
+
+        meter.enter_scope("f", Scope::Function);
+        meter.add(Scope::Function, 100).unwrap();
+        meter.add_items(Scope::Function, 10, 5).unwrap();
+        meter.add(Scope::Module, 7).unwrap();
+        assert_eq!(meter.ticks(), 157);
+
+        // Transfers move ticks between the inner meter's scopes, but charge nothing new.
+        meter.transfer(Scope::Function, Scope::Module, 1.0).unwrap();
+        assert_eq!(meter.ticks(), 157);
+
+        assert_eq!(inner.get_usage(Scope::Function), 150);
+    }
+
+    #[test]
+    fn tick_counting_meter_preserves_limits() {
+        let mut inner = bound_meter(100);
+        let mut meter = TickCountingMeter::new(&mut inner);
+
+        meter.enter_scope("f", Scope::Function);
+        meter.add(Scope::Function, 60).unwrap();
+        assert!(meter.add(Scope::Function, 60).is_err());
+
+        // Ticks that pushed the inner meter over its limit are still attributed.
+        assert_eq!(meter.ticks(), 120);
+    }
+
+    #[test]
+    fn tick_counting_meters_nest() {
+        let mut inner = DummyMeter;
+        let mut outer = TickCountingMeter::new(&mut inner);
+        {
+            let mut nested = TickCountingMeter::new(&mut outer);
+            nested.add(Scope::Function, 42).unwrap();
+            assert_eq!(nested.ticks(), 42);
+        }
+
+        outer.add(Scope::Module, 8).unwrap();
+        assert_eq!(outer.ticks(), 50);
+    }
+}
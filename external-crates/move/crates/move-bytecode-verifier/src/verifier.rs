@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! This module contains the public APIs supported by the bytecode verifier.
-use crate::meter::{DummyMeter, Meter};
+use crate::meter::{DummyMeter, Meter, TickCountingMeter};
 use crate::{
     ability_field_requirements, check_duplication::DuplicationChecker,
     code_unit_verifier::CodeUnitVerifier, constants, friends,
@@ -18,7 +18,7 @@ use move_binary_format::{
     file_format::{CompiledModule, CompiledScript},
 };
 use move_vm_config::verifier::VerifierConfig;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Helper for a "canonical" verification of a module.
 ///
@@ -71,23 +71,70 @@ pub fn verify_module_with_config_metered(
     module: &CompiledModule,
     meter: &mut impl Meter,
 ) -> VMResult<()> {
-    BoundsChecker::verify_module(module).map_err(|e| {
-        // We can't point the error at the module, because if bounds-checking
-        // failed, we cannot safely index into module's handle to itself.
-        e.finish(Location::Undefined)
+    verify_module_with_config_metered_observed(config, module, meter, &mut |_, _, _| ())
+}
+
+/// Like `verify_module_with_config_metered`, but calls `observer` after each pass that runs, with
+/// the pass's name, how long it took and how many ticks it charged to `meter`.
+pub fn verify_module_with_config_metered_observed<M: Meter>(
+    config: &VerifierConfig,
+    module: &CompiledModule,
+    meter: &mut M,
+    observer: &mut impl FnMut(&'static str, Duration, u128),
+) -> VMResult<()> {
+    observe_pass("bounds", meter, observer, |_| {
+        BoundsChecker::verify_module(module).map_err(|e| {
+            // We can't point the error at the module, because if bounds-checking
+            // failed, we cannot safely index into module's handle to itself.
+            e.finish(Location::Undefined)
+        })
+    })?;
+    observe_pass("limits", meter, observer, |_| {
+        LimitsVerifier::verify_module(config, module)
+    })?;
+    observe_pass("duplication", meter, observer, |_| {
+        DuplicationChecker::verify_module(module)
+    })?;
+    observe_pass("signatures", meter, observer, |_| {
+        SignatureChecker::verify_module(module)
+    })?;
+    observe_pass("instruction_consistency", meter, observer, |_| {
+        InstructionConsistency::verify_module(module)
     })?;
-    LimitsVerifier::verify_module(config, module)?;
-    DuplicationChecker::verify_module(module)?;
-    SignatureChecker::verify_module(module)?;
-    InstructionConsistency::verify_module(module)?;
-    constants::verify_module(module)?;
-    friends::verify_module(module)?;
-    ability_field_requirements::verify_module(module)?;
-    RecursiveStructDefChecker::verify_module(module)?;
-    InstantiationLoopChecker::verify_module(module)?;
-    CodeUnitVerifier::verify_module(config, module, meter)?;
+    observe_pass("constants", meter, observer, |_| {
+        constants::verify_module(module)
+    })?;
+    observe_pass("friends", meter, observer, |_| {
+        friends::verify_module(module)
+    })?;
+    observe_pass("ability_field_requirements", meter, observer, |_| {
+        ability_field_requirements::verify_module(module)
+    })?;
+    observe_pass("recursive_struct_defs", meter, observer, |_| {
+        RecursiveStructDefChecker::verify_module(module)
+    })?;
+    observe_pass("instantiation_loops", meter, observer, |_| {
+        InstantiationLoopChecker::verify_module(module)
+    })?;
+    observe_pass("code_units", meter, observer, |meter| {
+        CodeUnitVerifier::verify_module(config, module, meter)
+    })?;
+    observe_pass("script_signature", meter, observer, |_| {
+        script_signature::verify_module(module, no_additional_script_signature_checks)
+    })
+}
 
-    script_signature::verify_module(module, no_additional_script_signature_checks)
+fn observe_pass<M: Meter, T>(
+    name: &'static str,
+    meter: &mut M,
+    observer: &mut impl FnMut(&'static str, Duration, u128),
+    pass: impl FnOnce(&mut TickCountingMeter<M>) -> T,
+) -> T {
+    let mut meter = TickCountingMeter::new(meter);
+    let start = Instant::now();
+    let result = pass(&mut meter);
+    observer(name, start.elapsed(), meter.ticks());
+    result
 }
 
 pub fn verify_module_with_config_unmetered(
@@ -496,12 +496,30 @@ def generate_lib(output_file: TextIO):
                 executor.format(spc=spc, version=feature or version, cut=cut)
                 for (version, feature, cut) in cuts
             )
+        elif var == "NATIVE_COSTS_CUTS":
+            executor = (
+                "{spc}{version} => Arc::new({cut}::Executor::new_with_native_costs(\n"
+                "{spc}    protocol_config,\n"
+                "{spc}    native_costs,\n"
+                "{spc}    silent,\n"
+                "{spc})?),\n"
+            )
+            return "\n".join(
+                executor.format(spc=spc, version=feature or version, cut=cut)
+                for (version, feature, cut) in cuts
+            )
         elif var == "VERIFIER_CUTS":
             call = "Verifier::new(protocol_config, is_metered, metrics)"
             return "\n".join(
                 f"{spc}{feature or version} => Box::new({cut}::{call}),"
                 for (version, feature, cut) in cuts
             )
+        elif var == "DESERIALIZER_CUTS":
+            call = "deserialize_modules(protocol_config, module_bytes)"
+            return "\n".join(
+                f"{spc}{feature or version} => {cut}::{call},"
+                for (version, feature, cut) in cuts
+            )
         else:
             raise Exception(f"Don't know how to substitute {var}")
 
@@ -529,6 +547,8 @@ NOT_A_CUT = {
     "executor",
     "lib",
     "lib.template",
+    "native_costs",
+    "shadow",
     "tests",
     "verifier",
 }
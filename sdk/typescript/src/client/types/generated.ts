@@ -858,6 +858,8 @@ export type SuiEndOfEpochTransactionKind =
 			AuthenticatorStateExpire: SuiAuthenticatorStateExpire;
 	  };
 export interface SuiExecutionResult {
+	/** The gas used by the command, in gas units */
+	gasUsed?: string;
 	/** The value of any arguments that were mutably borrowed. Non-mut borrowed values are not included */
 	mutableReferenceOutputs?: [SuiArgument, number[], string][];
 	/** The return values from the transaction */
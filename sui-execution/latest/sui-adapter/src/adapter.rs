@@ -5,13 +5,17 @@ pub use checked::*;
 
 #[sui_macros::with_checked_arithmetic]
 mod checked {
-    use std::{collections::BTreeMap, sync::Arc};
+    use std::{collections::BTreeMap, sync::Arc, time::Duration};
 
     use anyhow::Result;
-    use move_binary_format::{access::ModuleAccess, file_format::CompiledModule};
+    use move_binary_format::{
+        access::ModuleAccess,
+        errors::{Location, VMResult},
+        file_format::CompiledModule,
+    };
     use move_bytecode_verifier::meter::Meter;
-    use move_bytecode_verifier::verify_module_with_config_metered;
-    use move_core_types::account_address::AccountAddress;
+    use move_bytecode_verifier::verify_module_with_config_metered_observed;
+    use move_core_types::{account_address::AccountAddress, language_storage::ModuleId};
     use move_vm_config::{
         runtime::{VMConfig, VMRuntimeLimitsConfig},
         verifier::VerifierConfig,
@@ -21,7 +25,9 @@ mod checked {
         native_functions::NativeFunctionTable,
     };
     use sui_move_natives::object_runtime;
-    use sui_types::metrics::BytecodeVerifierMetrics;
+    use sui_types::metrics::{
+        BytecodeVerifierMetrics, ModuleVerifierReport, VerifierOutcome, VerifierPassReport,
+    };
     use sui_verifier::check_for_verifier_timeout;
     use tracing::instrument;
 
@@ -34,7 +40,7 @@ mod checked {
         metrics::LimitsMetrics,
         storage::ChildObjectResolver,
     };
-    use sui_verifier::verifier::sui_verify_module_metered_check_timeout_only;
+    use sui_verifier::verifier::sui_verify_module_metered_observed;
 
     pub fn default_verifier_config(
         protocol_config: &ProtocolConfig,
@@ -109,6 +115,44 @@ mod checked {
         .map_err(|_| SuiError::ExecutionInvariantViolation)
     }
 
+    /// Deserialize `module_bytes` as the modules of a package, using the binary format settings
+    /// that execution uses under `protocol_config`, but without running the bytecode verifier.
+    pub fn deserialize_modules(
+        protocol_config: &ProtocolConfig,
+        module_bytes: &[Vec<u8>],
+    ) -> Result<Vec<CompiledModule>, SuiError> {
+        if module_bytes.is_empty() {
+            return Err(SuiError::ModuleDeserializationFailure {
+                error: "Package must contain at least one module".to_string(),
+            });
+        }
+
+        deserialize_module_bytes(protocol_config, module_bytes).map_err(|e| {
+            SuiError::ModuleDeserializationFailure {
+                error: e.to_string(),
+            }
+        })
+    }
+
+    /// Deserialize each of `module_bytes` with the binary format settings that execution uses
+    /// under `protocol_config`. Shared by publishing and upgrading, and `deserialize_modules`.
+    pub fn deserialize_module_bytes(
+        protocol_config: &ProtocolConfig,
+        module_bytes: &[Vec<u8>],
+    ) -> VMResult<Vec<CompiledModule>> {
+        module_bytes
+            .iter()
+            .map(|b| {
+                CompiledModule::deserialize_with_config(
+                    b,
+                    protocol_config.move_binary_format_version(),
+                    protocol_config.no_extraneous_module_bytes(),
+                )
+                .map_err(|e| e.finish(Location::Undefined))
+            })
+            .collect()
+    }
+
     pub fn new_native_extensions<'r>(
         child_resolver: &'r dyn ChildObjectResolver,
         input_objects: BTreeMap<ObjectID, object_runtime::InputObject>,
@@ -183,6 +227,26 @@ mod checked {
         verifier_config: &VerifierConfig,
         meter: &mut impl Meter,
         metrics: &Arc<BytecodeVerifierMetrics>,
+    ) -> Result<(), SuiError> {
+        run_metered_move_bytecode_verifier_with_report(
+            modules,
+            verifier_config,
+            meter,
+            metrics,
+            &mut vec![],
+        )
+    }
+
+    /// Like `run_metered_move_bytecode_verifier`, but additionally appends a breakdown of the time
+    /// and ticks spent in each verifier pass to `reports`, one entry per module that was
+    /// verified (including a module that timed out).
+    #[instrument(level = "trace", skip_all)]
+    pub fn run_metered_move_bytecode_verifier_with_report(
+        modules: &[CompiledModule],
+        verifier_config: &VerifierConfig,
+        meter: &mut impl Meter,
+        metrics: &Arc<BytecodeVerifierMetrics>,
+        reports: &mut Vec<ModuleVerifierReport>,
     ) -> Result<(), SuiError> {
         // run the Move verifier
         for module in modules.iter() {
@@ -190,9 +254,27 @@ mod checked {
                 .verifier_runtime_per_module_success_latency
                 .start_timer();
 
-            if let Err(e) = verify_module_with_config_metered(verifier_config, module, meter) {
+            let mut passes = vec![];
+            let mut observe = |pass: &'static str, duration: Duration, ticks: u128| {
+                let pass = VerifierPassReport {
+                    pass,
+                    duration,
+                    ticks,
+                };
+                metrics.observe_pass(&pass);
+                passes.push(pass);
+            };
+
+            let move_result = verify_module_with_config_metered_observed(
+                verifier_config,
+                module,
+                meter,
+                &mut observe,
+            );
+
+            let outcome = match move_result {
                 // Check that the status indicates mtering timeout
-                if check_for_verifier_timeout(&e.major_status()) {
+                Err(e) if check_for_verifier_timeout(&e.major_status()) => {
                     // Discard success timer, but record timeout/failure timer
                     metrics
                         .verifier_runtime_per_module_timeout_latency
@@ -204,27 +286,56 @@ mod checked {
                             BytecodeVerifierMetrics::TIMEOUT_TAG,
                         ])
                         .inc();
+                    record_module_report(
+                        metrics,
+                        reports,
+                        module,
+                        passes,
+                        VerifierOutcome::Timeout,
+                    );
                     return Err(SuiError::ModuleVerificationFailure {
                         error: format!("Verification timedout: {}", e),
                     });
-                };
-            } else if let Err(err) =
-                sui_verify_module_metered_check_timeout_only(module, &BTreeMap::new(), meter)
-            {
-                // We only checked that the failure was due to timeout
-                // Discard success timer, but record timeout/failure timer
-                metrics
-                    .verifier_runtime_per_module_timeout_latency
-                    .observe(per_module_meter_verifier_timer.stop_and_discard());
-                metrics
-                    .verifier_timeout_metrics
-                    .with_label_values(&[
-                        BytecodeVerifierMetrics::SUI_VERIFIER_TAG,
-                        BytecodeVerifierMetrics::TIMEOUT_TAG,
-                    ])
-                    .inc();
-                return Err(err.into());
-            }
+                }
+                Err(_) => VerifierOutcome::Failure,
+                Ok(()) => match sui_verify_module_metered_observed(
+                    module,
+                    &BTreeMap::new(),
+                    meter,
+                    &mut observe,
+                ) {
+                    Ok(()) => VerifierOutcome::Success,
+                    Err(err)
+                        if matches!(
+                            err.kind(),
+                            ExecutionErrorKind::SuiMoveVerificationTimedout
+                        ) =>
+                    {
+                        // Discard success timer, but record timeout/failure timer
+                        metrics
+                            .verifier_runtime_per_module_timeout_latency
+                            .observe(per_module_meter_verifier_timer.stop_and_discard());
+                        metrics
+                            .verifier_timeout_metrics
+                            .with_label_values(&[
+                                BytecodeVerifierMetrics::SUI_VERIFIER_TAG,
+                                BytecodeVerifierMetrics::TIMEOUT_TAG,
+                            ])
+                            .inc();
+                        record_module_report(
+                            metrics,
+                            reports,
+                            module,
+                            passes,
+                            VerifierOutcome::Timeout,
+                        );
+                        return Err(err.into());
+                    }
+                    // Failures other than timeouts are not reported by this function
+                    Err(_) => VerifierOutcome::Failure,
+                },
+            };
+
             // Save the success timer
             per_module_meter_verifier_timer.stop_and_record();
             metrics
@@ -234,7 +345,33 @@ mod checked {
                     BytecodeVerifierMetrics::SUCCESS_TAG,
                 ])
                 .inc();
+            record_module_report(metrics, reports, module, passes, outcome);
         }
         Ok(())
     }
+
+    fn record_module_report(
+        metrics: &BytecodeVerifierMetrics,
+        reports: &mut Vec<ModuleVerifierReport>,
+        module: &CompiledModule,
+        passes: Vec<VerifierPassReport>,
+        outcome: VerifierOutcome,
+    ) {
+        metrics.observe_outcome(outcome);
+        reports.push(ModuleVerifierReport {
+            module: checked_self_id(module),
+            passes,
+            outcome,
+        });
+    }
+
+    /// Like `CompiledModule::self_id`, but for modules that have not passed bounds checks.
+    fn checked_self_id(module: &CompiledModule) -> Option<ModuleId> {
+        let handle = module
+            .module_handles
+            .get(module.self_module_handle_idx.0 as usize)?;
+        let address = module.address_identifiers.get(handle.address.0 as usize)?;
+        let name = module.identifiers.get(handle.name.0 as usize)?;
+        Some(ModuleId::new(*address, name.clone()))
+    }
 }
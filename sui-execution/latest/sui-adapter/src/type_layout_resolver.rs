@@ -38,9 +38,14 @@ impl<'state, 'vm> LayoutResolver for TypeLayoutResolver<'state, 'vm> {
         &mut self,
         object: &MoveObject,
     ) -> Result<A::MoveStructLayout, SuiError> {
-        let struct_tag: StructTag = object.type_().clone().into();
-        let Ok(ty) = load_type_from_struct(self.vm, &mut self.linkage_view, &[], &struct_tag)
-        else {
+        self.get_annotated_struct_layout(&object.type_().clone().into())
+    }
+
+    fn get_annotated_struct_layout(
+        &mut self,
+        struct_tag: &StructTag,
+    ) -> Result<A::MoveStructLayout, SuiError> {
+        let Ok(ty) = load_type_from_struct(self.vm, &mut self.linkage_view, &[], struct_tag) else {
             return Err(SuiError::FailObjectLayout {
                 st: format!("{}", struct_tag),
             });
@@ -21,7 +21,7 @@ pub const INVALID_ECVRF_PROOF: u64 = 3;
 
 const ECVRF_SHA512_BLOCK_SIZE: usize = 128;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct EcvrfEcvrfVerifyCostParams {
     /// Base cost for invoking the `ecvrf_verify`
     pub ecvrf_ecvrf_verify_cost_base: InternalGas,
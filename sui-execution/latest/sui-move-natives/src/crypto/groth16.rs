@@ -24,7 +24,7 @@ pub const BN254: u8 = 1;
 // We need to set an upper bound on the number of public inputs to avoid a DoS attack
 pub const MAX_PUBLIC_INPUTS: usize = 8;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct Groth16PrepareVerifyingKeyCostParams {
     pub groth16_prepare_verifying_key_bls12381_cost_base: InternalGas,
     pub groth16_prepare_verifying_key_bn254_cost_base: InternalGas,
@@ -100,7 +100,7 @@ pub fn prepare_verifying_key_internal(
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct Groth16VerifyGroth16ProofInternalCostParams {
     pub groth16_verify_groth16_proof_internal_bls12381_cost_base: InternalGas,
     pub groth16_verify_groth16_proof_internal_bls12381_cost_per_public_input: InternalGas,
@@ -53,7 +53,7 @@ fn hash<H: HashFunction<DIGEST_SIZE>, const DIGEST_SIZE: usize>(
     ))
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct HashKeccak256CostParams {
     /// Base cost for invoking the `blake2b256` function
     pub hash_keccak256_cost_base: InternalGas,
@@ -94,7 +94,7 @@ pub fn keccak256(
     )
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct HashBlake2b256CostParams {
     /// Base cost for invoking the `blake2b256` function
     pub hash_blake2b256_cost_base: InternalGas,
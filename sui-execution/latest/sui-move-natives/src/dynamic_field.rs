@@ -62,7 +62,7 @@ macro_rules! get_or_fetch_object {
     }};
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct DynamicFieldHashTypeAndKeyCostParams {
     pub dynamic_field_hash_type_and_key_cost_base: InternalGas,
     pub dynamic_field_hash_type_and_key_type_cost_per_byte: InternalGas,
@@ -142,7 +142,7 @@ pub fn hash_type_and_key(
     Ok(NativeResult::ok(cost, smallvec![Value::address(id.into())]))
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct DynamicFieldAddChildObjectCostParams {
     pub dynamic_field_add_child_object_cost_base: InternalGas,
     pub dynamic_field_add_child_object_type_cost_per_byte: InternalGas,
@@ -239,7 +239,7 @@ pub fn add_child_object(
     Ok(NativeResult::ok(context.gas_used(), smallvec![]))
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct DynamicFieldBorrowChildObjectCostParams {
     pub dynamic_field_borrow_child_object_cost_base: InternalGas,
     pub dynamic_field_borrow_child_object_child_ref_cost_per_byte: InternalGas,
@@ -317,7 +317,7 @@ pub fn borrow_child_object(
     Ok(NativeResult::ok(context.gas_used(), smallvec![child_ref]))
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct DynamicFieldRemoveChildObjectCostParams {
     pub dynamic_field_remove_child_object_cost_base: InternalGas,
     pub dynamic_field_remove_child_object_child_cost_per_byte: InternalGas,
@@ -386,7 +386,7 @@ pub fn remove_child_object(
     Ok(NativeResult::ok(context.gas_used(), smallvec![child]))
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct DynamicFieldHasChildObjectCostParams {
     // All inputs are constant same size. No need for special costing as this is a lookup
     pub dynamic_field_has_child_object_cost_base: InternalGas,
@@ -425,7 +425,7 @@ pub fn has_child_object(
     ))
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct DynamicFieldHasChildObjectWithTyCostParams {
     pub dynamic_field_has_child_object_with_ty_cost_base: InternalGas,
     pub dynamic_field_has_child_object_with_ty_type_cost_per_byte: InternalGas,
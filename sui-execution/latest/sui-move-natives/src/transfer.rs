@@ -29,7 +29,7 @@ const E_RECEIVING_OBJECT_TYPE_MISMATCH: u64 = 2;
 // able to be accessed through the parent that is passed-in.
 const E_UNABLE_TO_RECEIVE_OBJECT: u64 = 3;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TransferReceiveObjectInternalCostParams {
     pub transfer_receive_object_internal_cost_base: InternalGas,
 }
@@ -104,7 +104,7 @@ pub fn receive_object_internal(
     Ok(NativeResult::ok(context.gas_used(), smallvec![child]))
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TransferInternalCostParams {
     pub transfer_transfer_internal_cost_base: InternalGas,
 }
@@ -142,7 +142,7 @@ pub fn transfer_internal(
     Ok(NativeResult::ok(cost, smallvec![]))
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TransferFreezeObjectCostParams {
     pub transfer_freeze_object_cost_base: InternalGas,
 }
@@ -178,7 +178,7 @@ pub fn freeze_object(
     Ok(NativeResult::ok(context.gas_used(), smallvec![]))
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TransferShareObjectCostParams {
     pub transfer_share_object_cost_base: InternalGas,
 }
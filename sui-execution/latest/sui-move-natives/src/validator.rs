@@ -12,7 +12,7 @@ use smallvec::smallvec;
 use std::collections::VecDeque;
 use sui_types::sui_system_state::sui_system_state_inner_v1::ValidatorMetadataV1;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ValidatorValidateMetadataBcsCostParams {
     pub validator_validate_metadata_cost_base: InternalGas,
     pub validator_validate_metadata_data_cost_per_byte: InternalGas,
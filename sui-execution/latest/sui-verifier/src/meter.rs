@@ -6,6 +6,8 @@ use move_bytecode_verifier::meter::{Meter, Scope};
 use move_core_types::vm_status::StatusCode;
 use move_vm_config::verifier::VerifierConfig;
 
+pub use move_bytecode_verifier::meter::TickCountingMeter;
+
 struct SuiVerifierMeterBounds {
     name: String,
     ticks: u128,
@@ -3,12 +3,15 @@
 
 //! This module contains the public APIs supported by the bytecode verifier.
 
+use std::time::{Duration, Instant};
+
 use move_binary_format::file_format::CompiledModule;
 use sui_types::{error::ExecutionError, move_package::FnInfoMap};
 
 use crate::{
     entry_points_verifier, global_storage_access_verifier, id_leak_verifier,
-    one_time_witness_verifier, private_generics, struct_with_key_verifier,
+    meter::TickCountingMeter, one_time_witness_verifier, private_generics,
+    struct_with_key_verifier,
 };
 use move_bytecode_verifier::meter::DummyMeter;
 use move_bytecode_verifier::meter::Meter;
@@ -19,12 +22,48 @@ pub fn sui_verify_module_metered(
     fn_info_map: &FnInfoMap,
     meter: &mut impl Meter,
 ) -> Result<(), ExecutionError> {
-    struct_with_key_verifier::verify_module(module)?;
-    global_storage_access_verifier::verify_module(module)?;
-    id_leak_verifier::verify_module(module, meter)?;
-    private_generics::verify_module(module)?;
-    entry_points_verifier::verify_module(module, fn_info_map)?;
-    one_time_witness_verifier::verify_module(module, fn_info_map)
+    sui_verify_module_metered_observed(module, fn_info_map, meter, &mut |_, _, _| ())
+}
+
+/// Like `sui_verify_module_metered`, but calls `observer` after each pass that runs, with the
+/// pass's name, how long it took and how many ticks it charged to `meter`.
+pub fn sui_verify_module_metered_observed(
+    module: &CompiledModule,
+    fn_info_map: &FnInfoMap,
+    meter: &mut impl Meter,
+    observer: &mut impl FnMut(&'static str, Duration, u128),
+) -> Result<(), ExecutionError> {
+    observe_pass("struct_with_key", meter, observer, |_| {
+        struct_with_key_verifier::verify_module(module)
+    })?;
+    observe_pass("global_storage_access", meter, observer, |_| {
+        global_storage_access_verifier::verify_module(module)
+    })?;
+    observe_pass("id_leak", meter, observer, |meter| {
+        id_leak_verifier::verify_module(module, meter)
+    })?;
+    observe_pass("private_generics", meter, observer, |_| {
+        private_generics::verify_module(module)
+    })?;
+    observe_pass("entry_points", meter, observer, |_| {
+        entry_points_verifier::verify_module(module, fn_info_map)
+    })?;
+    observe_pass("one_time_witness", meter, observer, |_| {
+        one_time_witness_verifier::verify_module(module, fn_info_map)
+    })
+}
+
+fn observe_pass<M: Meter, T>(
+    name: &'static str,
+    meter: &mut M,
+    observer: &mut impl FnMut(&'static str, Duration, u128),
+    pass: impl FnOnce(&mut TickCountingMeter<M>) -> T,
+) -> T {
+    let mut meter = TickCountingMeter::new(meter);
+    let start = Instant::now();
+    let result = pass(&mut meter);
+    observer(name, start.elapsed(), meter.ticks());
+    result
 }
 
 /// Runs the Sui verifier and checks if the error counts as a Sui verifier timeout
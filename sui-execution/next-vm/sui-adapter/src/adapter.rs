@@ -8,7 +8,11 @@ mod checked {
     use std::{collections::BTreeMap, sync::Arc};
 
     use anyhow::Result;
-    use move_binary_format::{access::ModuleAccess, file_format::CompiledModule};
+    use move_binary_format::{
+        access::ModuleAccess,
+        errors::{Location, VMResult},
+        file_format::CompiledModule,
+    };
     use move_bytecode_verifier::meter::Meter;
     use move_bytecode_verifier::verify_module_with_config_metered;
     use move_core_types::account_address::AccountAddress;
@@ -109,6 +113,44 @@ mod checked {
         .map_err(|_| SuiError::ExecutionInvariantViolation)
     }
 
+    /// Deserialize `module_bytes` as the modules of a package, using the binary format settings
+    /// that execution uses under `protocol_config`, but without running the bytecode verifier.
+    pub fn deserialize_modules(
+        protocol_config: &ProtocolConfig,
+        module_bytes: &[Vec<u8>],
+    ) -> Result<Vec<CompiledModule>, SuiError> {
+        if module_bytes.is_empty() {
+            return Err(SuiError::ModuleDeserializationFailure {
+                error: "Package must contain at least one module".to_string(),
+            });
+        }
+
+        deserialize_module_bytes(protocol_config, module_bytes).map_err(|e| {
+            SuiError::ModuleDeserializationFailure {
+                error: e.to_string(),
+            }
+        })
+    }
+
+    /// Deserialize each of `module_bytes` with the binary format settings that execution uses
+    /// under `protocol_config`. Shared by publishing and upgrading, and `deserialize_modules`.
+    pub fn deserialize_module_bytes(
+        protocol_config: &ProtocolConfig,
+        module_bytes: &[Vec<u8>],
+    ) -> VMResult<Vec<CompiledModule>> {
+        module_bytes
+            .iter()
+            .map(|b| {
+                CompiledModule::deserialize_with_config(
+                    b,
+                    protocol_config.move_binary_format_version(),
+                    protocol_config.no_extraneous_module_bytes(),
+                )
+                .map_err(|e| e.finish(Location::Undefined))
+            })
+            .collect()
+    }
+
     pub fn new_native_extensions<'r>(
         child_resolver: &'r dyn ChildObjectResolver,
         input_objects: BTreeMap<ObjectID, object_runtime::InputObject>,
@@ -63,7 +63,7 @@ mod checked {
     };
     use tracing::instrument;
 
-    use crate::adapter::substitute_package_id;
+    use crate::adapter::{deserialize_module_bytes, substitute_package_id};
     use crate::programmable_transactions::context::*;
 
     pub fn execute<Mode: ExecutionMode>(
@@ -120,6 +120,10 @@ mod checked {
         mode_results: &mut Mode::ExecutionResults,
         command: Command,
     ) -> Result<(), ExecutionError> {
+        let gas_used_before = context
+            .gas_charger
+            .move_gas_status()
+            .gas_used_pre_gas_price();
         let mut argument_updates = Mode::empty_arguments();
         let results = match command {
             Command::MakeMoveVec(tag_opt, args) if args.is_empty() => {
@@ -319,7 +323,12 @@ mod checked {
             }
         };
 
-        Mode::finish_command(context, mode_results, argument_updates, &results)?;
+        let gas_used = context
+            .gas_charger
+            .move_gas_status()
+            .gas_used_pre_gas_price()
+            .saturating_sub(gas_used_before);
+        Mode::finish_command(context, mode_results, argument_updates, &results, gas_used)?;
         context.push_command_results(results)?;
         Ok(())
     }
@@ -789,17 +798,7 @@ mod checked {
         context: &mut ExecutionContext<'_, '_, '_>,
         module_bytes: &[Vec<u8>],
     ) -> Result<Vec<CompiledModule>, ExecutionError> {
-        let modules = module_bytes
-            .iter()
-            .map(|b| {
-                CompiledModule::deserialize_with_config(
-                    b,
-                    context.protocol_config.move_binary_format_version(),
-                    context.protocol_config.no_extraneous_module_bytes(),
-                )
-                .map_err(|e| e.finish(Location::Undefined))
-            })
-            .collect::<VMResult<Vec<CompiledModule>>>()
+        let modules = deserialize_module_bytes(context.protocol_config, module_bytes)
             .map_err(|e| context.convert_vm_error(e))?;
 
         assert_invariant!(
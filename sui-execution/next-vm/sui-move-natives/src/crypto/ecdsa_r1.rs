@@ -32,7 +32,7 @@ pub const SHA256: u8 = 1;
 const KECCAK256_BLOCK_SIZE: usize = 136;
 const SHA256_BLOCK_SIZE: usize = 64;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct EcdsaR1EcrecoverCostParams {
     /// Base cost for invoking the `ecrecover` function with `hash=0` implying KECCAK256
     pub ecdsa_r1_ecrecover_keccak256_cost_base: InternalGas,
@@ -139,7 +139,7 @@ pub fn ecrecover(
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct EcdsaR1Secp256R1VerifyCostParams {
     /// Base cost for invoking the `secp256r1_verify` function with `hash=0` implying KECCAK256
     pub ecdsa_r1_secp256r1_verify_keccak256_cost_base: InternalGas,
@@ -13,7 +13,7 @@ use sui_types::base_types::{ObjectID, TransactionDigest};
 
 use crate::{object_runtime::ObjectRuntime, NativesCostTable};
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct TxContextDeriveIdCostParams {
     pub tx_context_derive_id_cost_base: InternalGas,
 }
@@ -2,23 +2,121 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{collections::HashSet, sync::Arc};
-use sui_protocol_config::ProtocolConfig;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
 use sui_types::storage::BackingStore;
 use sui_types::{
-    base_types::{ObjectRef, SuiAddress, TxContext},
+    base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress, TxContext},
     committee::EpochId,
     digests::TransactionDigest,
     effects::TransactionEffects,
-    error::ExecutionError,
+    error::{ExecutionError, SuiResult},
     execution::TypeLayoutStore,
-    execution_mode::ExecutionResult,
-    gas::SuiGasStatus,
+    execution_mode::CommandResult,
+    gas::{GasCostSummary, SuiGasStatus},
+    gas_model::gas_predicates::txn_base_cost_as_multiplier,
     inner_temporary_store::InnerTemporaryStore,
     metrics::LimitsMetrics,
-    transaction::{CheckedInputObjects, ProgrammableTransaction, TransactionKind},
+    transaction::{
+        CheckedInputObjects, EndOfEpochTransactionKind, ProgrammableTransaction, TransactionKind,
+        VerifiedTransaction,
+    },
     type_resolver::LayoutResolver,
 };
 
+/// The parameters that gas charges are computed from, for a given protocol version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GasParameters {
+    pub gas_model_version: u64,
+    /// Flat fee charged for every transaction, the minimum computation cost.
+    pub base_tx_cost_fixed: u64,
+    /// Whether `base_tx_cost_fixed` is scaled by the reference gas price.
+    pub base_tx_cost_as_multiplier: bool,
+    /// Computation cost is rounded up to buckets, the largest of which is this many units.
+    pub max_gas_computation_bucket: u64,
+    pub object_read_per_byte_cost: u64,
+    pub package_publish_per_byte_cost: u64,
+    pub storage_per_byte_cost: u64,
+    pub storage_gas_price: u64,
+    /// Basis points of an object's storage cost that are refunded when it is mutated or deleted.
+    pub storage_rebate_rate: u64,
+    pub max_tx_gas: u64,
+    pub max_gas_price: u64,
+}
+
+/// Outcome of executing a transaction to estimate its gas usage, without committing its effects.
+/// The gas charged is in the effects' gas cost summary, and if execution failed, its status says
+/// why (gas is still charged for failed transactions).
+#[derive(Debug)]
+pub struct GasEstimate {
+    /// Effects of the dry run, and the objects and events it would have written.
+    pub effects: TransactionEffects,
+    pub inner_temporary_store: InnerTemporaryStore,
+}
+
+/// Everything needed to build the transaction that advances the network to the next epoch.
+#[derive(Clone, Debug)]
+pub struct ChangeEpochParams {
+    pub next_epoch: EpochId,
+    pub next_protocol_version: ProtocolVersion,
+    /// Gas charged over the course of the epoch that is ending.
+    pub gas_cost_summary: GasCostSummary,
+    pub epoch_start_timestamp_ms: u64,
+    /// The system packages to upgrade to, as (version, module bytes, dependencies).
+    pub system_packages: Vec<(SequenceNumber, Vec<Vec<u8>>, Vec<ObjectID>)>,
+}
+
+impl ChangeEpochParams {
+    /// A standalone change epoch transaction, for versions that predate end-of-epoch
+    /// transactions.
+    pub(crate) fn into_change_epoch_transaction(self) -> VerifiedTransaction {
+        let ChangeEpochParams {
+            next_epoch,
+            next_protocol_version,
+            gas_cost_summary,
+            epoch_start_timestamp_ms,
+            system_packages,
+        } = self;
+
+        VerifiedTransaction::new_change_epoch(
+            next_epoch,
+            next_protocol_version,
+            gas_cost_summary.storage_cost,
+            gas_cost_summary.computation_cost,
+            gas_cost_summary.storage_rebate,
+            gas_cost_summary.non_refundable_storage_fee,
+            epoch_start_timestamp_ms,
+            system_packages,
+        )
+    }
+
+    /// An end-of-epoch transaction that runs `prelude` and then changes the epoch.
+    pub(crate) fn into_end_of_epoch_transaction(
+        self,
+        mut prelude: Vec<EndOfEpochTransactionKind>,
+    ) -> VerifiedTransaction {
+        let ChangeEpochParams {
+            next_epoch,
+            next_protocol_version,
+            gas_cost_summary,
+            epoch_start_timestamp_ms,
+            system_packages,
+        } = self;
+
+        prelude.push(EndOfEpochTransactionKind::new_change_epoch(
+            next_epoch,
+            next_protocol_version,
+            gas_cost_summary.storage_cost,
+            gas_cost_summary.computation_cost,
+            gas_cost_summary.storage_rebate,
+            gas_cost_summary.non_refundable_storage_fee,
+            epoch_start_timestamp_ms,
+            system_packages,
+        ));
+
+        VerifiedTransaction::new_end_of_epoch_transaction(prelude)
+    }
+}
+
 /// Abstracts over access to the VM across versions of the execution layer.
 pub trait Executor {
     fn execute_transaction_to_effects(
@@ -70,7 +168,7 @@ pub trait Executor {
     ) -> (
         InnerTemporaryStore,
         TransactionEffects,
-        Result<Vec<ExecutionResult>, ExecutionError>,
+        Result<Vec<CommandResult>, ExecutionError>,
     );
 
     fn update_genesis_state(
@@ -90,4 +188,89 @@ pub trait Executor {
         &'vm self,
         store: Box<dyn TypeLayoutStore + 'store>,
     ) -> Box<dyn LayoutResolver + 'r>;
+
+    fn reference_gas_parameters(&self, protocol_config: &ProtocolConfig) -> GasParameters {
+        GasParameters {
+            gas_model_version: protocol_config.gas_model_version(),
+            base_tx_cost_fixed: protocol_config.base_tx_cost_fixed(),
+            base_tx_cost_as_multiplier: txn_base_cost_as_multiplier(protocol_config),
+            max_gas_computation_bucket: protocol_config.max_gas_computation_bucket(),
+            object_read_per_byte_cost: protocol_config.obj_access_cost_read_per_byte(),
+            package_publish_per_byte_cost: protocol_config.package_publish_cost_per_byte(),
+            storage_per_byte_cost: protocol_config.obj_data_cost_refundable(),
+            storage_gas_price: protocol_config.storage_gas_price(),
+            storage_rebate_rate: protocol_config.storage_rebate_rate(),
+            max_tx_gas: protocol_config.max_tx_gas(),
+            max_gas_price: protocol_config.max_gas_price(),
+        }
+    }
+
+    /// Execute `transaction_kind` against `store` as a dry run -- the resulting writes are
+    /// discarded -- and report the gas it was charged. `input_objects` and `gas_status` are
+    /// expected to come from the same input checks that precede a real execution. Gas is only
+    /// charged to `gas_coins`, which may be a mock coin standing in for the transaction's own gas
+    /// payment.
+    fn estimate_gas_for_transaction(
+        &self,
+        store: &dyn BackingStore,
+        // Configuration
+        protocol_config: &ProtocolConfig,
+        metrics: Arc<LimitsMetrics>,
+        certificate_deny_set: &HashSet<TransactionDigest>,
+        // Epoch
+        epoch_id: &EpochId,
+        epoch_timestamp_ms: u64,
+        // Transaction Inputs
+        input_objects: CheckedInputObjects,
+        // Gas related
+        gas_coins: Vec<ObjectRef>,
+        gas_status: SuiGasStatus,
+        // Transaction
+        transaction_kind: TransactionKind,
+        transaction_signer: SuiAddress,
+        transaction_digest: TransactionDigest,
+    ) -> GasEstimate {
+        let (inner_temporary_store, effects, _) = self.execute_transaction_to_effects(
+            store,
+            protocol_config,
+            metrics,
+            /* enable_expensive_checks */ false,
+            certificate_deny_set,
+            epoch_id,
+            epoch_timestamp_ms,
+            input_objects,
+            gas_coins,
+            gas_status,
+            transaction_kind,
+            transaction_signer,
+            transaction_digest,
+        );
+
+        GasEstimate {
+            effects,
+            inner_temporary_store,
+        }
+    }
+
+    /// Build the transaction that ends the current epoch. `prelude` holds the end-of-epoch
+    /// transactions to run before the epoch changes (e.g. creating system state objects). Where
+    /// end-of-epoch transactions are not supported (by this version of the execution layer, or
+    /// under `protocol_config`), the prelude is dropped, and a standalone change epoch transaction
+    /// is built instead.
+    fn advance_epoch_transaction(
+        &self,
+        protocol_config: &ProtocolConfig,
+        prelude: Vec<EndOfEpochTransactionKind>,
+        params: ChangeEpochParams,
+    ) -> VerifiedTransaction;
+
+    /// Build the transaction that writes the randomness generated for `randomness_round` to the
+    /// randomness state object, failing if this version of the execution layer cannot execute it.
+    fn randomness_state_update_transaction(
+        &self,
+        epoch: EpochId,
+        randomness_round: u64,
+        random_bytes: Vec<u8>,
+        randomness_obj_initial_shared_version: SequenceNumber,
+    ) -> SuiResult<VerifiedTransaction>;
 }
@@ -7,51 +7,87 @@ use move_binary_format::CompiledModule;
 use move_vm_config::verifier::VerifierConfig;
 use sui_protocol_config::ProtocolConfig;
 use sui_types::{
-    base_types::{ObjectRef, SuiAddress, TxContext},
+    base_types::{ObjectRef, SequenceNumber, SuiAddress, TxContext},
     committee::EpochId,
     digests::TransactionDigest,
     effects::TransactionEffects,
     error::{ExecutionError, SuiError, SuiResult},
     execution::TypeLayoutStore,
-    execution_mode::{self, ExecutionResult},
+    execution_mode::{self, CommandResult},
     gas::SuiGasStatus,
     inner_temporary_store::InnerTemporaryStore,
-    metrics::{BytecodeVerifierMetrics, LimitsMetrics},
-    transaction::{CheckedInputObjects, ProgrammableTransaction, TransactionKind},
+    metrics::{BytecodeVerifierMetrics, LimitsMetrics, ModuleVerifierReport},
+    transaction::{
+        CheckedInputObjects, EndOfEpochTransactionKind, ProgrammableTransaction, TransactionKind,
+        VerifiedTransaction,
+    },
     type_resolver::LayoutResolver,
 };
 
 use move_bytecode_verifier_latest::meter::Scope;
 use move_vm_runtime_latest::move_vm::MoveVM;
 use sui_adapter_latest::adapter::{
-    default_verifier_config, new_move_vm, run_metered_move_bytecode_verifier,
+    self, default_verifier_config, new_move_vm, run_metered_move_bytecode_verifier_with_report,
 };
 use sui_adapter_latest::execution_engine::{
     execute_genesis_state_update, execute_transaction_to_effects,
 };
 use sui_adapter_latest::type_layout_resolver::TypeLayoutResolver;
 use sui_move_natives_latest::all_natives;
+#[cfg(feature = "native-cost-overrides")]
+use sui_move_natives_latest::NativesCostTable;
 use sui_types::storage::BackingStore;
 use sui_verifier_latest::meter::SuiVerifierMeter;
 
-use crate::executor;
+use crate::executor::{self, ChangeEpochParams};
+#[cfg(feature = "native-cost-overrides")]
+use crate::native_costs::NativeCostOverrides;
 use crate::verifier;
 use crate::verifier::{VerifierMeteredValues, VerifierOverrides};
 
-pub(crate) struct Executor(Arc<MoveVM>);
+/// The Move VM, and (if native function costs are overridden) the protocol config to execute under,
+/// with the overridden costs applied.
+pub(crate) struct Executor(Arc<MoveVM>, Option<ProtocolConfig>);
 
 pub(crate) struct Verifier<'m> {
     config: VerifierConfig,
     metrics: &'m Arc<BytecodeVerifierMetrics>,
     meter: SuiVerifierMeter,
+    report: Vec<ModuleVerifierReport>,
 }
 
 impl Executor {
     pub(crate) fn new(protocol_config: &ProtocolConfig, silent: bool) -> Result<Self, SuiError> {
-        Ok(Executor(Arc::new(new_move_vm(
-            all_natives(silent),
-            protocol_config,
-        )?)))
+        Ok(Executor(
+            Arc::new(new_move_vm(all_natives(silent), protocol_config)?),
+            None,
+        ))
+    }
+
+    /// Like `new`, but charging for native functions according to `native_costs`, on top of the
+    /// costs set by `protocol_config`.
+    #[cfg(feature = "native-cost-overrides")]
+    pub(crate) fn new_with_native_costs(
+        protocol_config: &ProtocolConfig,
+        native_costs: &NativeCostOverrides,
+        silent: bool,
+    ) -> Result<Self, SuiError> {
+        let protocol_config = native_costs
+            .protocol_config(protocol_config, NativesCostTable::from_protocol_config)?;
+        Ok(Executor(
+            Arc::new(new_move_vm(all_natives(silent), &protocol_config)?),
+            Some(protocol_config),
+        ))
+    }
+
+    /// The protocol config to execute under: `protocol_config`, unless this executor overrides
+    /// native function costs at the same protocol version. Natives read their costs from a table
+    /// that is derived from this config once per session, when the session's extensions are built.
+    fn protocol_config<'c>(&'c self, protocol_config: &'c ProtocolConfig) -> &'c ProtocolConfig {
+        match &self.1 {
+            Some(overridden) if overridden.version == protocol_config.version => overridden,
+            _ => protocol_config,
+        }
     }
 }
 
@@ -67,10 +103,18 @@ impl<'m> Verifier<'m> {
             config,
             metrics,
             meter,
+            report: vec![],
         }
     }
 }
 
+pub(crate) fn deserialize_modules(
+    protocol_config: &ProtocolConfig,
+    module_bytes: &[Vec<u8>],
+) -> SuiResult<Vec<CompiledModule>> {
+    adapter::deserialize_modules(protocol_config, module_bytes)
+}
+
 impl executor::Executor for Executor {
     fn execute_transaction_to_effects(
         &self,
@@ -103,7 +147,7 @@ impl executor::Executor for Executor {
             &self.0,
             epoch_id,
             epoch_timestamp_ms,
-            protocol_config,
+            self.protocol_config(protocol_config),
             metrics,
             enable_expensive_checks,
             certificate_deny_set,
@@ -128,7 +172,7 @@ impl executor::Executor for Executor {
     ) -> (
         InnerTemporaryStore,
         TransactionEffects,
-        Result<Vec<ExecutionResult>, ExecutionError>,
+        Result<Vec<CommandResult>, ExecutionError>,
     ) {
         execute_transaction_to_effects::<execution_mode::DevInspect>(
             store,
@@ -141,7 +185,7 @@ impl executor::Executor for Executor {
             &self.0,
             epoch_id,
             epoch_timestamp_ms,
-            protocol_config,
+            self.protocol_config(protocol_config),
             metrics,
             enable_expensive_checks,
             certificate_deny_set,
@@ -159,7 +203,7 @@ impl executor::Executor for Executor {
     ) -> Result<InnerTemporaryStore, ExecutionError> {
         execute_genesis_state_update(
             store,
-            protocol_config,
+            self.protocol_config(protocol_config),
             metrics,
             &self.0,
             tx_context,
@@ -174,6 +218,34 @@ impl executor::Executor for Executor {
     ) -> Box<dyn LayoutResolver + 'r> {
         Box::new(TypeLayoutResolver::new(&self.0, store))
     }
+
+    fn advance_epoch_transaction(
+        &self,
+        protocol_config: &ProtocolConfig,
+        prelude: Vec<EndOfEpochTransactionKind>,
+        params: ChangeEpochParams,
+    ) -> VerifiedTransaction {
+        if protocol_config.end_of_epoch_transaction_supported() {
+            params.into_end_of_epoch_transaction(prelude)
+        } else {
+            params.into_change_epoch_transaction()
+        }
+    }
+
+    fn randomness_state_update_transaction(
+        &self,
+        epoch: EpochId,
+        randomness_round: u64,
+        random_bytes: Vec<u8>,
+        randomness_obj_initial_shared_version: SequenceNumber,
+    ) -> SuiResult<VerifiedTransaction> {
+        Ok(VerifiedTransaction::new_randomness_state_update(
+            epoch,
+            randomness_round,
+            random_bytes,
+            randomness_obj_initial_shared_version,
+        ))
+    }
 }
 
 impl<'m> verifier::Verifier for Verifier<'m> {
@@ -182,7 +254,14 @@ impl<'m> verifier::Verifier for Verifier<'m> {
         _protocol_config: &ProtocolConfig,
         modules: &[CompiledModule],
     ) -> SuiResult<()> {
-        run_metered_move_bytecode_verifier(modules, &self.config, &mut self.meter, self.metrics)
+        self.report.clear();
+        run_metered_move_bytecode_verifier_with_report(
+            modules,
+            &self.config,
+            &mut self.meter,
+            self.metrics,
+            &mut self.report,
+        )
     }
 
     fn meter_compiled_modules_with_overrides(
@@ -196,7 +275,14 @@ impl<'m> verifier::Verifier for Verifier<'m> {
         let max_per_mod_meter_current = config.max_per_mod_meter_units;
         config.max_per_fun_meter_units = config_overrides.max_per_fun_meter_units;
         config.max_per_mod_meter_units = config_overrides.max_per_mod_meter_units;
-        run_metered_move_bytecode_verifier(modules, &config, &mut self.meter, self.metrics)?;
+        self.report.clear();
+        run_metered_move_bytecode_verifier_with_report(
+            modules,
+            &config,
+            &mut self.meter,
+            self.metrics,
+            &mut self.report,
+        )?;
         let fun_meter_units_result = self.meter.get_usage(Scope::Function);
         let mod_meter_units_result = self.meter.get_usage(Scope::Function);
         Ok(VerifierMeteredValues::new(
@@ -206,4 +292,8 @@ impl<'m> verifier::Verifier for Verifier<'m> {
             mod_meter_units_result,
         ))
     }
+
+    fn metering_report(&self) -> Option<&[ModuleVerifierReport]> {
+        Some(&self.report)
+    }
 }
@@ -5,13 +5,22 @@
 
 use std::sync::Arc;
 
+use move_binary_format::CompiledModule;
+use move_core_types::{annotated_value as A, language_storage::StructTag};
 use sui_protocol_config::ProtocolConfig;
-use sui_types::{error::SuiResult, metrics::BytecodeVerifierMetrics};
+use sui_types::{
+    error::{SuiError, SuiResult},
+    execution::TypeLayoutStore,
+    metrics::BytecodeVerifierMetrics,
+};
 
-pub use executor::Executor;
+pub use executor::{ChangeEpochParams, Executor, GasEstimate, GasParameters};
 pub use verifier::Verifier;
 
 pub mod executor;
+#[cfg(feature = "native-cost-overrides")]
+pub mod native_costs;
+pub mod shadow;
 pub mod verifier;
 
 mod latest;
@@ -28,6 +37,18 @@ pub fn executor(
     silent: bool,
 ) -> SuiResult<Arc<dyn Executor + Send + Sync>> {
     let version = protocol_config.execution_version_as_option().unwrap_or(0);
+    executor_for_version(version, protocol_config, silent)
+}
+
+/// Like `executor`, but for an explicitly chosen execution `version`, rather than the one set in
+/// `protocol_config`. This is intended for running transactions under a different version of the
+/// execution layer than the network is using (e.g. to shadow execution with a new version), and
+/// the results must not be committed.
+pub fn executor_for_version(
+    version: u64,
+    protocol_config: &ProtocolConfig,
+    silent: bool,
+) -> SuiResult<Arc<dyn Executor + Send + Sync>> {
     Ok(match version {
         0 => Arc::new(v0::Executor::new(protocol_config, silent)?),
 
@@ -37,10 +58,97 @@ pub fn executor(
 
         NEXT_VM => Arc::new(next_vm::Executor::new(protocol_config, silent)?),
 
-        v => panic!("Unsupported execution version {v}"),
+        v => {
+            return Err(SuiError::UnsupportedFeatureError {
+                error: format!("Unsupported execution version {v}"),
+            })
+        }
+    })
+}
+
+/// Like `executor`, but charging for native functions according to `native_costs`, falling back to
+/// `protocol_config` for the costs of native functions that are not overridden. Fails if
+/// `native_costs` overrides a parameter that is not a native function cost at this protocol
+/// version.
+#[cfg(feature = "native-cost-overrides")]
+pub fn executor_with_native_costs(
+    protocol_config: &ProtocolConfig,
+    native_costs: &native_costs::NativeCostOverrides,
+    silent: bool,
+) -> SuiResult<Arc<dyn Executor + Send + Sync>> {
+    let version = protocol_config.execution_version_as_option().unwrap_or(0);
+    Ok(match version {
+        0 => Arc::new(v0::Executor::new_with_native_costs(
+            protocol_config,
+            native_costs,
+            silent,
+        )?),
+
+        1 => Arc::new(v1::Executor::new_with_native_costs(
+            protocol_config,
+            native_costs,
+            silent,
+        )?),
+
+        2 => Arc::new(latest::Executor::new_with_native_costs(
+            protocol_config,
+            native_costs,
+            silent,
+        )?),
+
+        NEXT_VM => Arc::new(next_vm::Executor::new_with_native_costs(
+            protocol_config,
+            native_costs,
+            silent,
+        )?),
+
+        v => {
+            return Err(SuiError::UnsupportedFeatureError {
+                error: format!("Unsupported execution version {v}"),
+            })
+        }
     })
 }
 
+/// Deserialize the modules of a package, as execution `version` would, with the binary format
+/// settings from `protocol_config`, but without verifying them. Modules that fail to deserialize
+/// here would also fail to be published or upgraded under this version and configuration.
+pub fn deserialize_modules(
+    version: u64,
+    protocol_config: &ProtocolConfig,
+    module_bytes: &[Vec<u8>],
+) -> SuiResult<Vec<CompiledModule>> {
+    match version {
+        0 => v0::deserialize_modules(protocol_config, module_bytes),
+        1 => v1::deserialize_modules(protocol_config, module_bytes),
+        2 => latest::deserialize_modules(protocol_config, module_bytes),
+        NEXT_VM => next_vm::deserialize_modules(protocol_config, module_bytes),
+        v => Err(SuiError::UnsupportedFeatureError {
+            error: format!("Unsupported execution version {v}"),
+        }),
+    }
+}
+
+/// The layout of values of type `struct_tag`, resolved against the packages in `store` exactly as
+/// execution `version` would resolve it. Addresses in `struct_tag` are expected to be the IDs of
+/// the packages that define each type, as in the types of objects.
+///
+/// This creates a new executor for every call, so it is meant for one-off lookups (tools and
+/// tests). Callers resolving many layouts -- like the RPC, which serves object layouts through
+/// `AuthorityState` -- should hold on to an executor (e.g. the epoch store's), and use its
+/// `type_layout_resolver` instead.
+pub fn type_layout(
+    version: u64,
+    protocol_config: &ProtocolConfig,
+    struct_tag: &StructTag,
+    store: Box<dyn TypeLayoutStore + '_>,
+) -> SuiResult<A::MoveTypeLayout> {
+    let executor = executor_for_version(version, protocol_config, /* silent */ true)?;
+    let mut resolver = executor.type_layout_resolver(store);
+    let layout = resolver.get_annotated_struct_layout(struct_tag)?;
+    Ok(A::MoveTypeLayout::Struct(layout))
+}
+
 pub fn verifier<'m>(
     protocol_config: &ProtocolConfig,
     is_metered: bool,
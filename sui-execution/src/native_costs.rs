@@ -0,0 +1,254 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Support for charging native functions according to a custom cost table, for experimenting with
+//! native costs on local networks and for benchmarking. Not for use in production: a validator
+//! running with overridden costs will produce different effects from the rest of the network.
+//!
+//! Overrides are applied to a copy of the protocol config once, when the executor is created (see
+//! `executor_with_native_costs`), and the executor runs transactions under that copy, so each
+//! session derives its native cost table from the overridden costs when it is set up.
+
+use std::collections::BTreeMap;
+
+use sui_protocol_config::{ProtocolConfig, ProtocolConfigValue};
+use sui_types::error::{SuiError, SuiResult};
+
+/// Costs to charge for native functions, in place of those set by the protocol config. Keyed by
+/// the name of the protocol config parameter being overridden, from its "Native Function Costs"
+/// section (e.g. `address_from_bytes_cost_base`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NativeCostOverrides(BTreeMap<String, u64>);
+
+impl NativeCostOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Charge `cost` for the native function cost parameter `param`, instead of its value in the
+    /// protocol config.
+    pub fn set(&mut self, param: impl Into<String>, cost: u64) -> &mut Self {
+        self.0.insert(param.into(), cost);
+        self
+    }
+
+    /// A copy of `protocol_config` with these overrides applied. Fails if a parameter being
+    /// overridden is not one that `protocol_config` sets to an integer, or is not one that `build`
+    /// reads when deriving the native cost table (i.e. it is not a native function cost), so that
+    /// overrides are not silently ignored (e.g. because of a typo, or because the parameter does
+    /// not exist at this protocol version), and other protocol config parameters can't be changed.
+    pub(crate) fn protocol_config<T: PartialEq>(
+        &self,
+        protocol_config: &ProtocolConfig,
+        build: impl Fn(&ProtocolConfig) -> T,
+    ) -> SuiResult<ProtocolConfig> {
+        let baseline = build(protocol_config);
+        let mut patched = protocol_config.clone();
+
+        for (param, cost) in &self.0 {
+            let Some(ProtocolConfigValue::u64(current)) =
+                protocol_config.lookup_attr(param.clone())
+            else {
+                return Err(SuiError::UnsupportedFeatureError {
+                    error: format!("Cannot override native cost parameter '{param}'"),
+                });
+            };
+
+            // Only a parameter that the cost table depends on will change it, when its value does.
+            let mut probe = protocol_config.clone();
+            probe.set_attr_for_testing(param.clone(), current.wrapping_add(1).to_string());
+            if build(&probe) == baseline {
+                return Err(SuiError::UnsupportedFeatureError {
+                    error: format!("'{param}' is not a native function cost parameter"),
+                });
+            }
+
+            patched.set_attr_for_testing(param.clone(), cost.to_string());
+        }
+
+        Ok(patched)
+    }
+}
+
+impl FromIterator<(String, u64)> for NativeCostOverrides {
+    fn from_iter<I: IntoIterator<Item = (String, u64)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, sync::Arc};
+
+    use move_core_types::{gas_algebra::InternalGas, identifier::Identifier};
+    use sui_framework::BuiltInFramework;
+    use sui_move_natives_latest::NativesCostTable;
+    use sui_types::{
+        base_types::{ObjectID, SuiAddress},
+        crypto::default_hash,
+        digests::TransactionDigest,
+        effects::TransactionEffectsAPI,
+        gas::{GasCostSummary, SuiGasStatus},
+        gas_coin::GAS,
+        in_memory_storage::InMemoryStorage,
+        metrics::LimitsMetrics,
+        object::Object,
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+        transaction::{
+            Argument, CheckedInputObjects, InputObjects, ObjectReadResult, TransactionData,
+            TransactionDataAPI,
+        },
+        SUI_FRAMEWORK_PACKAGE_ID,
+    };
+
+    use crate::Executor;
+
+    use super::*;
+
+    const GAS_PRICE: u64 = 1_000;
+    const GAS_BUDGET: u64 = 1_000_000_000;
+
+    /// Execute a transaction that splits a coin off the gas coin (creating an object, which calls
+    /// the `object::record_new_uid` native), returning the gas it was charged.
+    fn split_coin_gas_cost(executor: &dyn Executor, config: &ProtocolConfig) -> GasCostSummary {
+        let sender = SuiAddress::from(ObjectID::from_single_byte(1));
+        let gas = Object::with_id_owner_gas_for_testing(
+            ObjectID::from_single_byte(10),
+            sender,
+            10 * GAS_BUDGET,
+        );
+
+        let mut objects: Vec<_> = BuiltInFramework::iter_system_packages()
+            .map(|package| package.genesis_object())
+            .collect();
+        objects.push(gas.clone());
+        let store = InMemoryStorage::new(objects);
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let amount = builder.pure(1_000u64).unwrap();
+        let coin = builder.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            Identifier::new("coin").unwrap(),
+            Identifier::new("split").unwrap(),
+            vec![GAS::type_tag()],
+            vec![Argument::GasCoin, amount],
+        );
+        builder.transfer_arg(sender, coin);
+
+        let tx_data = TransactionData::new_programmable(
+            sender,
+            vec![gas.compute_object_reference()],
+            builder.finish(),
+            GAS_BUDGET,
+            GAS_PRICE,
+        );
+
+        let input_objects = tx_data
+            .input_objects()
+            .unwrap()
+            .into_iter()
+            .map(|kind| {
+                let object = store.get_object(&kind.object_id()).unwrap().clone();
+                ObjectReadResult::new(kind, object.into())
+            })
+            .collect();
+
+        let gas_status = SuiGasStatus::new(GAS_BUDGET, GAS_PRICE, GAS_PRICE, config).unwrap();
+        let digest = TransactionDigest::new(default_hash(&tx_data));
+        let (kind, signer, gas_coins) = tx_data.execution_parts();
+
+        let (_, effects, result) = executor.execute_transaction_to_effects(
+            &store,
+            config,
+            Arc::new(LimitsMetrics::new(&prometheus::Registry::new())),
+            /* enable_expensive_checks */ true,
+            &HashSet::new(),
+            /* epoch_id */ &0,
+            /* epoch_timestamp_ms */ 0,
+            CheckedInputObjects::new_for_replay(InputObjects::new(input_objects)),
+            gas_coins,
+            gas_status,
+            kind,
+            signer,
+            digest,
+        );
+
+        result.unwrap();
+        effects.gas_cost_summary().clone()
+    }
+
+    #[test]
+    fn overrides_patch_native_costs_only() {
+        let config = ProtocolConfig::get_for_max_version_UNSAFE();
+        let mut overrides = NativeCostOverrides::new();
+        overrides.set("address_from_bytes_cost_base", 12345);
+
+        let patched = overrides
+            .protocol_config(&config, NativesCostTable::from_protocol_config)
+            .unwrap();
+        let table = NativesCostTable::from_protocol_config(&patched);
+
+        let baseline = NativesCostTable::from_protocol_config(&config);
+        assert_eq!(
+            table
+                .address_from_bytes_cost_params
+                .address_from_bytes_cost_base,
+            InternalGas::new(12345),
+        );
+        assert!(
+            table.address_to_u256_cost_params == baseline.address_to_u256_cost_params,
+            "Parameters that are not overridden keep their protocol config values",
+        );
+    }
+
+    #[test]
+    fn unknown_parameters_are_rejected() {
+        let config = ProtocolConfig::get_for_max_version_UNSAFE();
+        let mut overrides = NativeCostOverrides::new();
+        overrides.set("address_from_bytes_cost_bsae", 1);
+
+        assert!(overrides
+            .protocol_config(&config, NativesCostTable::from_protocol_config)
+            .is_err());
+    }
+
+    #[test]
+    fn non_native_parameters_are_rejected() {
+        let config = ProtocolConfig::get_for_max_version_UNSAFE();
+        let mut overrides = NativeCostOverrides::new();
+        overrides.set("max_tx_gas", 1);
+
+        assert!(overrides
+            .protocol_config(&config, NativesCostTable::from_protocol_config)
+            .is_err());
+        assert!(crate::executor_with_native_costs(&config, &overrides, true).is_err());
+    }
+
+    #[test]
+    fn executor_with_native_costs() {
+        let config = ProtocolConfig::get_for_max_version_UNSAFE();
+        let mut overrides = NativeCostOverrides::new();
+        overrides.set("address_from_bytes_cost_base", 12345);
+
+        assert!(crate::executor_with_native_costs(&config, &overrides, true).is_ok());
+    }
+
+    #[test]
+    fn overridden_costs_change_gas_charged() {
+        let config = ProtocolConfig::get_for_max_version_UNSAFE();
+        let mut overrides = NativeCostOverrides::new();
+        overrides.set("object_record_new_uid_cost_base", 100_000_000);
+
+        let baseline = crate::executor(&config, true).unwrap();
+        let overridden = crate::executor_with_native_costs(&config, &overrides, true).unwrap();
+
+        let baseline = split_coin_gas_cost(baseline.as_ref(), &config);
+        let overridden = split_coin_gas_cost(overridden.as_ref(), &config);
+
+        assert!(
+            overridden.computation_cost > baseline.computation_cost,
+            "Overridden: {overridden:?}, baseline: {baseline:?}",
+        );
+        assert_eq!(overridden.storage_cost, baseline.storage_cost);
+    }
+}
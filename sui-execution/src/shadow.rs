@@ -0,0 +1,202 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Support for shadow execution: running a transaction under a second version of the execution
+//! layer, alongside the version the network is using, and reporting where their effects differ.
+
+use std::{collections::HashSet, fmt, sync::Arc};
+
+use sui_protocol_config::ProtocolConfig;
+use sui_types::{
+    base_types::{ObjectRef, SuiAddress},
+    committee::EpochId,
+    digests::TransactionDigest,
+    effects::{TransactionEffects, TransactionEffectsAPI},
+    error::ExecutionError,
+    gas::SuiGasStatus,
+    inner_temporary_store::InnerTemporaryStore,
+    metrics::LimitsMetrics,
+    storage::BackingStore,
+    transaction::{CheckedInputObjects, TransactionKind},
+};
+
+use crate::executor::Executor;
+
+/// The outcome of executing a transaction with a primary and a shadow executor.
+pub struct ShadowExecution {
+    /// Output of the primary executor, exactly as `Executor::execute_transaction_to_effects`
+    /// returned it. This is the output that may be committed.
+    pub primary: (
+        InnerTemporaryStore,
+        TransactionEffects,
+        Result<(), ExecutionError>,
+    ),
+    /// Effects produced by the shadow executor. Its writes are discarded.
+    pub shadow_effects: TransactionEffects,
+    /// Where the shadow executor's effects differ from the primary's (empty if they agree).
+    pub differences: Vec<EffectsDifference>,
+}
+
+/// A single field whose value differs between two sets of effects.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EffectsDifference {
+    pub field: &'static str,
+    pub primary: String,
+    pub shadow: String,
+}
+
+/// Execute a transaction with `primary`, and again with `shadow`, against the same `store`, and
+/// compare the effects they produce.
+///
+/// Execution consumes its input objects and gas status, so `prepare_inputs` is called once for
+/// each executor, to produce fresh copies of them.
+pub fn execute_with_shadow(
+    primary: &dyn Executor,
+    shadow: &dyn Executor,
+    store: &dyn BackingStore,
+    // Configuration
+    protocol_config: &ProtocolConfig,
+    metrics: Arc<LimitsMetrics>,
+    enable_expensive_checks: bool,
+    certificate_deny_set: &HashSet<TransactionDigest>,
+    // Epoch
+    epoch_id: &EpochId,
+    epoch_timestamp_ms: u64,
+    // Transaction Inputs and Gas
+    mut prepare_inputs: impl FnMut() -> (CheckedInputObjects, SuiGasStatus),
+    gas_coins: Vec<ObjectRef>,
+    // Transaction
+    transaction_kind: TransactionKind,
+    transaction_signer: SuiAddress,
+    transaction_digest: TransactionDigest,
+) -> ShadowExecution {
+    let (input_objects, gas_status) = prepare_inputs();
+    let (_, shadow_effects, _) = shadow.execute_transaction_to_effects(
+        store,
+        protocol_config,
+        metrics.clone(),
+        enable_expensive_checks,
+        certificate_deny_set,
+        epoch_id,
+        epoch_timestamp_ms,
+        input_objects,
+        gas_coins.clone(),
+        gas_status,
+        transaction_kind.clone(),
+        transaction_signer,
+        transaction_digest,
+    );
+
+    let (input_objects, gas_status) = prepare_inputs();
+    let primary = primary.execute_transaction_to_effects(
+        store,
+        protocol_config,
+        metrics,
+        enable_expensive_checks,
+        certificate_deny_set,
+        epoch_id,
+        epoch_timestamp_ms,
+        input_objects,
+        gas_coins,
+        gas_status,
+        transaction_kind,
+        transaction_signer,
+        transaction_digest,
+    );
+
+    let differences = compare_effects(&primary.1, &shadow_effects);
+    ShadowExecution {
+        primary,
+        shadow_effects,
+        differences,
+    }
+}
+
+/// Compare the parts of two sets of effects that depend on how the transaction executed,
+/// returning the fields that differ.
+pub fn compare_effects(
+    primary: &TransactionEffects,
+    shadow: &TransactionEffects,
+) -> Vec<EffectsDifference> {
+    let mut differences = vec![];
+    let mut compare = |field, p: &dyn fmt::Debug, s: &dyn fmt::Debug| {
+        let (primary, shadow) = (format!("{p:?}"), format!("{s:?}"));
+        if primary != shadow {
+            differences.push(EffectsDifference {
+                field,
+                primary,
+                shadow,
+            });
+        }
+    };
+
+    compare("status", primary.status(), shadow.status());
+    compare(
+        "gas_cost_summary",
+        primary.gas_cost_summary(),
+        shadow.gas_cost_summary(),
+    );
+    compare("created", &primary.created(), &shadow.created());
+    compare("mutated", &primary.mutated(), &shadow.mutated());
+    compare("unwrapped", &primary.unwrapped(), &shadow.unwrapped());
+    compare("deleted", &primary.deleted(), &shadow.deleted());
+    compare(
+        "unwrapped_then_deleted",
+        &primary.unwrapped_then_deleted(),
+        &shadow.unwrapped_then_deleted(),
+    );
+    compare("wrapped", &primary.wrapped(), &shadow.wrapped());
+    compare(
+        "events_digest",
+        &primary.events_digest(),
+        &shadow.events_digest(),
+    );
+    compare(
+        "dependencies",
+        &primary.dependencies(),
+        &shadow.dependencies(),
+    );
+
+    differences
+}
+
+impl fmt::Display for EffectsDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: primary = {}, shadow = {}",
+            self.field, self.primary, self.shadow
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_types::{
+        execution_status::{ExecutionFailureStatus, ExecutionStatus},
+        gas::GasCostSummary,
+    };
+
+    use super::*;
+
+    #[test]
+    fn identical_effects_have_no_differences() {
+        let effects = TransactionEffects::default();
+        assert!(compare_effects(&effects, &effects).is_empty());
+    }
+
+    #[test]
+    fn differing_fields_are_reported() {
+        let primary = TransactionEffects::default();
+        let mut shadow = primary.clone();
+        *shadow.status_mut_for_testing() =
+            ExecutionStatus::new_failure(ExecutionFailureStatus::InsufficientGas, None);
+        *shadow.gas_cost_summary_mut_for_testing() = GasCostSummary::new(1, 0, 0, 0);
+
+        let fields: Vec<_> = compare_effects(&primary, &shadow)
+            .into_iter()
+            .map(|difference| difference.field)
+            .collect();
+        assert_eq!(fields, vec!["status", "gas_cost_summary"]);
+    }
+}
@@ -3,7 +3,7 @@
 
 use move_binary_format::CompiledModule;
 use sui_protocol_config::ProtocolConfig;
-use sui_types::error::SuiResult;
+use sui_types::{error::SuiResult, metrics::ModuleVerifierReport};
 
 pub trait Verifier {
     /// Run the bytecode verifier with a meter limit
@@ -46,6 +46,13 @@ pub trait Verifier {
         protocol_config: &ProtocolConfig,
         config_overrides: &VerifierOverrides,
     ) -> SuiResult<VerifierMeteredValues>;
+
+    /// Per-module, per-pass breakdown of the time and meter ticks spent by the most recent call
+    /// to `meter_compiled_modules` (or `meter_compiled_modules_with_overrides`), for verifiers
+    /// that collect one.
+    fn metering_report(&self) -> Option<&[ModuleVerifierReport]> {
+        None
+    }
 }
 
 /// Controls verifier config values to override.
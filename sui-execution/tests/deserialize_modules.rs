@@ -0,0 +1,80 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks that every execution cut deserializes package modules, and rejects malformed packages,
+//! through the same helper it uses when publishing and upgrading.
+
+use move_binary_format::file_format::{empty_module, CompiledModule};
+use sui_execution::NEXT_VM;
+use sui_protocol_config::ProtocolConfig;
+use sui_types::error::SuiError;
+
+const VERSIONS: &[u64] = &[0, 1, 2, NEXT_VM];
+
+fn module_bytes(module: &CompiledModule) -> Vec<u8> {
+    let mut bytes = vec![];
+    module.serialize(&mut bytes).unwrap();
+    bytes
+}
+
+fn is_deserialization_failure(result: Result<Vec<CompiledModule>, SuiError>) -> bool {
+    matches!(result, Err(SuiError::ModuleDeserializationFailure { .. }))
+}
+
+#[test]
+fn test_deserialize_modules() {
+    let config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let module = empty_module();
+    let bytes = module_bytes(&module);
+
+    for version in VERSIONS {
+        let modules = sui_execution::deserialize_modules(*version, &config, &[bytes.clone()])
+            .unwrap_or_else(|e| panic!("Version {version} failed to deserialize: {e}"));
+        assert_eq!(modules, vec![module.clone()], "Version {version}");
+    }
+}
+
+#[test]
+fn test_deserialize_empty_package() {
+    let config = ProtocolConfig::get_for_max_version_UNSAFE();
+    for version in VERSIONS {
+        assert!(
+            is_deserialization_failure(sui_execution::deserialize_modules(*version, &config, &[])),
+            "Version {version}",
+        );
+    }
+}
+
+#[test]
+fn test_deserialize_malformed_module() {
+    let config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let bytes = module_bytes(&empty_module());
+
+    let truncated = bytes[..bytes.len() / 2].to_vec();
+    let mut extraneous = bytes.clone();
+    extraneous.push(0);
+
+    for version in VERSIONS {
+        for malformed in [&truncated, &extraneous] {
+            // A single malformed module fails the whole package.
+            let package = [bytes.clone(), malformed.clone()];
+            assert!(
+                is_deserialization_failure(sui_execution::deserialize_modules(
+                    *version, &config, &package,
+                )),
+                "Version {version}",
+            );
+        }
+    }
+}
+
+#[test]
+fn test_deserialize_unsupported_version() {
+    let config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let bytes = module_bytes(&empty_module());
+
+    assert!(matches!(
+        sui_execution::deserialize_modules(3, &config, &[bytes]),
+        Err(SuiError::UnsupportedFeatureError { .. }),
+    ));
+}
@@ -0,0 +1,346 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Regression suite for the effects produced by each supported execution version.
+//!
+//! A corpus of serialized transactions, each with a snapshot of the store it executes against
+//! (in `tests/effects_goldens/corpus/`), is run through every execution version that the
+//! supported protocol versions select, and the digests of the resulting effects are compared
+//! against golden files (one per execution version, in `tests/effects_goldens/`). A mismatch
+//! means that execution behaviour changed, which must not happen unintentionally for versions
+//! that are already in use.
+//!
+//! Missing goldens and corpus fixtures are failures. To record them (for a new execution version
+//! or corpus entry), or to re-record goldens after an intentional change in behaviour, run:
+//!
+//! ```sh
+//! UPDATE_GOLDENS=1 cargo nextest run -p sui-execution --test effects_goldens
+//! ```
+//!
+//! Fixtures are only ever generated if they are missing, so that the corpus stays fixed once it
+//! has been recorded.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use move_binary_format::file_format::empty_module;
+use move_core_types::identifier::Identifier;
+use serde::{Deserialize, Serialize};
+use sui_framework::BuiltInFramework;
+use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    crypto::default_hash,
+    digests::TransactionDigest,
+    effects::TransactionEffectsAPI,
+    gas::SuiGasStatus,
+    gas_coin::GAS,
+    in_memory_storage::InMemoryStorage,
+    message_envelope::Message,
+    metrics::LimitsMetrics,
+    object::{MoveObject, Object, Owner, OBJECT_START_VERSION},
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::{
+        CheckedInputObjects, InputObjects, ObjectArg, ObjectReadResult, TransactionData,
+        TransactionDataAPI,
+    },
+    MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID,
+};
+
+const GAS_PRICE: u64 = 1_000;
+const GAS_BUDGET: u64 = 50_000_000;
+const GAS_BALANCE: u64 = 1_000_000_000;
+
+/// A transaction from the corpus, along with every object it expects to find in the store,
+/// including the framework packages it was recorded against, so that changes to the framework do
+/// not change the effects recorded for execution versions that are already in use. Stored in the
+/// corpus as BCS.
+#[derive(Serialize, Deserialize)]
+struct Fixture {
+    objects: Vec<Object>,
+    tx_data: TransactionData,
+}
+
+#[test]
+fn test_effects_goldens() {
+    let update = std::env::var_os("UPDATE_GOLDENS").is_some();
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/effects_goldens");
+    let corpus_dir = dir.join("corpus");
+
+    let mut failures = vec![];
+    for (name, fixture) in generate_corpus() {
+        let path = corpus_dir.join(format!("{name}.bcs"));
+        if path.exists() {
+            continue;
+        } else if !update {
+            failures.push(format!("Missing fixture: {}", path.display()));
+            continue;
+        }
+
+        fs::create_dir_all(&corpus_dir).unwrap();
+        fs::write(&path, bcs::to_bytes(&fixture).unwrap()).unwrap();
+    }
+
+    let corpus = read_corpus(&corpus_dir);
+    assert!(
+        !corpus.is_empty(),
+        "No fixtures in {}",
+        corpus_dir.display()
+    );
+
+    for (version, protocol_config) in execution_versions() {
+        let actual = run_corpus(version, &protocol_config, &corpus);
+        let path = dir.join(format!("v{version}.txt"));
+
+        match fs::read_to_string(&path) {
+            Ok(expect) if expect == actual => {}
+            _ if update => fs::write(&path, actual).unwrap(),
+            Ok(_) => failures.push(format!("Effects differ: {}", path.display())),
+            Err(_) => failures.push(format!("Missing goldens: {}", path.display())),
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{failures:#?}\nIf this change in behaviour is intentional, re-record the goldens with \
+         UPDATE_GOLDENS=1.",
+    );
+}
+
+/// Each execution version in use by a supported protocol version, with the config of the first
+/// protocol version that uses it.
+fn execution_versions() -> BTreeMap<u64, ProtocolConfig> {
+    let mut versions = BTreeMap::new();
+    for v in ProtocolVersion::MIN.as_u64()..=ProtocolVersion::MAX.as_u64() {
+        let config = ProtocolConfig::get_for_version(ProtocolVersion::new(v), Chain::Unknown);
+        let version = config.execution_version_as_option().unwrap_or(0);
+        versions.entry(version).or_insert(config);
+    }
+
+    versions
+}
+
+/// Every fixture in the corpus, keyed (and therefore ordered) by name.
+fn read_corpus(dir: &Path) -> BTreeMap<String, Fixture> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return BTreeMap::new();
+    };
+
+    entries
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "bcs"))
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let bytes = fs::read(&path).unwrap();
+            let fixture = bcs::from_bytes(&bytes)
+                .unwrap_or_else(|e| panic!("Failed to deserialize {}: {e}", path.display()));
+            (name, fixture)
+        })
+        .collect()
+}
+
+/// Execute every transaction in the corpus under execution `version`, and summarise the results,
+/// one line per transaction.
+fn run_corpus(
+    version: u64,
+    protocol_config: &ProtocolConfig,
+    corpus: &BTreeMap<String, Fixture>,
+) -> String {
+    let executor = sui_execution::executor_for_version(version, protocol_config, true).unwrap();
+    let metrics = Arc::new(LimitsMetrics::new(&prometheus::Registry::new()));
+
+    let mut summary = String::new();
+    for (name, Fixture { objects, tx_data }) in corpus {
+        let store = InMemoryStorage::new(objects.clone());
+
+        let input_objects = tx_data
+            .input_objects()
+            .unwrap()
+            .into_iter()
+            .map(|kind| {
+                let object = store.get_object(&kind.object_id()).unwrap().clone();
+                ObjectReadResult::new(kind, object.into())
+            })
+            .collect();
+
+        let gas_status =
+            SuiGasStatus::new(GAS_BUDGET, GAS_PRICE, GAS_PRICE, protocol_config).unwrap();
+        let digest = TransactionDigest::new(default_hash(tx_data));
+        let (kind, signer, gas_coins) = tx_data.execution_parts();
+
+        let (_, effects, _) = executor.execute_transaction_to_effects(
+            &store,
+            protocol_config,
+            metrics.clone(),
+            /* enable_expensive_checks */ true,
+            &HashSet::new(),
+            /* epoch_id */ &0,
+            /* epoch_timestamp_ms */ 0,
+            CheckedInputObjects::new_for_replay(InputObjects::new(input_objects)),
+            gas_coins,
+            gas_status,
+            kind,
+            signer,
+            digest,
+        );
+
+        let status = if effects.status().is_ok() {
+            "success"
+        } else {
+            "failure"
+        };
+
+        summary += &format!("{name}: {status} {}\n", effects.digest());
+    }
+
+    summary
+}
+
+/// Build the fixtures that the corpus is recorded from. Object IDs and addresses are fixed, so
+/// that the corpus (and therefore the effects it produces) is deterministic.
+fn generate_corpus() -> Vec<(&'static str, Fixture)> {
+    let sender = address(1);
+    let recipient = address(2);
+
+    let gas =
+        Object::with_id_owner_gas_for_testing(ObjectID::from_single_byte(10), sender, GAS_BALANCE);
+    let coin =
+        Object::with_id_owner_gas_for_testing(ObjectID::from_single_byte(11), sender, GAS_BALANCE);
+    let shared = Object::new_move(
+        MoveObject::new_gas_coin(
+            OBJECT_START_VERSION,
+            ObjectID::from_single_byte(12),
+            GAS_BALANCE,
+        ),
+        Owner::Shared {
+            initial_shared_version: OBJECT_START_VERSION,
+        },
+        TransactionDigest::genesis_marker(),
+    );
+
+    let gas_ref = gas.compute_object_reference();
+    let coin_ref = coin.compute_object_reference();
+
+    let module = {
+        let mut bytes = vec![];
+        empty_module().serialize(&mut bytes).unwrap();
+        bytes
+    };
+
+    // Every transaction in the corpus only depends on the Move standard library and the Sui
+    // framework.
+    let framework: Vec<_> = [MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID]
+        .iter()
+        .map(|id| BuiltInFramework::get_package_by_id(id).genesis_object())
+        .collect();
+
+    let fixture = |objects: &[&Object], build: &dyn Fn(&mut ProgrammableTransactionBuilder)| {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        build(&mut builder);
+        Fixture {
+            objects: framework
+                .iter()
+                .chain(objects.iter().copied())
+                .cloned()
+                .collect(),
+            tx_data: TransactionData::new_programmable(
+                sender,
+                vec![gas_ref],
+                builder.finish(),
+                GAS_BUDGET,
+                GAS_PRICE,
+            ),
+        }
+    };
+
+    // Split `amount` off `coin` by calling into the framework, and send it to the recipient.
+    let split = |b: &mut ProgrammableTransactionBuilder, coin: ObjectArg, amount: u64| {
+        let coin = b.obj(coin).unwrap();
+        let amount = b.pure(amount).unwrap();
+        let split = b.programmable_move_call(
+            SUI_FRAMEWORK_PACKAGE_ID,
+            Identifier::new("coin").unwrap(),
+            Identifier::new("split").unwrap(),
+            vec![GAS::type_tag()],
+            vec![coin, amount],
+        );
+        b.transfer_arg(recipient, split);
+    };
+
+    vec![
+        (
+            "transfer_gas_coin",
+            fixture(&[&gas], &|b| b.transfer_sui(recipient, None)),
+        ),
+        (
+            "split_and_transfer_gas_coin",
+            fixture(&[&gas], &|b| b.transfer_sui(recipient, Some(1_000))),
+        ),
+        (
+            "pay_multiple_recipients",
+            fixture(&[&gas], &|b| {
+                b.pay_sui(vec![recipient, address(3)], vec![1_000, 2_000])
+                    .unwrap()
+            }),
+        ),
+        (
+            "pay_from_coin",
+            fixture(&[&gas, &coin], &|b| {
+                b.pay(vec![coin_ref], vec![recipient], vec![500]).unwrap()
+            }),
+        ),
+        (
+            "transfer_object",
+            fixture(&[&gas, &coin], &|b| {
+                b.transfer_object(recipient, coin_ref).unwrap()
+            }),
+        ),
+        (
+            "insufficient_balance",
+            fixture(&[&gas], &|b| {
+                b.transfer_sui(recipient, Some(GAS_BALANCE * 2))
+            }),
+        ),
+        (
+            "move_call",
+            fixture(&[&gas, &coin], &|b| {
+                split(b, ObjectArg::ImmOrOwnedObject(coin_ref), 500)
+            }),
+        ),
+        (
+            "move_call_abort",
+            fixture(&[&gas, &coin], &|b| {
+                split(b, ObjectArg::ImmOrOwnedObject(coin_ref), GAS_BALANCE * 2)
+            }),
+        ),
+        (
+            "publish",
+            fixture(&[&gas], &|b| {
+                let cap = b.publish_upgradeable(
+                    vec![module.clone()],
+                    vec![MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID],
+                );
+                b.transfer_arg(sender, cap);
+            }),
+        ),
+        (
+            "shared_object",
+            fixture(&[&gas, &shared], &|b| {
+                let shared = ObjectArg::SharedObject {
+                    id: shared.id(),
+                    initial_shared_version: OBJECT_START_VERSION,
+                    mutable: true,
+                };
+                split(b, shared, 500)
+            }),
+        ),
+    ]
+}
+
+fn address(byte: u8) -> SuiAddress {
+    SuiAddress::from(ObjectID::from_single_byte(byte))
+}
@@ -0,0 +1,98 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks that every protocol version's executor builds the system transactions that its version
+//! of the execution layer is able to run.
+
+use sui_execution::ChangeEpochParams;
+use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
+use sui_types::{
+    base_types::SequenceNumber,
+    gas::GasCostSummary,
+    transaction::{EndOfEpochTransactionKind, TransactionDataAPI, TransactionKind},
+};
+
+fn protocol_configs() -> impl Iterator<Item = ProtocolConfig> {
+    (ProtocolVersion::MIN.as_u64()..=ProtocolVersion::MAX.as_u64())
+        .map(|v| ProtocolConfig::get_for_version(ProtocolVersion::new(v), Chain::Unknown))
+}
+
+fn change_epoch_params(config: &ProtocolConfig) -> ChangeEpochParams {
+    ChangeEpochParams {
+        next_epoch: 1,
+        next_protocol_version: config.version,
+        gas_cost_summary: GasCostSummary::new(100, 200, 50, 10),
+        epoch_start_timestamp_ms: 1_000,
+        system_packages: vec![],
+    }
+}
+
+#[test]
+fn test_advance_epoch_transaction() {
+    for config in protocol_configs() {
+        let version = config.version.as_u64();
+        let executor = sui_execution::executor(&config, true).unwrap();
+        let prelude = vec![EndOfEpochTransactionKind::new_authenticator_state_create()];
+        let tx = executor.advance_epoch_transaction(&config, prelude, change_epoch_params(&config));
+
+        match tx.data().intent_message().value.kind() {
+            TransactionKind::ChangeEpoch(change_epoch) => {
+                assert!(
+                    !config.end_of_epoch_transaction_supported(),
+                    "Version {version} should end the epoch with an end-of-epoch transaction",
+                );
+                assert_eq!(change_epoch.epoch, 1, "Version {version}");
+                assert_eq!(change_epoch.computation_charge, 100, "Version {version}");
+            }
+
+            TransactionKind::EndOfEpochTransaction(txns) => {
+                assert!(
+                    config.end_of_epoch_transaction_supported(),
+                    "Version {version} should end the epoch with a change epoch transaction",
+                );
+
+                // The prelude runs first, and the epoch changes last.
+                let [EndOfEpochTransactionKind::AuthenticatorStateCreate, EndOfEpochTransactionKind::ChangeEpoch(change_epoch)] =
+                    txns.as_slice()
+                else {
+                    panic!("Version {version}: Unexpected end-of-epoch transactions {txns:?}");
+                };
+
+                assert_eq!(change_epoch.epoch, 1, "Version {version}");
+                assert_eq!(change_epoch.computation_charge, 100, "Version {version}");
+            }
+
+            kind => panic!("Version {version}: Unexpected transaction kind {kind:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_randomness_state_update_transaction() {
+    for config in protocol_configs() {
+        let version = config.version.as_u64();
+        let executor = sui_execution::executor(&config, true).unwrap();
+        let tx = executor.randomness_state_update_transaction(
+            0,
+            1,
+            vec![42; 32],
+            SequenceNumber::from_u64(1),
+        );
+
+        // Only the execution layer from version 2 onwards can run randomness state updates.
+        if config.execution_version_as_option().unwrap_or(0) < 2 {
+            assert!(tx.is_err(), "Version {version}");
+            continue;
+        }
+
+        let tx = tx.unwrap_or_else(|e| panic!("Version {version}: {e}"));
+        let TransactionKind::RandomnessStateUpdate(update) =
+            tx.data().intent_message().value.kind()
+        else {
+            panic!("Version {version}: Expected a randomness state update");
+        };
+
+        assert_eq!(update.randomness_round, 1, "Version {version}");
+        assert_eq!(update.random_bytes, vec![42; 32], "Version {version}");
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks that every execution cut resolves the layouts of framework types, and that they agree
+//! on them.
+
+use move_core_types::annotated_value as A;
+use sui_execution::NEXT_VM;
+use sui_framework::BuiltInFramework;
+use sui_protocol_config::ProtocolConfig;
+use sui_types::{gas_coin::GasCoin, in_memory_storage::InMemoryStorage};
+
+const VERSIONS: &[u64] = &[0, 1, 2, NEXT_VM];
+
+#[test]
+fn test_framework_type_layout() {
+    let config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let store = InMemoryStorage::new(BuiltInFramework::genesis_objects().collect());
+    let coin = GasCoin::type_();
+
+    let mut layouts = vec![];
+    for version in VERSIONS {
+        let layout = sui_execution::type_layout(*version, &config, &coin, Box::new(&store))
+            .unwrap_or_else(|e| panic!("Version {version} failed to resolve {coin}: {e}"));
+
+        let A::MoveTypeLayout::Struct(struct_layout) = &layout else {
+            panic!("Version {version}: Expected a struct layout, got {layout:?}");
+        };
+
+        assert_eq!(struct_layout.type_, coin, "Version {version}");
+        let fields: Vec<_> = struct_layout
+            .fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(fields, vec!["id", "balance"], "Version {version}");
+
+        layouts.push(bcs::to_bytes(&layout).unwrap());
+    }
+
+    assert!(
+        layouts.windows(2).all(|w| w[0] == w[1]),
+        "Versions disagree on the layout of {coin}",
+    );
+}
+
+#[test]
+fn test_missing_type_layout() {
+    let config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let store = InMemoryStorage::new(vec![]);
+    let coin = GasCoin::type_();
+
+    // Without the framework packages, there is nothing to resolve the type against.
+    for version in VERSIONS {
+        assert!(
+            sui_execution::type_layout(*version, &config, &coin, Box::new(&store)).is_err(),
+            "Version {version}",
+        );
+    }
+}
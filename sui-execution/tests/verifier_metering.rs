@@ -0,0 +1,130 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks the per-pass breakdown that the metered verifier reports for each module it verifies.
+
+use std::sync::Arc;
+
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{empty_module, ModuleHandleIndex},
+};
+use sui_protocol_config::ProtocolConfig;
+use sui_types::metrics::{BytecodeVerifierMetrics, VerifierOutcome};
+
+const MOVE_PASSES: &[&str] = &[
+    "bounds",
+    "limits",
+    "duplication",
+    "signatures",
+    "instruction_consistency",
+    "constants",
+    "friends",
+    "ability_field_requirements",
+    "recursive_struct_defs",
+    "instantiation_loops",
+    "code_units",
+    "script_signature",
+];
+
+const SUI_PASSES: &[&str] = &[
+    "struct_with_key",
+    "global_storage_access",
+    "id_leak",
+    "private_generics",
+    "entry_points",
+    "one_time_witness",
+];
+
+fn metrics() -> Arc<BytecodeVerifierMetrics> {
+    Arc::new(BytecodeVerifierMetrics::new(&prometheus::Registry::new()))
+}
+
+#[test]
+fn test_report_covers_every_pass() {
+    let config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let metrics = metrics();
+    let mut verifier = sui_execution::verifier(&config, /* is_metered */ true, &metrics);
+
+    let module = empty_module();
+    verifier
+        .meter_compiled_modules(&config, &[module.clone()])
+        .unwrap();
+
+    let report = verifier
+        .metering_report()
+        .expect("Latest execution version reports on metering");
+    assert_eq!(report.len(), 1);
+
+    let report = &report[0];
+    assert_eq!(report.module, Some(module.self_id()));
+    assert_eq!(report.outcome, VerifierOutcome::Success);
+
+    let passes: Vec<_> = report.passes.iter().map(|p| p.pass).collect();
+    let expected: Vec<_> = MOVE_PASSES.iter().chain(SUI_PASSES).copied().collect();
+    assert_eq!(passes, expected);
+
+    // An empty module has no code to charge ticks for.
+    assert!(report.passes.iter().all(|p| p.ticks == 0));
+
+    for pass in &expected {
+        let latency = metrics
+            .verifier_runtime_per_pass_latency
+            .with_label_values(&[*pass]);
+        assert_eq!(latency.get_sample_count(), 1, "Pass {pass} not observed");
+    }
+
+    assert_eq!(
+        metrics
+            .verifier_module_outcome
+            .with_label_values(&[BytecodeVerifierMetrics::SUCCESS_TAG])
+            .get(),
+        1,
+    );
+}
+
+#[test]
+fn test_report_stops_at_failing_pass() {
+    let config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let metrics = metrics();
+    let mut verifier = sui_execution::verifier(&config, /* is_metered */ true, &metrics);
+
+    // Points at a module handle that does not exist, so the module fails bounds checks.
+    let mut module = empty_module();
+    module.self_module_handle_idx = ModuleHandleIndex(1);
+
+    // Failures other than timeouts are not reported as errors by the metered verifier.
+    verifier.meter_compiled_modules(&config, &[module]).unwrap();
+
+    let report = verifier.metering_report().unwrap();
+    assert_eq!(report.len(), 1);
+    assert_eq!(report[0].module, None);
+    assert_eq!(report[0].outcome, VerifierOutcome::Failure);
+
+    let passes: Vec<_> = report[0].passes.iter().map(|p| p.pass).collect();
+    assert_eq!(passes, vec!["bounds"]);
+
+    assert_eq!(
+        metrics
+            .verifier_module_outcome
+            .with_label_values(&[BytecodeVerifierMetrics::FAILURE_TAG])
+            .get(),
+        1,
+    );
+}
+
+#[test]
+fn test_report_resets_between_calls() {
+    let config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let metrics = metrics();
+    let mut verifier = sui_execution::verifier(&config, /* is_metered */ true, &metrics);
+
+    let module = empty_module();
+    verifier
+        .meter_compiled_modules(&config, &[module.clone(), module.clone()])
+        .unwrap();
+    assert_eq!(verifier.metering_report().unwrap().len(), 2);
+
+    verifier.meter_compiled_modules(&config, &[module]).unwrap();
+    assert_eq!(verifier.metering_report().unwrap().len(), 1);
+}
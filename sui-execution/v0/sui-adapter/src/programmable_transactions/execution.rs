@@ -117,6 +117,10 @@ mod checked {
         mode_results: &mut Mode::ExecutionResults,
         command: Command,
     ) -> Result<(), ExecutionError> {
+        let gas_used_before = context
+            .gas_charger
+            .move_gas_status()
+            .gas_used_pre_gas_price();
         let mut argument_updates = Mode::empty_arguments();
         let results = match command {
             Command::MakeMoveVec(tag_opt, args) if args.is_empty() => {
@@ -314,7 +318,12 @@ mod checked {
             }
         };
 
-        Mode::finish_command(context, mode_results, argument_updates, &results)?;
+        let gas_used = context
+            .gas_charger
+            .move_gas_status()
+            .gas_used_pre_gas_price()
+            .saturating_sub(gas_used_before);
+        Mode::finish_command(context, mode_results, argument_updates, &results, gas_used)?;
         context.push_command_results(results)?;
         Ok(())
     }
@@ -43,7 +43,13 @@ impl<'state, 'vm> LayoutResolver for TypeLayoutResolver<'state, 'vm> {
         &mut self,
         object: &MoveObject,
     ) -> Result<A::MoveStructLayout, SuiError> {
-        let struct_tag: StructTag = object.type_().clone().into();
+        self.get_annotated_struct_layout(&object.type_().clone().into())
+    }
+
+    fn get_annotated_struct_layout(
+        &mut self,
+        struct_tag: &StructTag,
+    ) -> Result<A::MoveStructLayout, SuiError> {
         let type_tag: TypeTag = TypeTag::from(struct_tag.clone());
         let Ok(ty) = load_type(&mut self.session, &type_tag) else {
             return Err(SuiError::FailObjectLayout {
@@ -12,7 +12,7 @@ use smallvec::smallvec;
 use std::collections::VecDeque;
 
 const E_ADDRESS_PARSE_ERROR: u64 = 0;
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct AddressFromBytesCostParams {
     /// addresses are constant size, so base cost suffices
     pub address_from_bytes_cost_base: InternalGas,
@@ -51,7 +51,7 @@ pub fn from_bytes(
         Err(_) => NativeResult::err(cost, E_ADDRESS_PARSE_ERROR),
     })
 }
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct AddressToU256CostParams {
     /// addresses and u256 are constant size, so base cost suffices
     pub address_to_u256_cost_base: InternalGas,
@@ -90,7 +90,7 @@ pub fn to_u256(
     Ok(NativeResult::ok(context.gas_used(), smallvec![u256_val]))
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct AddressFromU256CostParams {
     /// addresses and u256 are constant size, so base cost suffices
     pub address_from_u256_cost_base: InternalGas,
@@ -16,7 +16,7 @@ use std::collections::VecDeque;
 
 const HMAC_SHA3_256_BLOCK_SIZE: usize = 136;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct HmacHmacSha3256CostParams {
     /// Base cost for invoking the `hmac_sha3_256` function
     pub hmac_hmac_sha3_256_cost_base: InternalGas,
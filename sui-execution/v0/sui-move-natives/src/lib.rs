@@ -59,7 +59,7 @@ mod tx_context;
 mod types;
 mod validator;
 
-#[derive(Tid)]
+#[derive(PartialEq, Tid)]
 pub struct NativesCostTable {
     // Address natives
     pub address_from_bytes_cost_params: AddressFromBytesCostParams,
@@ -14,7 +14,7 @@ use move_vm_types::{
 use smallvec::smallvec;
 use std::collections::VecDeque;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct BorrowUidCostParams {
     pub object_borrow_uid_cost_base: InternalGas,
 }
@@ -46,7 +46,7 @@ pub fn borrow_uid(
     Ok(NativeResult::ok(context.gas_used(), smallvec![id_field]))
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct DeleteImplCostParams {
     pub object_delete_impl_cost_base: InternalGas,
 }
@@ -83,7 +83,7 @@ pub fn delete_impl(
     Ok(NativeResult::ok(context.gas_used(), smallvec![]))
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct RecordNewIdCostParams {
     pub object_record_new_uid_cost_base: InternalGas,
 }
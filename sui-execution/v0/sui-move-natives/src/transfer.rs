@@ -18,7 +18,7 @@ use sui_types::{base_types::SequenceNumber, object::Owner};
 
 const E_SHARED_NON_NEW_OBJECT: u64 = 0;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TransferInternalCostParams {
     pub transfer_transfer_internal_cost_base: InternalGas,
 }
@@ -55,7 +55,7 @@ pub fn transfer_internal(
     Ok(NativeResult::ok(context.gas_used(), smallvec![]))
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TransferFreezeObjectCostParams {
     pub transfer_freeze_object_cost_base: InternalGas,
 }
@@ -90,7 +90,7 @@ pub fn freeze_object(
     Ok(NativeResult::ok(context.gas_used(), smallvec![]))
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TransferShareObjectCostParams {
     pub transfer_share_object_cost_base: InternalGas,
 }
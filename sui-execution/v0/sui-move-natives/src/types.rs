@@ -29,7 +29,7 @@ pub(crate) fn is_otw_struct(struct_layout: &MoveStructLayout, type_tag: &TypeTag
         TypeTag::Struct(struct_tag) if has_one_bool_field && struct_tag.name.to_string() == struct_tag.module.to_string().to_ascii_uppercase())
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct TypesIsOneTimeWitnessCostParams {
     pub types_is_one_time_witness_cost_base: InternalGas,
     pub types_is_one_time_witness_type_tag_cost_per_byte: InternalGas,
@@ -20,7 +20,7 @@ use crate::NativesCostTable;
 
 const BLS12381_BLOCK_SIZE: usize = 64;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct Bls12381Bls12381MinSigVerifyCostParams {
     /// Base cost for invoking the `bls12381_min_sig_verify` function
     pub bls12381_bls12381_min_sig_verify_cost_base: InternalGas,
@@ -100,7 +100,7 @@ pub fn bls12381_min_sig_verify(
     ))
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct Bls12381Bls12381MinPkVerifyCostParams {
     /// Base cost for invoking the `bls12381_min_sig_verify` function
     pub bls12381_bls12381_min_pk_verify_cost_base: InternalGas,
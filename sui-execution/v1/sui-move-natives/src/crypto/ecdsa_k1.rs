@@ -31,7 +31,7 @@ pub const SHA256: u8 = 1;
 const KECCAK256_BLOCK_SIZE: usize = 136;
 const SHA256_BLOCK_SIZE: usize = 64;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct EcdsaK1EcrecoverCostParams {
     /// Base cost for invoking the `ecrecover` function with `hash=0` implying KECCAK256
     pub ecdsa_k1_ecrecover_keccak256_cost_base: InternalGas,
@@ -136,7 +136,7 @@ pub fn ecrecover(
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct EcdsaK1DecompressPubkeyCostParams {
     pub ecdsa_k1_decompress_pubkey_cost_base: InternalGas,
 }
@@ -177,7 +177,7 @@ pub fn decompress_pubkey(
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct EcdsaK1Secp256k1VerifyCostParams {
     /// Base cost for invoking the `secp256k1_verify` function with `hash=0` implying KECCAK256
     pub ecdsa_k1_secp256k1_verify_keccak256_cost_base: InternalGas,
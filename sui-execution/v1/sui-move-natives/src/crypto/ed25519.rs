@@ -19,7 +19,7 @@ use std::collections::VecDeque;
 
 const ED25519_BLOCK_SIZE: usize = 128;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct Ed25519VerifyCostParams {
     /// Base cost for invoking the `ed25519_verify` function
     pub ed25519_ed25519_verify_cost_base: InternalGas,
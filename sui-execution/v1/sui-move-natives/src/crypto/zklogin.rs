@@ -18,7 +18,7 @@ use std::collections::VecDeque;
 
 pub const INVALID_INPUT: u64 = 0;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct CheckZkloginIdCostParams {
     /// Base cost for invoking the `check_zklogin_id` function
     pub check_zklogin_id_cost_base: Option<InternalGas>,
@@ -123,7 +123,7 @@ fn check_id_internal(
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct CheckZkloginIssuerCostParams {
     /// Base cost for invoking the `check_zklogin_issuer` function
     pub check_zklogin_issuer_cost_base: Option<InternalGas>,
@@ -12,7 +12,7 @@ use smallvec::smallvec;
 use std::collections::VecDeque;
 use sui_types::error::VMMemoryLimitExceededSubStatusCode;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EventEmitCostParams {
     pub event_emit_cost_base: InternalGas,
     pub event_emit_value_size_derivation_cost_per_byte: InternalGas,